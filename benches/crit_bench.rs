@@ -2,9 +2,9 @@ use std::fs::File;
 use std::io::{BufRead, BufReader};
 
 use criterion::{criterion_group, criterion_main, Criterion, Throughput};
-use serde_json_borrow::OwnedValue;
+use serde_json_borrow::{from_str_with_scratch, OwnedValue, Scratch, Value};
 
-pub fn bench_for_lines<'a, F, I>(
+pub fn bench_for_lines<F, I>(
     c: &mut Criterion,
     iter_gen: F,
     group_name: &str,
@@ -26,6 +26,29 @@ pub fn bench_for_lines<'a, F, I>(
         })
     });
 
+    group.bench_function("serde-json-borrowed", |b| {
+        b.iter(|| {
+            let mut len = 0;
+            for line in iter_gen() {
+                let json: Value = serde_json::from_str(&line).unwrap();
+                len += json.len().unwrap_or(0);
+            }
+            len
+        })
+    });
+
+    group.bench_function("serde-json-borrowed-scratch", |b| {
+        let mut scratch = Scratch::new();
+        b.iter(|| {
+            let mut len = 0;
+            for line in iter_gen() {
+                let json = from_str_with_scratch(&line, &mut scratch).unwrap();
+                len += json.len().unwrap_or(0);
+            }
+            len
+        })
+    });
+
     group.bench_function("serde-json-borrowed-ownedvalue", |b| {
         b.iter(|| {
             let mut val = None;
@@ -0,0 +1,239 @@
+use core::cmp::Ordering;
+
+use crate::Value;
+
+impl<'ctx> Value<'ctx> {
+    /// If the Value is an array, appends `value` to it.
+    ///
+    /// Returns `value` back as the error if the Value is not an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"[1]"#).unwrap();
+    ///
+    /// data.push(Value::Str("a".into())).unwrap();
+    /// assert_eq!(data.to_string(), r#"[1,"a"]"#);
+    /// assert_eq!(Value::Null.push(Value::Bool(true)), Err(Value::Bool(true)));
+    /// ```
+    pub fn push(&mut self, value: Value<'ctx>) -> Result<(), Value<'ctx>> {
+        match self {
+            Value::Array(arr) => {
+                arr.push(value);
+                Ok(())
+            }
+            _ => Err(value),
+        }
+    }
+
+    /// If the Value is an array, removes its last element and returns it.
+    ///
+    /// Returns `None` if the array is empty or the Value is not an array.
+    pub fn pop(&mut self) -> Option<Value<'ctx>> {
+        match self {
+            Value::Array(arr) => arr.pop(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an array, inserts `value` at position `index`, shifting all elements after
+    /// it to the right.
+    ///
+    /// Returns `value` back as the error if the Value is not an array or `index` is greater than
+    /// the array's length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"[1, 3]"#).unwrap();
+    ///
+    /// data.insert_at(1, Value::Number(2u64.into())).unwrap();
+    /// assert_eq!(data.to_string(), "[1,2,3]");
+    /// assert!(data.insert_at(4, Value::Null).is_err());
+    /// ```
+    pub fn insert_at(&mut self, index: usize, value: Value<'ctx>) -> Result<(), Value<'ctx>> {
+        match self {
+            Value::Array(arr) if index <= arr.len() => {
+                arr.insert(index, value);
+                Ok(())
+            }
+            _ => Err(value),
+        }
+    }
+
+    /// If the Value is an array, removes the element at position `index` and returns it, shifting
+    /// all elements after it to the left.
+    ///
+    /// Returns `None` if `index` is out of bounds or the Value is not an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"["a", "b", "c"]"#).unwrap();
+    ///
+    /// assert_eq!(data.remove_at(1), Some(Value::Str("b".into())));
+    /// assert_eq!(data.remove_at(2), None);
+    /// assert_eq!(data.to_string(), r#"["a","c"]"#);
+    /// ```
+    pub fn remove_at(&mut self, index: usize) -> Option<Value<'ctx>> {
+        match self {
+            Value::Array(arr) if index < arr.len() => Some(arr.remove(index)),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an array, retains only the elements for which `f` returns true.
+    ///
+    /// The order of the retained elements is preserved. Does nothing if the Value is not an array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"[1, null, "a", null]"#).unwrap();
+    ///
+    /// data.retain_array(|value| !value.is_null());
+    /// assert_eq!(data.to_string(), r#"[1,"a"]"#);
+    /// ```
+    pub fn retain_array<F>(&mut self, f: F)
+    where F: FnMut(&Value<'ctx>) -> bool {
+        if let Value::Array(arr) = self {
+            arr.retain(f);
+        }
+    }
+
+    /// If the Value is an array, sorts its elements with the comparator function `compare`.
+    /// Does nothing otherwise.
+    ///
+    /// The sort is stable, so equal elements keep their order. Nested arrays are not sorted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"[{"id": 2}, {"id": 3}, {"id": 1}]"#).unwrap();
+    ///
+    /// data.sort_array_by(|a, b| a.get("id").cmp(b.get("id")));
+    /// assert_eq!(data.to_string(), r#"[{"id":1},{"id":2},{"id":3}]"#);
+    /// ```
+    pub fn sort_array_by<F>(&mut self, compare: F)
+    where F: FnMut(&Value<'ctx>, &Value<'ctx>) -> Ordering {
+        if let Value::Array(arr) = self {
+            arr.sort_by(compare);
+        }
+    }
+
+    /// Recursively sorts the elements of all arrays, including arrays nested in objects, by the
+    /// total order of `Value`.
+    ///
+    /// Nested arrays are sorted before the array containing them, so arrays which only differ in
+    /// the order of elements at any depth end up equal. This is useful to get a canonical form of
+    /// documents whose arrays are really sets. Object entries are not reordered, see
+    /// [`sort_keys`](Value::sort_keys) for that.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut a: Value = serde_json::from_str(r#"{"tags": ["b", "a"], "ids": [[3, 1], [2]]}"#).unwrap();
+    /// let mut b: Value = serde_json::from_str(r#"{"tags": ["a", "b"], "ids": [[2], [1, 3]]}"#).unwrap();
+    ///
+    /// a.sort_all_arrays();
+    /// b.sort_all_arrays();
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.to_string(), r#"{"tags":["a","b"],"ids":[[1,3],[2]]}"#);
+    /// ```
+    pub fn sort_all_arrays(&mut self) {
+        match self {
+            Value::Array(arr) => {
+                arr.iter_mut().for_each(Value::sort_all_arrays);
+                arr.sort();
+            }
+            Value::Object(obj) => obj.iter_mut().for_each(|(_, v)| v.sort_all_arrays()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn array_mutation_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"list": []}"#)?;
+        let list = data.get_mut("list").unwrap();
+
+        list.push(Value::Number(1u64.into())).unwrap();
+        list.push(Value::Number(3u64.into())).unwrap();
+        list.insert_at(0, Value::Null).unwrap();
+        list.insert_at(3, Value::Bool(true)).unwrap();
+        list.insert_at(2, Value::Number(2u64.into())).unwrap();
+        assert_eq!(data.to_string(), r#"{"list":[null,1,2,3,true]}"#);
+
+        let list = data.get_mut("list").unwrap();
+        assert_eq!(list.pop(), Some(Value::Bool(true)));
+        assert_eq!(list.remove_at(0), Some(Value::Null));
+        assert_eq!(list.remove_at(3), None);
+        assert_eq!(data.to_string(), r#"{"list":[1,2,3]}"#);
+
+        let mut empty = Value::Array(Vec::new());
+        assert_eq!(empty.pop(), None);
+        assert_eq!(empty.remove_at(0), None);
+
+        assert_eq!(data.push(Value::Null), Err(Value::Null));
+        assert_eq!(data.insert_at(0, Value::Null), Err(Value::Null));
+        assert_eq!(data.pop(), None);
+        assert_eq!(data.remove_at(0), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn retain_array_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"[1, 2, 3, 4, {"a": 5}]"#)?;
+        data.retain_array(|value| !matches!(value.as_u64(), Some(n) if n % 2 == 1));
+        assert_eq!(data.to_string(), r#"[2,4,{"a":5}]"#);
+
+        let mut obj: Value = serde_json::from_str(r#"{"a": 1}"#)?;
+        obj.retain_array(|_| false);
+        assert_eq!(obj.to_string(), r#"{"a":1}"#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort_arrays_test() -> io::Result<()> {
+        let mut data: Value =
+            serde_json::from_str(r#"[3, "a", null, [2, 1], 1.5, true, {"k": [1, 0]}]"#)?;
+        let mut shallow = data.clone();
+        shallow.sort_array_by(|a, b| b.cmp(a));
+        assert_eq!(
+            shallow.to_string(),
+            r#"[{"k":[1,0]},[2,1],"a",3,1.5,true,null]"#
+        );
+
+        data.sort_all_arrays();
+        assert_eq!(
+            data.to_string(),
+            r#"[null,true,1.5,3,"a",[1,2],{"k":[0,1]}]"#
+        );
+
+        let mut obj: Value = serde_json::from_str(r#"{"b": 1, "a": 2}"#)?;
+        obj.sort_array_by(|a, b| a.cmp(b));
+        obj.sort_all_arrays();
+        assert_eq!(obj.to_string(), r#"{"b":1,"a":2}"#);
+        Ok(())
+    }
+}
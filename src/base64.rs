@@ -0,0 +1,127 @@
+//! Strict decoding of standard base64, behind the `base64` feature.
+//!
+//! The decoder is implemented here instead of depending on the `base64` crate, whose configurable
+//! engines are not needed for a single strict, decode-only variant. This keeps the crate's only
+//! dependencies `serde` and `serde_json`, and works with `no_std`.
+
+use alloc::vec::Vec;
+
+use crate::Value;
+
+impl<'ctx> Value<'ctx> {
+    /// If the Value is a string, decodes it as standard base64 with padding, as defined in
+    /// [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-4). Requires the `base64`
+    /// feature.
+    ///
+    /// Returns None if the Value is not a string or not valid base64. The decoding is strict:
+    /// whitespace, missing padding, the URL-safe alphabet and non-zero trailing bits are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"blob": "aGVsbG8=", "bad": "aGVsbG8"}"#).unwrap();
+    /// assert_eq!(data.get("blob").as_base64_bytes().unwrap(), b"hello");
+    /// assert_eq!(data.get("bad").as_base64_bytes(), None);
+    /// assert_eq!(data.get("missing").as_base64_bytes(), None);
+    /// ```
+    pub fn as_base64_bytes(&self) -> Option<Vec<u8>> {
+        decode(self.as_str()?)
+    }
+}
+
+/// Decodes standard base64 with padding, returning None for invalid input.
+fn decode(text: &str) -> Option<Vec<u8>> {
+    let bytes = text.as_bytes();
+    let chunks = bytes.chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return None;
+    }
+    let mut out = Vec::with_capacity(bytes.len() / 4 * 3);
+    let mut chunks = chunks.peekable();
+    while let Some(chunk) = chunks.next() {
+        let padding = if chunks.peek().is_none() {
+            chunk.iter().rev().take_while(|&&b| b == b'=').count()
+        } else {
+            0
+        };
+        if padding > 2 {
+            return None;
+        }
+        let mut n = 0u32;
+        for &b in &chunk[..4 - padding] {
+            n = n << 6 | decode_digit(b)? as u32;
+        }
+        n <<= 6 * padding;
+        // The bits which are not part of a decoded byte must be zero.
+        if n & ((1 << (8 * padding)) - 1) != 0 {
+            return None;
+        }
+        out.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Some(out)
+}
+
+fn decode_digit(b: u8) -> Option<u8> {
+    match b {
+        b'A'..=b'Z' => Some(b - b'A'),
+        b'a'..=b'z' => Some(b - b'a' + 26),
+        b'0'..=b'9' => Some(b - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_test() {
+        // Test vectors from RFC 4648, section 10.
+        let cases = [
+            ("", ""),
+            ("Zg==", "f"),
+            ("Zm8=", "fo"),
+            ("Zm9v", "foo"),
+            ("Zm9vYg==", "foob"),
+            ("Zm9vYmE=", "fooba"),
+            ("Zm9vYmFy", "foobar"),
+        ];
+        for (encoded, decoded) in cases {
+            assert_eq!(decode(encoded).unwrap(), decoded.as_bytes(), "{}", encoded);
+        }
+        assert_eq!(decode("+/+/").unwrap(), [0xfb, 0xff, 0xbf]);
+        assert_eq!(decode("AAEC/w==").unwrap(), [0, 1, 2, 255]);
+
+        for invalid in [
+            "Zg",
+            "Zg=",
+            "Zm9",
+            "Zg===",
+            "====",
+            "Zg==Zg==",
+            "Zm=v",
+            "Zh==",
+            "Zm9=",
+            "-_-_",
+            "Zm9v\n",
+            " Zm9v",
+            "Zm\u{e9}=",
+        ] {
+            assert_eq!(decode(invalid), None, "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn as_base64_bytes_test() {
+        assert_eq!(
+            Value::Str("3q2+7w==".into()).as_base64_bytes(),
+            Some(vec![0xde, 0xad, 0xbe, 0xef])
+        );
+        assert_eq!(Value::Number(1u64.into()).as_base64_bytes(), None);
+        assert_eq!(Value::Array(Vec::new()).as_base64_bytes(), None);
+    }
+}
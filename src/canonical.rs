@@ -0,0 +1,185 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::num::Number;
+use crate::write::{write_value, JsonFormat};
+use crate::Value;
+
+impl<'ctx> Value<'ctx> {
+    /// Serializes the `Value` into canonical JSON, so equal documents produce byte-identical
+    /// output regardless of key order or number formatting in the input.
+    ///
+    /// The output follows the JSON Canonicalization Scheme ([RFC 8785]):
+    /// - No whitespace is emitted.
+    /// - Object keys are sorted recursively by their UTF-16 code units. Duplicate keys are kept in
+    ///   their original relative order.
+    /// - Strings are escaped minimally: only `"`, `\` and control characters are escaped, using the
+    ///   short forms `\b`, `\t`, `\n`, `\f`, `\r` where available and `\u00xx` otherwise.
+    /// - Numbers are converted to `f64` and written like ECMAScript's `Number.prototype.toString`,
+    ///   with the shortest digits that round-trip, e.g. `1.0` becomes `1`, `-0.0` becomes `0` and
+    ///   both `1e21` and `1000000000000000000000` become `1e+21`.
+    ///
+    /// As required by RFC 8785, integers beyond 2<sup>53</sup> are therefore rounded to the
+    /// nearest `f64`, e.g. `9007199254740993` becomes `9007199254740992`. Numbers which are
+    /// non-finite or out of the `f64` range can't be represented in RFC 8785 and are written as
+    /// `null`, like `serde_json` does. A `Value::Raw` is parsed
+    /// and canonicalized as well, or written as a string holding its text if it is not valid JSON.
+    ///
+    /// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let a: Value = serde_json::from_str(r#"{"b": [1.0, 2.50], "a": {"y": null, "x": "A"}}"#).unwrap();
+    /// let b: Value = serde_json::from_str(r#"{"a": {"x": "A", "y": null}, "b": [1, 2.5]}"#).unwrap();
+    /// assert_eq!(a.to_canonical_string(), r#"{"a":{"x":"A","y":null},"b":[1,2.5]}"#);
+    /// assert_eq!(a.to_canonical_string(), b.to_canonical_string());
+    /// ```
+    pub fn to_canonical_string(&self) -> String {
+        let mut out = String::new();
+        write_value(&mut out, self, &Canonical).expect("writing to a String can't fail");
+        out
+    }
+}
+
+/// The RFC 8785 format used by [`Value::to_canonical_string`].
+struct Canonical;
+
+impl JsonFormat for Canonical {
+    fn write_number<W: Write + ?Sized>(&self, w: &mut W, n: &Number) -> fmt::Result {
+        let mut out = String::new();
+        write_number(n, &mut out);
+        w.write_str(&out)
+    }
+
+    #[cfg(feature = "raw_value")]
+    fn write_raw<W: Write + ?Sized>(&self, w: &mut W, text: &str) -> fmt::Result {
+        write_value(w, &crate::value::parse_raw_lossy(text), self)
+    }
+
+    fn sort_entries(&self, entries: &mut [&(Cow<'_, str>, Value<'_>)]) {
+        entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+    }
+}
+
+fn write_number(n: &Number, out: &mut String) {
+    write_float(n.as_f64().unwrap_or(f64::NAN), out);
+}
+
+/// Writes a float like ECMAScript's `Number.prototype.toString`.
+fn write_float(f: f64, out: &mut String) {
+    if !f.is_finite() {
+        out.push_str("null");
+        return;
+    }
+    if f == 0.0 {
+        out.push('0');
+        return;
+    }
+    if f < 0.0 {
+        out.push('-');
+    }
+    // `{:e}` yields the shortest digits which round-trip, e.g. `1.2345e2`.
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exp) = sci.split_once('e').expect("exponent notation");
+    let digits = mantissa.replace('.', "");
+    let exp: i32 = exp.parse().expect("valid exponent");
+    // The position of the decimal point relative to the digits.
+    let point = exp + 1;
+    let len = digits.len() as i32;
+    if len <= point && point <= 21 {
+        out.push_str(&digits);
+        out.push_str(&"0".repeat((point - len) as usize));
+    } else if 0 < point && point <= 21 {
+        out.push_str(&digits[..point as usize]);
+        out.push('.');
+        out.push_str(&digits[point as usize..]);
+    } else if -6 < point && point <= 0 {
+        out.push_str("0.");
+        out.push_str(&"0".repeat(-point as usize));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if digits.len() > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        let sign = if exp < 0 { '-' } else { '+' };
+        write!(out, "e{}{}", sign, exp.abs()).expect("writing to a String can't fail");
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn canonical(input: &str) -> io::Result<String> {
+        Ok(serde_json::from_str::<Value>(input)?.to_canonical_string())
+    }
+
+    #[test]
+    fn canonical_numbers_test() -> io::Result<()> {
+        // Examples from RFC 8785, appendix B.
+        let cases = [
+            ("0", "0"),
+            ("-0.0", "0"),
+            ("1.0", "1"),
+            ("4.50", "4.5"),
+            ("2e-3", "0.002"),
+            ("0.000001", "0.000001"),
+            ("1e-7", "1e-7"),
+            ("1e21", "1e+21"),
+            ("1e20", "100000000000000000000"),
+            ("1E30", "1e+30"),
+            ("-1.5e-10", "-1.5e-10"),
+            ("0.000000000000000000000000001", "1e-27"),
+            ("9007199254740993", "9007199254740992"),
+            ("1000000000000000000000", "1e+21"),
+            ("-18446744073709551615", "-18446744073709552000"),
+            (
+                "340282366920938463463374607431768211455",
+                "3.402823669209385e+38",
+            ),
+            ("-123", "-123"),
+            ("1.7976931348623157e308", "1.7976931348623157e+308"),
+            ("5e-324", "5e-324"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(canonical(input)?, expected, "{}", input);
+        }
+        // Shortest round-tripping digits, independent of the parser's float precision.
+        let third = Value::Number((1e9 / 3.0).into());
+        assert_eq!(third.to_canonical_string(), "333333333.3333333");
+        let tiny = Value::Number((-1.0 / 3.0 * 1e-6).into());
+        assert_eq!(tiny.to_canonical_string(), "-3.333333333333333e-7");
+        Ok(())
+    }
+
+    #[test]
+    fn canonical_structure_test() -> io::Result<()> {
+        assert_eq!(
+            canonical(r#" { "b" : [ true, null, { "d": 1, "c": 2 } ], "a": "xé\n\"" } "#)?,
+            "{\"a\":\"x\u{e9}\\n\\\"\",\"b\":[true,null,{\"c\":2,\"d\":1}]}"
+        );
+        // Duplicate keys keep their relative order.
+        assert_eq!(
+            canonical(r#"{"b": 1, "a": 2, "b": 0}"#)?,
+            r#"{"a":2,"b":1,"b":0}"#
+        );
+        // Keys are ordered by UTF-16 code units, placing U+FFFD after a surrogate pair.
+        assert_eq!(
+            canonical("{\"\u{fffd}\": 1, \"\u{1f600}\": 2, \"\u{7f}\": 3}")?,
+            "{\"\u{7f}\":3,\"\u{1f600}\":2,\"\u{fffd}\":1}"
+        );
+        assert_eq!(canonical("\"\\u001f\\b\"")?, r#""\u001f\b""#);
+        assert_eq!(canonical("[]")?, "[]");
+        assert_eq!(canonical("{}")?, "{}");
+        Ok(())
+    }
+}
@@ -1,108 +1,702 @@
-// use crate::error::Error;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
-use std::borrow::Cow;
 
-use serde::de::{Deserialize, MapAccess, SeqAccess, Visitor};
+use serde::de::value::{BorrowedStrDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{
+    Deserialize, DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess,
+    SeqAccess, Unexpected, VariantAccess, Visitor,
+};
 
+#[cfg(feature = "arbitrary_precision")]
+use crate::num::Number;
+use crate::num::N;
 use crate::value::Value;
 
-impl<'de> Deserialize<'de> for Value<'de> {
+/// The key `serde_json` uses to pass numbers with the `arbitrary_precision` feature.
+#[cfg(feature = "arbitrary_precision")]
+pub(crate) const NUMBER_TOKEN: &str = "$serde_json::private::Number";
+
+/// Deserializes a `Value` which borrows strings and keys from the input, if the deserializer
+/// provides them as borrowed.
+///
+/// The `Value` may have a shorter lifetime than the input, so it can be deserialized as part of
+/// another type borrowing from the same input.
+///
+/// To embed a `Value` in a struct deriving `Deserialize`, mark the field with `#[serde(borrow)]`,
+/// so the struct's lifetime is tied to the input:
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json_borrow::Value;
+///
+/// #[derive(Deserialize)]
+/// struct Event<'a> {
+///     kind: &'a str,
+///     #[serde(borrow)]
+///     payload: Value<'a>,
+/// }
+///
+/// let input = r#"{"kind": "click", "payload": {"x": 1, "target": "button"}}"#;
+/// let event: Event = serde_json::from_str(input).unwrap();
+/// assert_eq!(event.kind, "click");
+/// assert!(event.payload.is_borrowed());
+/// ```
+impl<'de: 'ctx, 'ctx> Deserialize<'de> for Value<'ctx> {
     #[inline]
-    fn deserialize<D>(deserializer: D) -> Result<Value<'de>, D::Error>
+    fn deserialize<D>(deserializer: D) -> Result<Value<'ctx>, D::Error>
     where D: serde::Deserializer<'de> {
-        struct ValueVisitor;
+        ValueSeed::unbounded().deserialize(deserializer)
+    }
+}
 
-        impl<'de> Visitor<'de> for ValueVisitor {
-            type Value = Value<'de>;
+/// Deserializes a `Value`, failing if arrays and objects are nested deeper than the remaining
+/// depth.
+#[derive(Clone, Copy)]
+pub(crate) struct ValueSeed {
+    remaining_depth: usize,
+    /// Keeps arrays and objects as [`Value::Raw`] once the remaining depth is exhausted, instead
+    /// of failing.
+    #[cfg(feature = "raw_value")]
+    lazy: bool,
+}
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("any valid JSON value")
-            }
+impl ValueSeed {
+    #[inline]
+    pub(crate) fn unbounded() -> Self {
+        Self::with_max_depth(usize::MAX)
+    }
 
-            #[inline]
-            fn visit_bool<E>(self, value: bool) -> Result<Value<'de>, E> {
-                Ok(Value::Bool(value))
-            }
+    #[inline]
+    pub(crate) fn with_max_depth(max_depth: usize) -> Self {
+        Self {
+            remaining_depth: max_depth,
+            #[cfg(feature = "raw_value")]
+            lazy: false,
+        }
+    }
 
-            #[inline]
-            fn visit_i64<E>(self, value: i64) -> Result<Value<'de>, E> {
-                Ok(Value::Number(value.into()))
-            }
+    /// Parses arrays and objects up to `depth` levels deep, and keeps those nested deeper as
+    /// [`Value::Raw`].
+    #[cfg(feature = "raw_value")]
+    #[inline]
+    pub(crate) fn lazy(depth: usize) -> Self {
+        Self {
+            remaining_depth: depth,
+            lazy: true,
+        }
+    }
 
-            #[inline]
-            fn visit_u64<E>(self, value: u64) -> Result<Value<'de>, E> {
-                Ok(Value::Number(value.into()))
+    /// Returns the seed for the children of an array or object.
+    #[inline]
+    fn child<E: serde::de::Error>(self) -> Result<Self, E> {
+        match self.remaining_depth.checked_sub(1) {
+            Some(remaining_depth) => {
+                let mut child = self;
+                child.remaining_depth = remaining_depth;
+                Ok(child)
             }
+            None => Err(E::custom("maximum nesting depth exceeded")),
+        }
+    }
+}
 
-            #[inline]
-            fn visit_f64<E>(self, value: f64) -> Result<Value<'de>, E> {
-                Ok(Value::Number(value.into()))
-            }
+impl<'de> DeserializeSeed<'de> for ValueSeed {
+    type Value = Value<'de>;
 
-            #[inline]
-            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
-            where E: serde::de::Error {
-                Ok(Value::Str(Cow::Owned(v)))
-            }
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: serde::Deserializer<'de> {
+        #[cfg(feature = "raw_value")]
+        if self.lazy && self.remaining_depth == 0 {
+            let raw = deserialize_raw(deserializer)?;
+            return match raw {
+                Value::Raw(text) if !text.starts_with(['[', '{']) => {
+                    // Scalars are cheap to parse, so they are never kept raw.
+                    serde_json::from_str(text).map_err(serde::de::Error::custom)
+                }
+                raw => Ok(raw),
+            };
+        }
+        deserializer.deserialize_any(ValueVisitor(self))
+    }
+}
 
-            #[inline]
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-            where E: serde::de::Error {
-                Ok(Value::Str(Cow::Owned(v.to_owned())))
-            }
+/// Deserializes any JSON value as a [`Value::Raw`] holding its verbatim text, without parsing it
+/// into a DOM. Requires the `raw_value` feature.
+///
+/// Use it to mark fields of a struct deriving `Deserialize` which should be kept unparsed, e.g.
+/// because they are only passed through, and parse them on demand with [`Value::parse`]. Only
+/// works with `serde_json` deserializers reading from a `&str` or byte slice.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json_borrow::Value;
+///
+/// #[derive(Deserialize)]
+/// struct Envelope<'a> {
+///     kind: &'a str,
+///     #[serde(borrow, deserialize_with = "serde_json_borrow::deserialize_raw")]
+///     payload: Value<'a>,
+/// }
+///
+/// let input = r#"{"kind": "event", "payload": {"id": 1, "tags": ["a"]}}"#;
+/// let envelope: Envelope = serde_json::from_str(input).unwrap();
+/// assert_eq!(envelope.payload, Value::Raw(r#"{"id": 1, "tags": ["a"]}"#));
+/// assert_eq!(envelope.payload.parse().unwrap().get("id"), 1);
+/// ```
+#[cfg(feature = "raw_value")]
+pub fn deserialize_raw<'de, D>(deserializer: D) -> Result<Value<'de>, D::Error>
+where D: serde::Deserializer<'de> {
+    let raw = <&'de serde_json::value::RawValue>::deserialize(deserializer)?;
+    Ok(Value::Raw(raw.get()))
+}
 
-            #[inline]
-            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
-            where E: serde::de::Error {
-                Ok(Value::Str(Cow::Borrowed(v)))
-            }
+impl<'de> Collect<'de> for ValueSeed {
+    #[inline]
+    fn collect_seq<A>(self, mut seq: A) -> Result<Value<'de>, A::Error>
+    where A: SeqAccess<'de> {
+        let child = self.child()?;
+        let mut vec = Vec::new();
 
-            #[inline]
-            fn visit_none<E>(self) -> Result<Value<'de>, E> {
-                Ok(Value::Null)
-            }
+        while let Some(elem) = seq.next_element_seed(child)? {
+            vec.push(elem);
+        }
 
-            #[inline]
-            fn visit_some<D>(self, deserializer: D) -> Result<Value<'de>, D::Error>
-            where D: serde::Deserializer<'de> {
-                Deserialize::deserialize(deserializer)
-            }
+        Ok(Value::Array(vec))
+    }
+
+    #[inline]
+    fn collect_map<A>(
+        self,
+        first: Option<Cow<'de, str>>,
+        mut map: A,
+    ) -> Result<Value<'de>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let child = self.child()?;
+        let mut values = Vec::new();
+
+        let mut next = first;
+        while let Some(key) = next {
+            let value = map.next_value_seed(child)?;
+            values.push((key, value));
+            next = map.next_key_seed(KeySeed)?;
+        }
+
+        Ok(Value::Object(values))
+    }
+}
+
+/// Collects the elements of arrays and the entries of objects for [`ValueVisitor`], e.g. into
+/// new `Vec`s or on a reusable stack.
+pub(crate) trait Collect<'de>: DeserializeSeed<'de, Value = Value<'de>> {
+    /// Collects the elements of an array.
+    fn collect_seq<A>(self, seq: A) -> Result<Value<'de>, A::Error>
+    where A: SeqAccess<'de>;
+
+    /// Collects the entries of an object, whose first key, if any, was already deserialized.
+    fn collect_map<A>(self, first: Option<Cow<'de, str>>, map: A) -> Result<Value<'de>, A::Error>
+    where A: MapAccess<'de>;
+}
+
+/// Visits any JSON value, handling scalars itself and passing arrays and objects on to `C`.
+pub(crate) struct ValueVisitor<C>(pub(crate) C);
+
+impl<'de, C: Collect<'de>> Visitor<'de> for ValueVisitor<C> {
+    type Value = Value<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid JSON value")
+    }
+
+    #[inline]
+    fn visit_bool<E>(self, value: bool) -> Result<Value<'de>, E> {
+        Ok(Value::Bool(value))
+    }
+
+    #[inline]
+    fn visit_i64<E>(self, value: i64) -> Result<Value<'de>, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_u64<E>(self, value: u64) -> Result<Value<'de>, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_i128<E>(self, value: i128) -> Result<Value<'de>, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_u128<E>(self, value: u128) -> Result<Value<'de>, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_f64<E>(self, value: f64) -> Result<Value<'de>, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Value::Str(Cow::Owned(v)))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Value::Str(Cow::Owned(v.to_owned())))
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Value::Str(Cow::Borrowed(v)))
+    }
+
+    #[inline]
+    fn visit_none<E>(self) -> Result<Value<'de>, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_some<D>(self, deserializer: D) -> Result<Value<'de>, D::Error>
+    where D: serde::Deserializer<'de> {
+        self.0.deserialize(deserializer)
+    }
+
+    #[inline]
+    fn visit_unit<E>(self) -> Result<Value<'de>, E> {
+        Ok(Value::Null)
+    }
+
+    #[inline]
+    fn visit_seq<V>(self, visitor: V) -> Result<Value<'de>, V::Error>
+    where V: SeqAccess<'de> {
+        self.0.collect_seq(visitor)
+    }
+
+    #[inline]
+    fn visit_map<V>(self, mut visitor: V) -> Result<Value<'de>, V::Error>
+    where V: MapAccess<'de> {
+        let first = visitor.next_key_seed(KeySeed)?;
+        #[cfg(feature = "arbitrary_precision")]
+        if first.as_deref() == Some(NUMBER_TOKEN) {
+            // `serde_json` passes numbers as a map with a single magic key.
+            let token: Cow<'de, str> = visitor.next_value_seed(KeySeed)?;
+            return Ok(Value::Number(Number::from_token(&token)));
+        }
+        self.0.collect_map(first, visitor)
+    }
+}
+
+/// Deserializes an object key, borrowing it from the input if it contains no escape sequences.
+///
+/// Keys are requested with `deserialize_str`, for which `serde_json` calls `visit_borrowed_str`
+/// with a slice of the input, so keys are never copied unless they need unescaping.
+pub(crate) struct KeySeed;
 
-            #[inline]
-            fn visit_unit<E>(self) -> Result<Value<'de>, E> {
-                Ok(Value::Null)
+impl<'de> DeserializeSeed<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: serde::Deserializer<'de> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KeySeed {
+    type Value = Cow<'de, str>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string key")
+    }
+
+    #[inline]
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Cow::Borrowed(v))
+    }
+
+    #[inline]
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Cow::Owned(v.to_owned()))
+    }
+
+    #[inline]
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where E: serde::de::Error {
+        Ok(Cow::Owned(v))
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Deserializes the `Value` into any type implementing `DeserializeOwned`, e.g. a subtree into
+    /// a struct deriving `Deserialize`.
+    ///
+    /// The `Value` is read directly through its `Deserializer` implementation, without converting
+    /// it into a `serde_json::Value` first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use serde_json_borrow::Value;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct User {
+    ///     name: String,
+    ///     tags: Vec<String>,
+    ///     age: Option<u8>,
+    /// }
+    ///
+    /// let value: Value = serde_json::from_str(r#"{"user": {"name": "a", "tags": ["x"]}}"#).unwrap();
+    /// let user: User = value.get("user").deserialize_into().unwrap();
+    /// assert_eq!(user, User { name: "a".to_string(), tags: vec!["x".to_string()], age: None });
+    ///
+    /// assert!(value.get("user").get("name").deserialize_into::<u8>().is_err());
+    /// ```
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        T::deserialize(self)
+    }
+
+    /// Describes the value for error messages of the `Deserializer` implementation.
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Value::Null => Unexpected::Unit,
+            Value::Bool(b) => Unexpected::Bool(*b),
+            Value::Number(n) => {
+                if let Some(n) = n.as_u64() {
+                    Unexpected::Unsigned(n)
+                } else if let Some(n) = n.as_i64() {
+                    Unexpected::Signed(n)
+                } else if let Some(f) = n.as_f64().filter(|_| n.is_f64()) {
+                    Unexpected::Float(f)
+                } else {
+                    Unexpected::Other("number")
+                }
             }
+            Value::Str(s) => Unexpected::Str(s),
+            Value::Array(_) => Unexpected::Seq,
+            Value::Object(_) => Unexpected::Map,
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => Unexpected::Other("raw JSON"),
+        }
+    }
+}
 
-            #[inline]
-            fn visit_seq<V>(self, mut visitor: V) -> Result<Value<'de>, V::Error>
-            where V: SeqAccess<'de> {
-                let mut vec = Vec::new();
+/// Runs `f` on a `serde_json` deserializer reading `text`, failing on trailing characters.
+#[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
+fn deserialize_text<'de, T>(
+    text: &'de str,
+    f: impl FnOnce(
+        &mut serde_json::Deserializer<serde_json::de::StrRead<'de>>,
+    ) -> Result<T, serde_json::Error>,
+) -> Result<T, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(text);
+    let value = f(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
 
-                while let Some(elem) = visitor.next_element()? {
-                    vec.push(elem);
+/// Returns the verbatim text of a number kept as text or a [`Value::Raw`], which is deserialized
+/// with `serde_json`.
+#[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
+fn verbatim_text<'de>(value: &'de Value<'de>) -> Option<&'de str> {
+    match value {
+        #[cfg(feature = "arbitrary_precision")]
+        Value::Number(n) => n.as_raw_str(),
+        #[cfg(feature = "raw_value")]
+        Value::Raw(text) => Some(text),
+        _ => None,
+    }
+}
+
+/// Forwards the numeric methods to `serde_json` for numbers kept as text, so their precision is
+/// preserved, and to `deserialize_any` otherwise.
+#[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
+macro_rules! deserialize_verbatim {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, serde_json::Error>
+            where V: Visitor<'de> {
+                match verbatim_text(self) {
+                    Some(text) => deserialize_text(text, |de| de.$method(visitor)),
+                    None => self.deserialize_any(visitor),
                 }
+            }
+        )*
+    };
+}
 
-                Ok(Value::Array(vec))
+/// Deserializes a type from a borrowed `Value`, borrowing strings from it.
+///
+/// This allows to parse a document once into a `Value`, and then extract typed views of it with
+/// `T::deserialize(&value)`, without copying strings. Fields of type `&str` borrow from the
+/// `Value`, and thereby from the input for strings which were borrowed during parsing.
+///
+/// Numbers are passed as `u64`, `i64`, `f64` or their 128-bit counterparts, strings as borrowed
+/// `&str` and bytes, arrays as sequences and objects as maps in their order, including duplicate
+/// keys. Enums are read like `serde_json` represents them: a unit variant as a string, other
+/// variants as an object with a single key. With the `raw_value` feature, `Value::Raw` is
+/// deserialized from its text.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Deserialize;
+/// use serde_json_borrow::Value;
+///
+/// #[derive(Deserialize)]
+/// struct Event<'a> {
+///     kind: &'a str,
+///     ids: Vec<u32>,
+/// }
+///
+/// let input = r#"{"events": [{"kind": "click", "ids": [1, 2]}, {"kind": "scroll", "ids": []}]}"#;
+/// let value: Value = serde_json::from_str(input).unwrap();
+///
+/// let events = Vec::<Event>::deserialize(value.get("events")).unwrap();
+/// assert_eq!(events[0].kind, "click");
+/// assert!(events[1].ids.is_empty());
+/// ```
+impl<'de> serde::Deserializer<'de> for &'de Value<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, serde_json::Error>
+    where V: Visitor<'de> {
+        match self {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(*b),
+            Value::Number(n) => match n.n {
+                N::PosInt(n) => visitor.visit_u64(n),
+                N::NegInt(n) => visitor.visit_i64(n),
+                N::Float(f) => visitor.visit_f64(f),
+                N::PosInt128(n) => visitor.visit_u128(n),
+                N::NegInt128(n) => visitor.visit_i128(n),
+                #[cfg(feature = "arbitrary_precision")]
+                N::Raw(ref text) => deserialize_text(text, |de| de.deserialize_any(visitor)),
+            },
+            Value::Str(s) => visitor.visit_borrowed_str(s),
+            Value::Array(arr) => {
+                let mut seq = SeqDeserializer::new(arr.iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
             }
+            Value::Object(obj) => visit_object(obj, visitor),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => deserialize_text(text, |de| de.deserialize_any(visitor)),
+        }
+    }
 
-            #[inline]
-            fn visit_map<V>(self, mut visitor: V) -> Result<Value<'de>, V::Error>
-            where V: MapAccess<'de> {
-                let mut values = Vec::new();
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, serde_json::Error>
+    where V: Visitor<'de> {
+        match self {
+            Value::Null => visitor.visit_none(),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => deserialize_text(text, |de| de.deserialize_option(visitor)),
+            _ => visitor.visit_some(self),
+        }
+    }
 
-                while let Some((key, value)) = visitor.next_entry()? {
-                    values.push((key, value));
-                }
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
 
-                Ok(Value::Object(values))
+    #[cfg_attr(not(feature = "raw_value"), allow(unused_variables))]
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Value::Str(variant) => visitor.visit_enum(EnumDeserializer {
+                variant,
+                value: None,
+            }),
+            Value::Object(obj) if obj.len() == 1 => visitor.visit_enum(EnumDeserializer {
+                variant: &obj[0].0,
+                value: Some(&obj[0].1),
+            }),
+            Value::Object(_) => Err(serde::de::Error::invalid_value(
+                Unexpected::Map,
+                &"map with a single key",
+            )),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => {
+                deserialize_text(text, |de| de.deserialize_enum(name, variants, visitor))
             }
+            other => Err(serde::de::Error::invalid_type(
+                other.unexpected(),
+                &"string or map",
+            )),
+        }
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, serde_json::Error>
+    where V: Visitor<'de> {
+        match self {
+            Value::Str(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            _ => self.deserialize_any(visitor),
         }
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, serde_json::Error>
+    where V: Visitor<'de> {
+        self.deserialize_bytes(visitor)
+    }
+
+    #[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
+    deserialize_verbatim! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64
+    }
+
+    #[cfg(not(any(feature = "arbitrary_precision", feature = "raw_value")))]
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64
+    }
 
-        deserializer.deserialize_any(ValueVisitor)
+    serde::forward_to_deserialize_any! {
+        char str string unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
     }
 }
 
-#[cfg(test)]
+impl<'de> IntoDeserializer<'de, serde_json::Error> for &'de Value<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+/// Passes the entries of an object to the visitor, borrowing the keys.
+fn visit_object<'de, V>(
+    obj: &'de [(Cow<'de, str>, Value<'de>)],
+    visitor: V,
+) -> Result<V::Value, serde_json::Error>
+where
+    V: Visitor<'de>,
+{
+    let entries = obj
+        .iter()
+        .map(|(key, val)| (BorrowedStrDeserializer::new(key), val));
+    let mut map = MapDeserializer::new(entries);
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+/// Deserializes an enum from a string holding a unit variant, or an object with a single key
+/// mapping the variant to its content, like `serde_json` represents enums.
+struct EnumDeserializer<'de> {
+    variant: &'de str,
+    value: Option<&'de Value<'de>>,
+}
+
+impl<'de> EnumAccess<'de> for EnumDeserializer<'de> {
+    type Error = serde_json::Error;
+    type Variant = VariantDeserializer<'de>;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self::Variant), serde_json::Error>
+    where S: DeserializeSeed<'de> {
+        let variant = seed.deserialize(BorrowedStrDeserializer::<serde_json::Error>::new(
+            self.variant,
+        ))?;
+        Ok((variant, VariantDeserializer { value: self.value }))
+    }
+}
+
+/// Deserializes the content of an enum variant, which is `None` for a variant given as a string.
+struct VariantDeserializer<'de> {
+    value: Option<&'de Value<'de>>,
+}
+
+impl<'de> VariantAccess<'de> for VariantDeserializer<'de> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), serde_json::Error> {
+        match self.value {
+            None | Some(Value::Null) => Ok(()),
+            Some(other) => Err(serde::de::Error::invalid_type(
+                other.unexpected(),
+                &"unit variant",
+            )),
+        }
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, serde_json::Error>
+    where S: DeserializeSeed<'de> {
+        match self.value {
+            Some(value) => seed.deserialize(value),
+            None => Err(serde::de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            )),
+        }
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, serde_json::Error>
+    where V: Visitor<'de> {
+        match self.value {
+            Some(value @ Value::Array(_)) => serde::Deserializer::deserialize_any(value, visitor),
+            Some(other) => Err(serde::de::Error::invalid_type(
+                other.unexpected(),
+                &"tuple variant",
+            )),
+            None => Err(serde::de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            )),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Some(Value::Object(obj)) => visit_object(obj, visitor),
+            Some(other) => Err(serde::de::Error::invalid_type(
+                other.unexpected(),
+                &"struct variant",
+            )),
+            None => Err(serde::de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            )),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
 
     use std::borrow::Cow;
@@ -149,4 +743,177 @@ mod tests {
             &Value::Str(Cow::Borrowed("string\"_val"))
         );
     }
+
+    #[test]
+    fn deserialize_json_escaped_keys() {
+        let json_obj = r#"{"plain": 1, "esc\"aped": 2, "new\nline": 3}"#;
+
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+        let keys: Vec<&Cow<str>> = val.iter_object().unwrap().map(|(k, _)| k).collect();
+        assert!(matches!(keys[0], Cow::Borrowed("plain")));
+        assert!(matches!(keys[1], Cow::Owned(k) if k == "esc\"aped"));
+        assert!(matches!(keys[2], Cow::Owned(k) if k == "new\nline"));
+        assert_eq!(val.get("esc\"aped"), &Value::Number(2u64.into()));
+    }
+
+    #[test]
+    fn deserialize_borrows_keys_from_input() {
+        let json = String::from(r#"{"outer": {"inner": [{"deep": 1}]}, "esc\"aped": 2}"#);
+        let input = json.as_bytes().as_ptr_range();
+        let assert_borrowed = |key: &Cow<str>| match key {
+            Cow::Borrowed(k) => assert!(input.contains(&k.as_ptr()), "{} is not in the input", k),
+            Cow::Owned(k) => panic!("{} is not borrowed", k),
+        };
+
+        for val in [
+            serde_json::from_str::<Value>(&json).unwrap(),
+            crate::from_slice(json.as_bytes()).unwrap(),
+        ] {
+            let keys: Vec<_> = val.iter_object().unwrap().map(|(k, _)| k).collect();
+            assert_borrowed(keys[0]);
+            assert!(matches!(keys[1], Cow::Owned(_)));
+            let inner = &val.get("outer").as_object().unwrap()[0].0;
+            assert_borrowed(inner);
+            let deep = &val.get("outer").get("inner").get(0).as_object().unwrap()[0].0;
+            assert_borrowed(deep);
+            assert_eq!(deep.as_ptr(), json[json.find("deep").unwrap()..].as_ptr());
+        }
+    }
+
+    #[test]
+    fn deserialize_128_bit_integers() {
+        use serde::de::value::{Error, I128Deserializer, U128Deserializer};
+        use serde::Deserialize;
+
+        let val = Value::deserialize(U128Deserializer::<Error>::new(u128::MAX)).unwrap();
+        assert_eq!(val.as_u128(), Some(u128::MAX));
+        let val = Value::deserialize(I128Deserializer::<Error>::new(i128::MIN)).unwrap();
+        assert_eq!(val.as_i128(), Some(i128::MIN));
+    }
+
+    #[test]
+    fn deserialize_into_test() {
+        use std::collections::BTreeMap;
+
+        use serde::Deserialize;
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum Shape {
+            Point,
+            Circle(f64),
+            Line(u32, u32),
+            Rect { w: u8, h: u8 },
+        }
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Doc {
+            id: u64,
+            offset: i32,
+            name: String,
+            tags: Vec<String>,
+            missing: Option<bool>,
+            null: Option<bool>,
+            shapes: Vec<Shape>,
+            attrs: BTreeMap<String, i64>,
+            pair: (bool, char),
+        }
+
+        let json = r#"{
+            "id": 18446744073709551615,
+            "offset": -7,
+            "name": "x\"y",
+            "tags": ["a", "b"],
+            "null": null,
+            "shapes": ["Point", {"Circle": 1.5}, {"Line": [1, 2]}, {"Rect": {"w": 3, "h": 4}}],
+            "attrs": {"k": -1, "l": 2},
+            "pair": [true, "c"],
+            "ignored": {"nested": [1]}
+        }"#;
+        let value: Value = serde_json::from_str(json).unwrap();
+        let doc: Doc = value.deserialize_into().unwrap();
+        assert_eq!(doc, serde_json::from_str::<Doc>(json).unwrap());
+        assert_eq!(doc.shapes[1], Shape::Circle(1.5));
+
+        let value: Value = serde_json::from_str(r#"{"id": -1}"#).unwrap();
+        assert!(value.get("id").deserialize_into::<u64>().is_err());
+        let value: Value = serde_json::from_str(r#"{"Point": 1}"#).unwrap();
+        assert!(value.deserialize_into::<Shape>().is_err());
+        let value: Value = serde_json::from_str(r#"{"Circle": 1, "Point": null}"#).unwrap();
+        assert!(value.deserialize_into::<Shape>().is_err());
+        assert_eq!(
+            Value::Str("s".into())
+                .deserialize_into::<u8>()
+                .unwrap_err()
+                .to_string(),
+            "invalid type: string \"s\", expected u8"
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Null; 3])
+                .deserialize_into::<(Option<u8>, ())>()
+                .unwrap_err()
+                .to_string(),
+            "invalid length 3, expected 2 elements in sequence"
+        );
+    }
+
+    #[test]
+    fn deserializer_borrows_strings_test() {
+        use std::collections::HashMap;
+
+        use serde::Deserialize;
+
+        #[derive(Deserialize)]
+        struct Item<'a> {
+            name: &'a str,
+            #[serde(borrow)]
+            label: Cow<'a, str>,
+            #[serde(borrow)]
+            attrs: HashMap<&'a str, &'a str>,
+            bytes: &'a [u8],
+        }
+
+        let input = String::from(
+            r#"[{"name": "a", "label": "l", "attrs": {"k": "v"}, "bytes": "b"}, {"name": "e\"sc", "label": "", "attrs": {}, "bytes": ""}]"#,
+        );
+        let range = input.as_bytes().as_ptr_range();
+        let value: Value = serde_json::from_str(&input).unwrap();
+
+        let items = Vec::<Item>::deserialize(&value).unwrap();
+        let (key, val) = items[0].attrs.iter().next().unwrap();
+        for s in [items[0].name, &items[0].label, key, val] {
+            assert!(range.contains(&s.as_ptr()), "{} is not in the input", s);
+        }
+        assert!(matches!(items[0].label, Cow::Borrowed("l")));
+        assert_eq!(items[0].bytes, b"b");
+        // Unescaped strings are owned by the `Value`, and borrowed from it.
+        assert_eq!(items[1].name, "e\"sc");
+        assert!(!range.contains(&items[1].name.as_ptr()));
+
+        let value = Value::Str("x".into());
+        assert_eq!(<&str>::deserialize(&value).unwrap(), "x");
+    }
+
+    #[cfg(any(feature = "arbitrary_precision", feature = "raw_value"))]
+    #[test]
+    fn deserialize_into_verbatim_test() {
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            let value: Value = serde_json::from_str("[1.000000000000000000001, 1e400]").unwrap();
+            let json: serde_json::Value = value.deserialize_into().unwrap();
+            assert_eq!(json.to_string(), "[1.000000000000000000001,1e+400]");
+            assert_eq!(value.get(0).deserialize_into::<f64>().unwrap(), 1.0);
+        }
+        #[cfg(feature = "raw_value")]
+        {
+            let value = Value::Raw(r#"{"a": [1, null]}"#);
+            let map: std::collections::BTreeMap<String, Vec<Option<u8>>> =
+                value.deserialize_into().unwrap();
+            assert_eq!(map["a"], [Some(1), None]);
+            assert_eq!(Value::Raw("7").deserialize_into::<u8>().unwrap(), 7);
+            assert!(Value::Raw("null")
+                .deserialize_into::<Option<u8>>()
+                .unwrap()
+                .is_none());
+        }
+    }
 }
@@ -0,0 +1,339 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::pointer::escape_token;
+use crate::{PatchOp, Value};
+
+/// A difference between two values, as returned by [`Value::diff`].
+///
+/// Paths are JSON Pointers, see [`Value::pointer`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<'a> {
+    /// A value only present in the new document.
+    Added {
+        /// Location of the added value in the new document.
+        path: String,
+        /// The added value.
+        value: &'a Value<'a>,
+    },
+    /// A value only present in the old document.
+    Removed {
+        /// Location of the removed value in the old document.
+        path: String,
+        /// The removed value.
+        value: &'a Value<'a>,
+    },
+    /// A value which differs between both documents.
+    Modified {
+        /// Location of the value in both documents.
+        path: String,
+        /// The value in the old document.
+        old: &'a Value<'a>,
+        /// The value in the new document.
+        new: &'a Value<'a>,
+    },
+}
+
+impl<'a> Change<'a> {
+    /// Returns the JSON Pointer of the changed value.
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Modified { path, .. } => path,
+        }
+    }
+
+    /// Converts the change into the equivalent JSON Patch operation, cloning the new value.
+    pub fn into_patch_op(self) -> PatchOp<'a> {
+        match self {
+            Change::Added { path, value } => PatchOp::Add {
+                path: Cow::Owned(path),
+                value: value.clone(),
+            },
+            Change::Removed { path, .. } => PatchOp::Remove {
+                path: Cow::Owned(path),
+            },
+            Change::Modified { path, new, .. } => PatchOp::Replace {
+                path: Cow::Owned(path),
+                value: new.clone(),
+            },
+        }
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Computes the structural differences from `self` to `other`.
+    ///
+    /// Objects are compared by key and arrays by index, recursing into values of the same type.
+    /// Any other difference, including a change of type, is reported as [`Change::Modified`].
+    ///
+    /// The diff of arrays is positional: elements at the same index are compared, so inserting or
+    /// removing an element in the middle reports every following element as modified. Extra
+    /// elements are reported as added in ascending order, or as removed in descending order. In
+    /// this order the changes can be applied one after the other, so converting them with
+    /// [`Change::into_patch_op`] yields a JSON Patch turning `self` into `other`, as long as
+    /// neither contains objects with duplicate keys.
+    ///
+    /// Only the first entry of a duplicate key is compared, later duplicates are ignored. They are
+    /// left unchanged by the patch, so it doesn't turn `self` into `other` then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json_borrow::{Change, Value};
+    ///
+    /// let old: Value = serde_json::from_str(r#"{"name": "a", "tags": ["x"], "old": 1}"#).unwrap();
+    /// let new: Value = serde_json::from_str(r#"{"name": "b", "tags": ["x", "y"]}"#).unwrap();
+    ///
+    /// let changes = old.diff(&new);
+    /// let paths: Vec<&str> = changes.iter().map(Change::path).collect();
+    /// assert_eq!(paths, ["/name", "/tags/1", "/old"]);
+    /// assert!(matches!(changes[2], Change::Removed { .. }));
+    ///
+    /// let patch: Vec<_> = changes.into_iter().map(Change::into_patch_op).collect();
+    /// let mut patched = old.clone();
+    /// patched.apply_patch(&patch).unwrap();
+    /// assert_eq!(patched.to_string(), r#"{"name":"b","tags":["x","y"]}"#);
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a Value<'ctx>) -> Vec<Change<'a>> {
+        let mut changes = Vec::new();
+        diff_into(self, other, &mut String::new(), &mut changes);
+        changes
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Returns the JSON Pointers of all values which are present in both `self` and `other` with
+    /// the same type, e.g. to find the common structure of two sample documents.
+    ///
+    /// Values have the same type if both are null, booleans, numbers, strings, arrays or objects;
+    /// their contents don't need to be equal. The paths are returned in document order of `self`,
+    /// starting with the empty pointer of the root if both roots have the same type. The children
+    /// of two objects are matched by key, using the first entry of duplicate keys. The children of
+    /// two arrays are matched by index, so only the first `min(len, other_len)` elements are
+    /// compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let v1: Value = serde_json::from_str(r#"{"id": 1, "name": "a", "tags": ["x"], "old": true}"#).unwrap();
+    /// let v2: Value = serde_json::from_str(r#"{"id": "1", "name": "b", "tags": ["y", "z"], "new": 0}"#).unwrap();
+    ///
+    /// assert_eq!(v1.shared_paths(&v2), ["", "/name", "/tags", "/tags/0"]);
+    /// ```
+    pub fn shared_paths(&self, other: &Value<'_>) -> Vec<String> {
+        let mut paths = Vec::new();
+        shared_paths_into(self, other, &mut String::new(), &mut paths);
+        paths
+    }
+}
+
+fn shared_paths_into(a: &Value<'_>, b: &Value<'_>, path: &mut String, paths: &mut Vec<String>) {
+    if a.type_name() != b.type_name() {
+        return;
+    }
+    paths.push(path.clone());
+    let len = path.len();
+    match (a, b) {
+        (Value::Array(a_arr), Value::Array(b_arr)) => {
+            for (i, (a_val, b_val)) in a_arr.iter().zip(b_arr).enumerate() {
+                path.push('/');
+                path.push_str(&i.to_string());
+                shared_paths_into(a_val, b_val, path, paths);
+                path.truncate(len);
+            }
+        }
+        (Value::Object(a_obj), Value::Object(b_obj)) => {
+            for (i, (key, a_val)) in a_obj.iter().enumerate() {
+                if a_obj[..i].iter().any(|(k, _)| k == key) {
+                    continue;
+                }
+                if let Some((_, b_val)) = b_obj.iter().find(|(k, _)| k == key) {
+                    path.push('/');
+                    path.push_str(&escape_token(key));
+                    shared_paths_into(a_val, b_val, path, paths);
+                    path.truncate(len);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn diff_into<'a>(
+    old: &'a Value<'a>,
+    new: &'a Value<'a>,
+    path: &mut String,
+    changes: &mut Vec<Change<'a>>,
+) {
+    let len = path.len();
+    match (old, new) {
+        (Value::Array(old_arr), Value::Array(new_arr)) => {
+            for (i, (old_val, new_val)) in old_arr.iter().zip(new_arr).enumerate() {
+                path.push('/');
+                path.push_str(&i.to_string());
+                diff_into(old_val, new_val, path, changes);
+                path.truncate(len);
+            }
+            for (i, value) in new_arr.iter().enumerate().skip(old_arr.len()) {
+                changes.push(Change::Added {
+                    path: format!("{}/{}", path, i),
+                    value,
+                });
+            }
+            for (i, value) in old_arr.iter().enumerate().skip(new_arr.len()).rev() {
+                changes.push(Change::Removed {
+                    path: format!("{}/{}", path, i),
+                    value,
+                });
+            }
+        }
+        (Value::Object(old_obj), Value::Object(new_obj)) => {
+            let first_entries = |obj: &'a [(Cow<'a, str>, Value<'a>)]| {
+                obj.iter()
+                    .enumerate()
+                    .filter(move |(i, (key, _))| !obj[..*i].iter().any(|(k, _)| k == key))
+                    .map(|(_, (key, value))| (key.as_ref(), value))
+            };
+            let mut removed = Vec::new();
+            for (key, old_val) in first_entries(old_obj) {
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match new_obj.iter().find(|(k, _)| k == key) {
+                    Some((_, new_val)) => diff_into(old_val, new_val, path, changes),
+                    None => removed.push(Change::Removed {
+                        path: path.clone(),
+                        value: old_val,
+                    }),
+                }
+                path.truncate(len);
+            }
+            for (key, value) in first_entries(new_obj) {
+                if !old_obj.iter().any(|(k, _)| k == key) {
+                    changes.push(Change::Added {
+                        path: format!("{}/{}", path, escape_token(key)),
+                        value,
+                    });
+                }
+            }
+            changes.append(&mut removed);
+        }
+        _ if old != new => changes.push(Change::Modified {
+            path: path.clone(),
+            old,
+            new,
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    /// Diffs both documents and checks that the diff, applied as a JSON Patch, turns `old` into
+    /// `new`.
+    fn diff_paths(old: &str, new: &str) -> io::Result<Vec<String>> {
+        let old: Value = serde_json::from_str(old)?;
+        let new: Value = serde_json::from_str(new)?;
+        let changes = old.diff(&new);
+        let paths = changes.iter().map(|c| c.path().to_string()).collect();
+        let patch: Vec<_> = changes.into_iter().map(Change::into_patch_op).collect();
+        let mut patched = old.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_eq!(patched, new);
+        Ok(paths)
+    }
+
+    #[test]
+    fn diff_test() -> io::Result<()> {
+        assert!(diff_paths(r#"{"a": [1, {"b": null}]}"#, r#"{"a": [1, {"b": null}]}"#)?.is_empty());
+        assert_eq!(diff_paths("1", "2")?, [""]);
+        assert_eq!(diff_paths(r#"{"a": 1}"#, r#"[1]"#)?, [""]);
+        assert_eq!(
+            diff_paths(r#"{"a": {"b": 1, "c": 2}}"#, r#"{"a": {"c": 3, "d": 4}}"#)?,
+            ["/a/c", "/a/d", "/a/b"]
+        );
+        assert_eq!(
+            diff_paths(r#"{"a/b": 1, "c~d": 1}"#, r#"{"a/b": 2}"#)?,
+            ["/a~1b", "/c~0d"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diff_arrays_test() -> io::Result<()> {
+        assert_eq!(diff_paths("[1, 2, 3, 4]", "[1, 5]")?, ["/1", "/3", "/2"]);
+        assert_eq!(diff_paths("[1]", "[1, 2, 3]")?, ["/1", "/2"]);
+        // Positional: removing the first element modifies all others.
+        assert_eq!(diff_paths("[1, 2, 3]", "[2, 3]")?, ["/0", "/1", "/2"]);
+        assert_eq!(diff_paths("[[1], []]", "[[1, 2], []]")?, ["/0/1"]);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_changes_test() -> io::Result<()> {
+        let old: Value = serde_json::from_str(r#"{"a": 1, "a": 2, "b": true}"#)?;
+        let new: Value = serde_json::from_str(r#"{"a": 3, "c": "x"}"#)?;
+        let changes = old.diff(&new);
+        assert_eq!(
+            changes,
+            [
+                Change::Modified {
+                    path: "/a".to_string(),
+                    old: &Value::Number(1u64.into()),
+                    new: &Value::Number(3u64.into()),
+                },
+                Change::Added {
+                    path: "/c".to_string(),
+                    value: &Value::Str("x".into()),
+                },
+                Change::Removed {
+                    path: "/b".to_string(),
+                    value: &Value::Bool(true),
+                },
+            ]
+        );
+
+        // The ignored duplicate is kept by the patch.
+        let patch: Vec<_> = changes.into_iter().map(Change::into_patch_op).collect();
+        let mut patched = old.clone();
+        patched.apply_patch(&patch).unwrap();
+        assert_ne!(patched, new);
+        assert_eq!(patched.to_string(), r#"{"a":3,"a":2,"c":"x"}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn shared_paths_test() -> io::Result<()> {
+        let a: Value = serde_json::from_str(
+            r#"{"a/b": {"x": 1, "y": [1, {"z": null}]}, "k": 1, "k": "s", "n": null, "only": 0}"#,
+        )?;
+        let b: Value = serde_json::from_str(
+            r#"{"n": null, "k": "t", "a/b": {"y": [2.5, {"z": null, "w": 1}, 3], "x": false}}"#,
+        )?;
+        assert_eq!(
+            a.shared_paths(&b),
+            [
+                "",
+                "/a~1b",
+                "/a~1b/y",
+                "/a~1b/y/0",
+                "/a~1b/y/1",
+                "/a~1b/y/1/z",
+                "/n"
+            ]
+        );
+        assert_eq!(b.shared_paths(&a).len(), 7);
+        assert!(a.shared_paths(&Value::Null).is_empty());
+        assert_eq!(Value::Null.shared_paths(&Value::Null), [""]);
+        Ok(())
+    }
+}
@@ -0,0 +1,280 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use core::fmt;
+
+/// An error which occurred while parsing JSON, with its position and a snippet of the input around
+/// it.
+///
+/// Wraps the `serde_json::Error`, which provides [`classify`](serde_json::Error::classify) and
+/// more via [`inner`](ParseError::inner), and additionally resolves the byte offset of the error
+/// within the input and keeps the surrounding text, so the error can be shown without access to
+/// the input. It is returned by all parse functions of this crate, like
+/// [`from_str`](crate::from_str) and [`from_slice`](crate::from_slice), and can be created from
+/// any `serde_json::Error` with [`ParseError::new`]. It converts back into a `serde_json::Error`
+/// with `?` or [`into_inner`](ParseError::into_inner).
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str;
+///
+/// let err = from_str("{\"id\": 1,\n \"name\": }").unwrap_err();
+/// assert_eq!((err.line(), err.column()), (2, 10));
+/// assert_eq!(err.offset(), Some(19));
+/// assert_eq!(err.snippet(), " \"name\": }");
+/// assert_eq!(
+///     err.to_string(),
+///     "expected value at line 2 column 10, near ` \"name\": }`"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ParseError {
+    inner: serde_json::Error,
+    line: usize,
+    column: usize,
+    offset: Option<usize>,
+    snippet: String,
+}
+
+/// Number of bytes of input kept before and after the error position in the snippet.
+const SNIPPET_CONTEXT: usize = 24;
+
+impl ParseError {
+    /// Wraps an error which occurred while parsing `input`, resolving its position within the
+    /// input.
+    pub fn new(err: serde_json::Error, input: &str) -> Self {
+        Self::within(err, input.as_bytes(), 0, 0)
+    }
+
+    /// Wraps an error which occurred while parsing the line of `input` starting at byte
+    /// `line_start`, which is preceded by `lines_before` lines. Use a `line_start` and
+    /// `lines_before` of 0 for errors within the whole input.
+    pub(crate) fn within(
+        err: serde_json::Error,
+        input: &[u8],
+        line_start: usize,
+        lines_before: usize,
+    ) -> Self {
+        let offset = error_offset(&err, &input[line_start..]).map(|offset| line_start + offset);
+        let snippet = offset
+            .map(|offset| String::from_utf8_lossy(snippet_around(input, offset)).into_owned())
+            .unwrap_or_default();
+        let line = if err.line() == 0 {
+            0
+        } else {
+            lines_before + err.line()
+        };
+        Self {
+            line,
+            column: err.column(),
+            inner: err,
+            offset,
+            snippet,
+        }
+    }
+
+    /// The one-based line of the error, or 0 if the error has no position.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The one-based column of the error in bytes, or 0 if the error has no position.
+    ///
+    /// The column is 0 as well if the error occurred right after a newline.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The byte offset of the error within the input, or `None` if the error has no position.
+    ///
+    /// This is the offset of the byte at which the error was detected. For unexpected ends of
+    /// input, this is the last byte of the input.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// The text of the error's line around the error position, or an empty string if the error
+    /// has no position.
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    /// Returns the underlying `serde_json::Error`.
+    ///
+    /// For errors of [`from_ndjson`](crate::from_ndjson), its position is relative to the line of
+    /// the error rather than to the whole input.
+    pub fn inner(&self) -> &serde_json::Error {
+        &self.inner
+    }
+
+    /// Converts into the underlying `serde_json::Error`.
+    pub fn into_inner(self) -> serde_json::Error {
+        self.inner
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = self.inner.to_string();
+        let position = format!(
+            " at line {} column {}",
+            self.inner.line(),
+            self.inner.column()
+        );
+        match msg.strip_suffix(&position) {
+            Some(msg) if self.line != 0 => {
+                write!(f, "{msg} at line {} column {}", self.line, self.column)?
+            }
+            _ => f.write_str(&msg)?,
+        }
+        if !self.snippet.is_empty() {
+            write!(f, ", near `{}`", self.snippet)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
+impl From<ParseError> for serde_json::Error {
+    fn from(err: ParseError) -> Self {
+        err.inner
+    }
+}
+
+/// Converts like `serde_json::Error`, but keeps the `ParseError` with its position and snippet as
+/// the source of syntax, data and EOF errors.
+#[cfg(feature = "std")]
+impl From<ParseError> for std::io::Error {
+    fn from(err: ParseError) -> Self {
+        use std::io::ErrorKind;
+
+        use serde_json::error::Category;
+
+        match err.inner.classify() {
+            Category::Io => err.inner.into(),
+            Category::Syntax | Category::Data => std::io::Error::new(ErrorKind::InvalidData, err),
+            Category::Eof => std::io::Error::new(ErrorKind::UnexpectedEof, err),
+        }
+    }
+}
+
+/// Resolves the line and column of the error into a byte offset within `input`.
+fn error_offset(err: &serde_json::Error, input: &[u8]) -> Option<usize> {
+    if err.line() == 0 || input.is_empty() {
+        return None;
+    }
+    let line_start = if err.line() == 1 {
+        0
+    } else {
+        newlines(input)
+            .nth(err.line() - 2)
+            .map_or(input.len(), |i| i + 1)
+    };
+    let offset = (line_start + err.column().saturating_sub(1)).min(input.len() - 1);
+    Some(floor_char_boundary(input, offset))
+}
+
+/// Returns up to `SNIPPET_CONTEXT` bytes of the line before and after `offset`.
+fn snippet_around(input: &[u8], offset: usize) -> &[u8] {
+    let line_start = input[..offset]
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |i| i + 1);
+    let line_end = newlines(&input[offset..])
+        .next()
+        .map_or(input.len(), |i| offset + i);
+    let start = offset.saturating_sub(SNIPPET_CONTEXT).max(line_start);
+    let end = (offset + 1 + SNIPPET_CONTEXT).min(line_end);
+    let start = ceil_char_boundary(input, start);
+    let end = floor_char_boundary(input, end).max(start);
+    &input[start..end]
+}
+
+fn newlines(input: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    input
+        .iter()
+        .enumerate()
+        .filter(|(_, &b)| b == b'\n')
+        .map(|(i, _)| i)
+}
+
+/// Whether `i` is at the end of the input or not in the middle of a UTF-8 sequence.
+fn is_char_boundary(input: &[u8], i: usize) -> bool {
+    !matches!(input.get(i), Some(&b) if b & 0xC0 == 0x80)
+}
+
+fn floor_char_boundary(input: &[u8], mut i: usize) -> usize {
+    while i > 0 && !is_char_boundary(input, i) {
+        i -= 1;
+    }
+    i
+}
+
+fn ceil_char_boundary(input: &[u8], mut i: usize) -> usize {
+    while !is_char_boundary(input, i) {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{from_str, Value};
+
+    #[test]
+    fn parse_error_position_test() {
+        let err = from_str("[1, 2").unwrap_err();
+        assert!(err.inner().is_eof());
+        assert_eq!((err.line(), err.column(), err.offset()), (1, 5, Some(4)));
+        assert_eq!(err.snippet(), "[1, 2");
+
+        let err = from_str("\n\n  {\"a\" 1}").unwrap_err();
+        assert_eq!((err.line(), err.column(), err.offset()), (3, 8, Some(9)));
+        assert_eq!(&"\n\n  {\"a\" 1}"[9..10], "1");
+        assert_eq!(err.snippet(), "  {\"a\" 1}");
+
+        let err = from_str("").unwrap_err();
+        assert_eq!(err.offset(), None);
+        assert_eq!(err.snippet(), "");
+        assert_eq!(err.to_string(), err.inner().to_string());
+    }
+
+    #[test]
+    fn parse_error_snippet_test() {
+        let input = format!("[\"{}\", \"é\", x, \"{}\"]", "a".repeat(40), "b".repeat(40));
+        let err = from_str(&input).unwrap_err();
+        assert_eq!(&input[err.offset().unwrap()..][..1], "x");
+        assert_eq!(
+            err.snippet(),
+            "aaaaaaaaaaaaaaa\", \"é\", x, \"bbbbbbbbbbbbbbbbbbbbb"
+        );
+        assert!(err
+            .to_string()
+            .starts_with("expected value at line 1 column"));
+
+        // A custom error without position.
+        let err = ParseError::new(serde::de::Error::custom("bad"), "[1]");
+        assert_eq!((err.line(), err.offset()), (0, None));
+        let err: serde_json::Error = err.into();
+        assert_eq!(err.to_string(), "bad");
+
+        let value: Value = from_str("[\"ok\"]").unwrap();
+        assert!(value.is_borrowed());
+
+        let err: std::io::Error = from_str("[1,\n x]").unwrap_err().into();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.to_string(),
+            "expected value at line 2 column 2, near ` x]`"
+        );
+        let err: std::io::Error = from_str("[1").unwrap_err().into();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+}
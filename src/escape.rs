@@ -0,0 +1,221 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use serde::de::Error as _;
+
+/// Escapes the contents of a JSON string, without the surrounding quotes.
+///
+/// `"`, `\` and control characters are escaped the same way `Display` of a `Value` escapes them:
+/// `\b`, `\t`, `\n`, `\f` and `\r` use their short forms, other control characters are written as
+/// `\u00xx`. All other characters, including non-ASCII ones, are kept as they are. If nothing needs
+/// to be escaped, the input is returned as `Cow::Borrowed`.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use serde_json_borrow::escape_str;
+///
+/// assert!(matches!(escape_str("plain é"), Cow::Borrowed("plain é")));
+/// assert_eq!(escape_str("say \"hi\"\n"), r#"say \"hi\"\n"#);
+/// ```
+pub fn escape_str(s: &str) -> Cow<'_, str> {
+    if !s.bytes().any(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+    let mut escaped = String::with_capacity(s.len() + 2);
+    write_escaped_contents(&mut escaped, s).expect("writing to a String can't fail");
+    Cow::Owned(escaped)
+}
+
+/// Unescapes the contents of a JSON string, without the surrounding quotes.
+///
+/// All escape sequences of the JSON grammar are supported, including UTF-16 surrogate pairs like
+/// `\ud83d\ude00`. If the input contains no escape sequences, it is returned as `Cow::Borrowed`.
+///
+/// Fails on invalid escape sequences, lone surrogates, unescaped `"` and unescaped control
+/// characters, which are not valid within a JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use serde_json_borrow::unescape_str;
+///
+/// assert!(matches!(unescape_str("plain"), Ok(Cow::Borrowed("plain"))));
+/// assert_eq!(unescape_str(r#"a\"b\u00e9\ud83d\ude00"#).unwrap(), "a\"b\u{e9}\u{1f600}");
+/// assert!(unescape_str(r"\x").is_err());
+/// ```
+pub fn unescape_str(s: &str) -> Result<Cow<'_, str>, serde_json::Error> {
+    let Some(first) = s.find('\\') else {
+        check_unescaped(s)?;
+        return Ok(Cow::Borrowed(s));
+    };
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+    let mut pos = first;
+    loop {
+        let text = &rest[..pos];
+        check_unescaped(text)?;
+        unescaped.push_str(text);
+        let mut chars = rest[pos + 1..].chars();
+        let c = match chars.next() {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => {
+                let (c, tail) = parse_unicode_escape(chars.as_str())?;
+                chars = tail.chars();
+                c
+            }
+            Some(_) => return Err(serde_json::Error::custom("invalid escape")),
+            None => return Err(serde_json::Error::custom("unexpected end of escape")),
+        };
+        unescaped.push(c);
+        rest = chars.as_str();
+        match rest.find('\\') {
+            Some(next) => pos = next,
+            None => break,
+        }
+    }
+    check_unescaped(rest)?;
+    unescaped.push_str(rest);
+    Ok(Cow::Owned(unescaped))
+}
+
+/// Checks that text without escape sequences contains no characters which must be escaped.
+fn check_unescaped(text: &str) -> Result<(), serde_json::Error> {
+    match text.bytes().find(|&b| b == b'"' || b < 0x20) {
+        None => Ok(()),
+        Some(b'"') => Err(serde_json::Error::custom("unescaped `\"` in string")),
+        Some(_) => Err(serde_json::Error::custom(
+            "control character (\\u0000-\\u001F) found while parsing a string",
+        )),
+    }
+}
+
+/// Parses the four hex digits after `\u`, and the low surrogate following a high surrogate.
+/// Returns the character and the remaining input.
+fn parse_unicode_escape(s: &str) -> Result<(char, &str), serde_json::Error> {
+    let hex = |s: &str| -> Result<u32, serde_json::Error> {
+        match s.get(..4) {
+            Some(digits) if digits.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                Ok(u32::from_str_radix(digits, 16).expect("valid hex digits"))
+            }
+            _ => Err(serde_json::Error::custom("invalid hex escape")),
+        }
+    };
+    let high = hex(s)?;
+    let rest = &s[4..];
+    match high {
+        0xD800..=0xDBFF => {
+            let Some(low_escape) = rest.strip_prefix("\\u") else {
+                return Err(serde_json::Error::custom(
+                    "lone leading surrogate in hex escape",
+                ));
+            };
+            let low = hex(low_escape)?;
+            if !(0xDC00..=0xDFFF).contains(&low) {
+                return Err(serde_json::Error::custom(
+                    "lone leading surrogate in hex escape",
+                ));
+            }
+            let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+            let c = char::from_u32(code).expect("surrogate pairs are valid code points");
+            Ok((c, &low_escape[4..]))
+        }
+        0xDC00..=0xDFFF => Err(serde_json::Error::custom(
+            "unexpected end of hex escape, lone trailing surrogate",
+        )),
+        _ => Ok((
+            char::from_u32(high).expect("non-surrogates are valid code points"),
+            rest,
+        )),
+    }
+}
+
+fn needs_escape(b: u8) -> bool {
+    b == b'"' || b == b'\\' || b < 0x20
+}
+
+/// Writes a JSON string literal, escaping `"`, `\` and control characters the way `serde_json`
+/// does.
+pub(crate) fn write_escaped_str<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    write_escaped_contents(w, s)?;
+    w.write_char('"')
+}
+
+fn write_escaped_contents<W: Write + ?Sized>(w: &mut W, s: &str) -> fmt::Result {
+    let mut start = 0;
+    for (i, byte) in s.bytes().enumerate() {
+        if !needs_escape(byte) {
+            continue;
+        }
+        w.write_str(&s[start..i])?;
+        match byte {
+            b'"' => w.write_str("\\\"")?,
+            b'\\' => w.write_str("\\\\")?,
+            b'\x08' => w.write_str("\\b")?,
+            b'\t' => w.write_str("\\t")?,
+            b'\n' => w.write_str("\\n")?,
+            b'\x0c' => w.write_str("\\f")?,
+            b'\r' => w.write_str("\\r")?,
+            _ => write!(w, "\\u{:04x}", byte)?,
+        }
+        start = i + 1;
+    }
+    w.write_str(&s[start..])
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_str_test() {
+        assert!(matches!(escape_str(""), Cow::Borrowed("")));
+        assert!(matches!(escape_str("/ü"), Cow::Borrowed("/ü")));
+        assert_eq!(
+            escape_str("\"\\\u{8}\u{c}\n\r\t\u{0}\u{1f}\u{7f}"),
+            r#"\"\\\b\f\n\r\t\u0000\u001f"#.to_string() + "\u{7f}"
+        );
+
+        // Escaped contents in quotes are parsed back to the original string.
+        let original = "mixed \"quotes\", \\ and \u{1}\nnewlines é";
+        let literal = format!("\"{}\"", escape_str(original));
+        assert_eq!(serde_json::from_str::<String>(&literal).unwrap(), original);
+    }
+
+    #[test]
+    fn unescape_str_test() {
+        assert!(matches!(unescape_str(""), Ok(Cow::Borrowed(""))));
+        assert!(matches!(unescape_str("ü/"), Ok(Cow::Borrowed("ü/"))));
+        assert_eq!(
+            unescape_str(r#"\"\\\/\b\f\n\r\t\u0041\u00E9\u20ac\uD83D\uDE00x"#).unwrap(),
+            "\"\\/\u{8}\u{c}\n\r\t\u{41}\u{e9}\u{20ac}\u{1f600}x"
+        );
+
+        let original = "round \"trip\" \\ é \u{1f600} \u{1}";
+        assert_eq!(unescape_str(&escape_str(original)).unwrap(), original);
+
+        for invalid in [
+            r"\", r"\q", r"\u12", r"\u12g4", r"\ud83d", r"\ud83dx", r"\ud83dA", r"\ude00", "\"",
+            "\n",
+        ] {
+            assert!(unescape_str(invalid).is_err(), "{:?}", invalid);
+        }
+        assert_eq!(
+            unescape_str(r"a\x").unwrap_err().to_string(),
+            "invalid escape"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::Value;
+
+/// How array indices are written in the paths produced by [`Value::flatten`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexNotation {
+    /// Indices are separated by a dot like keys, e.g. `a.b.0`.
+    Dot,
+    /// Indices are written in brackets, e.g. `a.b[0]`.
+    Bracket,
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Flattens the `Value` into pairs of dotted paths and leaf values, in document order.
+    ///
+    /// Leaves are null, booleans, numbers, strings as well as empty arrays and objects. Object keys
+    /// are joined with `.` and array indices are written as chosen by `notation`. A leaf at the
+    /// root has the empty path. Keys are not escaped, so keys containing `.` or `[` result in
+    /// ambiguous paths; use [`Value::pointer`] based traversal if that matters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json_borrow::{IndexNotation, Value};
+    ///
+    /// let data: Value = serde_json::from_str(r#"{"a": {"b": ["z", {"c": true}]}, "d": []}"#).unwrap();
+    ///
+    /// let paths: Vec<String> = data.flatten(IndexNotation::Dot).into_iter().map(|(p, _)| p).collect();
+    /// assert_eq!(paths, ["a.b.0", "a.b.1.c", "d"]);
+    ///
+    /// let flat = data.flatten(IndexNotation::Bracket);
+    /// assert_eq!(flat[0], ("a.b[0]".to_string(), &Value::Str("z".into())));
+    /// assert_eq!(flat[1].0, "a.b[1].c");
+    /// ```
+    pub fn flatten(&self, notation: IndexNotation) -> Vec<(String, &Value<'ctx>)> {
+        let mut leaves = Vec::new();
+        self.flatten_into(&mut String::new(), notation, &mut leaves);
+        leaves
+    }
+
+    fn flatten_into<'a>(
+        &'a self,
+        path: &mut String,
+        notation: IndexNotation,
+        leaves: &mut Vec<(String, &'a Value<'ctx>)>,
+    ) {
+        let len = path.len();
+        match self {
+            Value::Array(arr) if !arr.is_empty() => {
+                for (i, val) in arr.iter().enumerate() {
+                    match notation {
+                        IndexNotation::Dot if len > 0 => write!(path, ".{}", i),
+                        IndexNotation::Dot => write!(path, "{}", i),
+                        IndexNotation::Bracket => write!(path, "[{}]", i),
+                    }
+                    .expect("writing to a String can't fail");
+                    val.flatten_into(path, notation, leaves);
+                    path.truncate(len);
+                }
+            }
+            Value::Object(obj) if !obj.is_empty() => {
+                for (key, val) in obj {
+                    if len > 0 {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    val.flatten_into(path, notation, leaves);
+                    path.truncate(len);
+                }
+            }
+            _ => leaves.push((path.clone(), self)),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn flat(data: &Value, notation: IndexNotation) -> Vec<String> {
+        data.flatten(notation)
+            .into_iter()
+            .map(|(path, val)| format!("{}={}", path, val))
+            .collect()
+    }
+
+    #[test]
+    fn flatten_test() -> io::Result<()> {
+        let data: Value =
+            serde_json::from_str(r#"{"a": 1, "b": {"c": [null, [true, "x"]], "d": {}}, "e": []}"#)?;
+        assert_eq!(
+            flat(&data, IndexNotation::Dot),
+            [
+                "a=1",
+                "b.c.0=null",
+                "b.c.1.0=true",
+                r#"b.c.1.1="x""#,
+                "b.d={}",
+                "e=[]"
+            ]
+        );
+        assert_eq!(
+            flat(&data, IndexNotation::Bracket),
+            [
+                "a=1",
+                "b.c[0]=null",
+                "b.c[1][0]=true",
+                r#"b.c[1][1]="x""#,
+                "b.d={}",
+                "e=[]"
+            ]
+        );
+
+        let data: Value = serde_json::from_str(r#"[{"a": 1}, 2]"#)?;
+        assert_eq!(flat(&data, IndexNotation::Dot), ["0.a=1", "1=2"]);
+        assert_eq!(flat(&data, IndexNotation::Bracket), ["[0].a=1", "[1]=2"]);
+
+        assert_eq!(flat(&Value::Bool(false), IndexNotation::Dot), ["=false"]);
+
+        Ok(())
+    }
+}
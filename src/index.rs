@@ -1,20 +1,25 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use core::fmt;
+
 use super::Value;
 
 /// A type that can be used to index into a `serde_json_borrow::Value`.
 ///
 /// [`get`] of `Value` accept any type that implements `Index`. This
 /// trait is implemented for strings which are used as the index into a JSON
-/// map, and for `usize` which is used as the index into a JSON array.
+/// map, and for `usize` which is used as the index into a JSON array. [`FromEnd`] indexes into a
+/// JSON array counting from the end.
 ///
 /// [`get`]: ../enum.Value.html#method.get
 ///
 /// This trait is sealed and cannot be implemented for types outside of
 /// `serde_json_borrow`.
-
+///
 /// # Examples
 ///
 /// ```
-/// # use serde_json_borrow::Value;
+/// # use serde_json_borrow::{FromEnd, Value};
 /// #
 /// let json_obj = r#"
 /// {
@@ -29,32 +34,233 @@ use super::Value;
 /// assert_eq!(data.get("x").get("y").get(0), &Value::Str(std::borrow::Cow::Borrowed("z")));
 /// assert_eq!(data.get("x").get("y").get(1), &Value::Str(std::borrow::Cow::Borrowed("zz")));
 /// assert_eq!(data.get("x").get("y").get(2), &Value::Null);
+/// assert_eq!(data.get("x").get("y").get(FromEnd(1)), &Value::Str(std::borrow::Cow::Borrowed("zz")));
+/// assert_eq!(data.get("x").get("y").get(FromEnd(3)), &Value::Null);
 ///
 /// assert_eq!(data.get("a"), &Value::Null);
 /// assert_eq!(data.get("a").get("b"), &Value::Null);
 /// ```
-pub trait Index<'v> {
+pub trait Index {
+    /// Return None if the key is not already in the array or object.
+    #[doc(hidden)]
+    fn index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Option<&'a Value<'ctx>>;
+
     /// Return None if the key is not already in the array or object.
     #[doc(hidden)]
-    fn index_into(self, v: &'v Value<'v>) -> Option<&Value<'v>>;
+    fn index_into_mut<'a, 'ctx>(self, v: &'a mut Value<'ctx>) -> Option<&'a mut Value<'ctx>>;
+
+    /// Return an error describing why the key is not in the array or object.
+    #[doc(hidden)]
+    fn try_index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Result<&'a Value<'ctx>, GetError>;
 }
 
-impl<'v> Index<'v> for usize {
+impl Index for usize {
     #[inline]
-    fn index_into(self, v: &'v Value<'v>) -> Option<&Value<'v>> {
+    fn index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Option<&'a Value<'ctx>> {
         match v {
             Value::Array(vec) => vec.get(self),
             _ => None,
         }
     }
+
+    #[inline]
+    fn index_into_mut<'a, 'ctx>(self, v: &'a mut Value<'ctx>) -> Option<&'a mut Value<'ctx>> {
+        match v {
+            Value::Array(vec) => vec.get_mut(self),
+            _ => None,
+        }
+    }
+
+    fn try_index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Result<&'a Value<'ctx>, GetError> {
+        match v {
+            Value::Array(vec) => vec.get(self).ok_or(GetError::IndexOutOfBounds {
+                index: self,
+                len: vec.len(),
+            }),
+            _ => Err(GetError::WrongType {
+                expected: "array",
+                found: v.type_name(),
+            }),
+        }
+    }
+}
+
+/// An index into a JSON array counting from the end, like a negative index in Python.
+///
+/// `FromEnd(1)` is the last element, `FromEnd(2)` the one before it, and so on. `FromEnd(0)` and
+/// indices counting back further than the start of the array don't resolve to a value.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::{FromEnd, Value};
+/// #
+/// let data: Value = serde_json::from_str(r#"[1, 2, 3]"#).unwrap();
+///
+/// assert_eq!(data.get(FromEnd(1)), 3);
+/// assert_eq!(data.get(FromEnd(3)), 1);
+/// assert_eq!(data.get(FromEnd(4)), &Value::Null);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FromEnd(pub usize);
+
+impl FromEnd {
+    /// Resolves the index into an array of length `len`.
+    #[inline]
+    fn resolve(self, len: usize) -> Option<usize> {
+        len.checked_sub(self.0).filter(|_| self.0 > 0)
+    }
+}
+
+impl Index for FromEnd {
+    #[inline]
+    fn index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Option<&'a Value<'ctx>> {
+        match v {
+            Value::Array(vec) => vec.get(self.resolve(vec.len())?),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn index_into_mut<'a, 'ctx>(self, v: &'a mut Value<'ctx>) -> Option<&'a mut Value<'ctx>> {
+        match v {
+            Value::Array(vec) => {
+                let index = self.resolve(vec.len())?;
+                vec.get_mut(index)
+            }
+            _ => None,
+        }
+    }
+
+    fn try_index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Result<&'a Value<'ctx>, GetError> {
+        match v {
+            Value::Array(vec) => self.index_into(v).ok_or(GetError::IndexFromEndOutOfBounds {
+                index: self.0,
+                len: vec.len(),
+            }),
+            _ => Err(GetError::WrongType {
+                expected: "array",
+                found: v.type_name(),
+            }),
+        }
+    }
 }
 
-impl<'v, 'a: 'v> Index<'v> for &'a str {
+impl Index for &str {
     #[inline]
-    fn index_into(self, v: &'v Value<'v>) -> Option<&Value<'v>> {
+    fn index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Option<&'a Value<'ctx>> {
         match v {
-            Value::Object(map) => map.iter().find(|(k, _v)| k == &self).map(|(_k, v)| v),
+            Value::Object(map) => map.iter().find(|(k, _v)| k == self).map(|(_k, v)| v),
             _ => None,
         }
     }
+
+    #[inline]
+    fn index_into_mut<'a, 'ctx>(self, v: &'a mut Value<'ctx>) -> Option<&'a mut Value<'ctx>> {
+        match v {
+            Value::Object(map) => map.iter_mut().find(|(k, _v)| k == self).map(|(_k, v)| v),
+            _ => None,
+        }
+    }
+
+    fn try_index_into<'a, 'ctx>(self, v: &'a Value<'ctx>) -> Result<&'a Value<'ctx>, GetError> {
+        match v {
+            Value::Object(_) => self
+                .index_into(v)
+                .ok_or_else(|| GetError::KeyNotFound(self.to_owned())),
+            _ => Err(GetError::WrongType {
+                expected: "object",
+                found: v.type_name(),
+            }),
+        }
+    }
+}
+
+/// Error returned by [`try_get`] when the index doesn't resolve to a value.
+///
+/// [`try_get`]: ../enum.Value.html#method.try_get
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GetError {
+    /// The object has no entry with the key.
+    KeyNotFound(String),
+    /// The index is not smaller than the length of the array.
+    IndexOutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The length of the array.
+        len: usize,
+    },
+    /// The [`FromEnd`] index is 0 or counts back further than the start of the array.
+    IndexFromEndOutOfBounds {
+        /// The requested index, counted from the end.
+        index: usize,
+        /// The length of the array.
+        len: usize,
+    },
+    /// A key was used on a value which is not an object, or an index on a value which is not an
+    /// array.
+    WrongType {
+        /// The type required by the index, `"object"` or `"array"`.
+        expected: &'static str,
+        /// The actual type of the value, e.g. `"string"`.
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for GetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GetError::KeyNotFound(key) => write!(f, "key `{}` not found", key),
+            GetError::IndexOutOfBounds { index, len } => write!(
+                f,
+                "index {} out of bounds for array of length {}",
+                index, len
+            ),
+            GetError::IndexFromEndOutOfBounds { index, len } => write!(
+                f,
+                "index {} from the end out of bounds for array of length {}",
+                index, len
+            ),
+            GetError::WrongType { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetError {}
+
+/// A segment of a path into a `serde_json_borrow::Value`, used by [`get_path`].
+///
+/// [`get_path`]: ../enum.Value.html#method.get_path
+///
+/// Segments can be created from `&str` for object keys and from `usize` for array indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PathSegment<'a> {
+    /// Index into an object by key.
+    Key(&'a str),
+    /// Index into an array by position.
+    Index(usize),
+}
+
+impl<'a> From<&'a str> for PathSegment<'a> {
+    #[inline]
+    fn from(key: &'a str) -> Self {
+        PathSegment::Key(key)
+    }
+}
+
+impl<'a> From<&'a String> for PathSegment<'a> {
+    #[inline]
+    fn from(key: &'a String) -> Self {
+        PathSegment::Key(key)
+    }
+}
+
+impl From<usize> for PathSegment<'_> {
+    #[inline]
+    fn from(index: usize) -> Self {
+        PathSegment::Index(index)
+    }
 }
@@ -0,0 +1,175 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeSet;
+
+use crate::{from_str, ParseError, Value};
+
+/// Storage for the deduplicated strings of [`from_str_interned`].
+///
+/// Strings and keys containing escape sequences can't be borrowed from the input. The interner
+/// stores a single copy of each of them, which all occurrences in the parsed `Value` borrow.
+///
+/// The `Value` returned by [`from_str_interned`] borrows from the interner, so the interner can
+/// neither be dropped nor used for another parse while the `Value` is alive. Interned strings are
+/// kept across parses until the interner is [cleared](Interner::clear) or dropped, so strings
+/// which repeat across documents are only stored once.
+#[derive(Debug, Default)]
+pub struct Interner {
+    strings: BTreeSet<Box<str>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of interned strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Returns whether no strings are interned.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Removes all interned strings.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
+
+/// Parses a `Value` from a `&str`, deduplicating identical strings and object keys.
+///
+/// Strings and keys without escape sequences are borrowed from `input` as usual, and all
+/// occurrences of the same text share the slice of its first occurrence. Strings and keys with
+/// escape sequences are unescaped into the `interner`, and all occurrences of the same text borrow
+/// the same interned copy, instead of each holding its own `Cow::Owned`. So the result contains
+/// only `Cow::Borrowed` strings, and keeps a single copy of each distinct escaped string.
+///
+/// This saves memory in the result, not allocations while parsing: every escaped occurrence is
+/// still unescaped into a temporary `String` first, which is freed once the occurrence borrows the
+/// interned copy. Texts which are not interned yet are copied into the interner once.
+///
+/// See [`Interner`] for the lifetime of the interned strings.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use serde_json_borrow::{from_str_interned, Interner, Value};
+///
+/// let input = r#"[{"msg": "a\nb"}, {"msg": "a\nb"}, {"msg": "plain"}]"#;
+/// let mut interner = Interner::new();
+/// let value = from_str_interned(input, &mut interner).unwrap();
+///
+/// let (Value::Str(Cow::Borrowed(first)), Value::Str(Cow::Borrowed(second))) =
+///     (value.get(0).get("msg"), value.get(1).get("msg"))
+/// else {
+///     panic!("interned strings are borrowed");
+/// };
+/// assert_eq!(*first, "a\nb");
+/// assert_eq!(first.as_ptr(), second.as_ptr());
+/// drop(value);
+/// assert_eq!(interner.len(), 1);
+/// ```
+pub fn from_str_interned<'a>(
+    input: &'a str,
+    interner: &'a mut Interner,
+) -> Result<Value<'a>, ParseError> {
+    let mut value = from_str(input)?;
+    for_each_str_mut(&mut value, &mut |text| {
+        if let Cow::Owned(text) = text {
+            if !interner.strings.contains(text.as_str()) {
+                interner.strings.insert(text.as_str().into());
+            }
+        }
+    });
+    // No more strings are added, so the values can borrow from the interner from here on.
+    let interned: &'a BTreeSet<Box<str>> = &interner.strings;
+    let mut borrowed: BTreeSet<&'a str> = BTreeSet::new();
+    for_each_str_mut(&mut value, &mut |text| {
+        let shared = match text {
+            Cow::Borrowed(slice) => match borrowed.get(*slice) {
+                Some(first) => *first,
+                None => {
+                    borrowed.insert(*slice);
+                    return;
+                }
+            },
+            Cow::Owned(owned) => interned
+                .get(owned.as_str())
+                .expect("owned strings are interned"),
+        };
+        *text = Cow::Borrowed(shared);
+    });
+    Ok(value)
+}
+
+/// Calls `f` for every string and object key.
+fn for_each_str_mut<'a>(value: &mut Value<'a>, f: &mut impl FnMut(&mut Cow<'a, str>)) {
+    match value {
+        Value::Str(text) => f(text),
+        Value::Array(arr) => {
+            for val in arr {
+                for_each_str_mut(val, f);
+            }
+        }
+        Value::Object(obj) => {
+            for (key, val) in obj {
+                f(key);
+                for_each_str_mut(val, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn str_ptr(value: &Value) -> *const u8 {
+        match value {
+            Value::Str(Cow::Borrowed(s)) => s.as_ptr(),
+            _ => panic!("expected a borrowed string, got {:?}", value),
+        }
+    }
+
+    #[test]
+    fn from_str_interned_test() {
+        let input = r#"{"k\"": ["x\ty", "x\ty", "plain", "plain", "k\""], "k\"": "x\ty"}"#;
+        let mut interner = Interner::new();
+        let value = from_str_interned(input, &mut interner).unwrap();
+        let expected: Value = serde_json::from_str(input).unwrap();
+        assert_eq!(value, expected);
+
+        let list = value.get("k\"");
+        assert_eq!(str_ptr(list.get(0)), str_ptr(list.get(1)));
+        assert_eq!(
+            str_ptr(list.get(0)),
+            str_ptr(&value.as_object().unwrap()[1].1)
+        );
+        assert_eq!(str_ptr(list.get(2)), str_ptr(list.get(3)));
+        let plain = str_ptr(list.get(2));
+        assert!(input.as_bytes().as_ptr_range().contains(&plain));
+        // Keys share the interned copy with equal strings.
+        let keys: Vec<_> = value.iter_object().unwrap().map(|(k, _)| k).collect();
+        assert!(keys.iter().all(|key| matches!(key, Cow::Borrowed(_))));
+        assert_eq!(keys[0].as_ptr(), str_ptr(list.get(4)));
+        drop(value);
+        assert_eq!(interner.len(), 2);
+
+        // Interned strings are reused by later parses.
+        let value = from_str_interned(r#"["k\"", "new\n"]"#, &mut interner).unwrap();
+        assert_eq!(value.to_string(), r#"["k\"","new\n"]"#);
+        drop(value);
+        assert_eq!(interner.len(), 3);
+        interner.clear();
+        assert!(interner.is_empty());
+
+        assert!(from_str_interned("[1,", &mut interner).is_err());
+    }
+}
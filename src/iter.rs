@@ -0,0 +1,201 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use core::slice;
+
+use crate::Value;
+
+/// An owning iterator over the contents of a [`Value`], created by its `IntoIterator`
+/// implementation.
+///
+/// Yields `(None, element)` for the elements of an array, and `(Some(key), value)` for the entries
+/// of an object. Scalars, including `Null`, yield nothing.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let data: Value = serde_json::from_str(r#"{"a": 1, "b": 2}"#).unwrap();
+///
+/// let mut keys = Vec::new();
+/// for (key, _value) in data {
+///     keys.push(key.unwrap());
+/// }
+/// assert_eq!(keys, ["a", "b"]);
+/// ```
+#[derive(Debug)]
+pub struct IntoIter<'ctx> {
+    inner: IntoIterInner<'ctx>,
+}
+
+#[derive(Debug)]
+enum IntoIterInner<'ctx> {
+    Array(vec::IntoIter<Value<'ctx>>),
+    Object(vec::IntoIter<(Cow<'ctx, str>, Value<'ctx>)>),
+    Empty,
+}
+
+impl<'ctx> Iterator for IntoIter<'ctx> {
+    type Item = (Option<Cow<'ctx, str>>, Value<'ctx>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            IntoIterInner::Array(iter) => iter.next().map(|val| (None, val)),
+            IntoIterInner::Object(iter) => iter.next().map(|(key, val)| (Some(key), val)),
+            IntoIterInner::Empty => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IntoIter<'_> {
+    fn len(&self) -> usize {
+        match &self.inner {
+            IntoIterInner::Array(iter) => iter.len(),
+            IntoIterInner::Object(iter) => iter.len(),
+            IntoIterInner::Empty => 0,
+        }
+    }
+}
+
+/// An iterator over references to the contents of a [`Value`], created by the `IntoIterator`
+/// implementation of `&Value`.
+///
+/// Yields `(None, element)` for the elements of an array, and `(Some(key), value)` for the entries
+/// of an object. Scalars, including `Null`, yield nothing.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let data: Value = serde_json::from_str("[1, 2, 3]").unwrap();
+///
+/// let mut sum = 0;
+/// for (_, element) in &data {
+///     sum += element.as_u64().unwrap();
+/// }
+/// assert_eq!(sum, 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Iter<'a, 'ctx> {
+    inner: IterInner<'a, 'ctx>,
+}
+
+#[derive(Debug, Clone)]
+enum IterInner<'a, 'ctx> {
+    Array(slice::Iter<'a, Value<'ctx>>),
+    Object(slice::Iter<'a, (Cow<'ctx, str>, Value<'ctx>)>),
+    Empty,
+}
+
+impl<'a, 'ctx> Iterator for Iter<'a, 'ctx> {
+    type Item = (Option<&'a str>, &'a Value<'ctx>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.inner {
+            IterInner::Array(iter) => iter.next().map(|val| (None, val)),
+            IterInner::Object(iter) => iter.next().map(|(key, val)| (Some(key.as_ref()), val)),
+            IterInner::Empty => None,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Iter<'_, '_> {
+    fn len(&self) -> usize {
+        match &self.inner {
+            IterInner::Array(iter) => iter.len(),
+            IterInner::Object(iter) => iter.len(),
+            IterInner::Empty => 0,
+        }
+    }
+}
+
+impl<'ctx> IntoIterator for Value<'ctx> {
+    type Item = (Option<Cow<'ctx, str>>, Value<'ctx>);
+    type IntoIter = IntoIter<'ctx>;
+
+    /// Consumes the Value, yielding the elements of an array or the entries of an object. See
+    /// [`IntoIter`].
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self {
+            Value::Array(arr) => IntoIterInner::Array(arr.into_iter()),
+            Value::Object(obj) => IntoIterInner::Object(obj.into_iter()),
+            _ => IntoIterInner::Empty,
+        };
+        IntoIter { inner }
+    }
+}
+
+impl<'a, 'ctx> IntoIterator for &'a Value<'ctx> {
+    type Item = (Option<&'a str>, &'a Value<'ctx>);
+    type IntoIter = Iter<'a, 'ctx>;
+
+    /// Iterates over references to the elements of an array or the entries of an object. See
+    /// [`Iter`].
+    fn into_iter(self) -> Self::IntoIter {
+        let inner = match self {
+            Value::Array(arr) => IterInner::Array(arr.iter()),
+            Value::Object(obj) => IterInner::Object(obj.iter()),
+            _ => IterInner::Empty,
+        };
+        Iter { inner }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn into_iter_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [1, "x"], "b": null}"#)?;
+        let mut iter = data.clone().into_iter();
+        assert_eq!(iter.len(), 2);
+        let (key, arr) = iter.next().unwrap();
+        assert!(matches!(key, Some(Cow::Borrowed("a"))));
+        let elements: Vec<_> = arr.into_iter().collect();
+        assert_eq!(
+            elements,
+            [
+                (None, Value::Number(1u64.into())),
+                (None, Value::Str("x".into()))
+            ]
+        );
+        assert_eq!(iter.next(), Some((Some("b".into()), Value::Null)));
+        assert_eq!(iter.next(), None);
+
+        for scalar in [Value::Null, Value::Bool(true), Value::Str("s".into())] {
+            assert_eq!(scalar.into_iter().len(), 0);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn iter_ref_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [1, 2], "b": {"c": true}}"#)?;
+        let keys: Vec<_> = (&data).into_iter().map(|(key, _)| key).collect();
+        assert_eq!(keys, [Some("a"), Some("b")]);
+
+        let mut count = 0;
+        for (key, element) in data.get("a") {
+            assert!(key.is_none());
+            assert!(element.is_number());
+            count += 1;
+        }
+        assert_eq!(count, 2);
+        assert_eq!((&Value::Number(1u64.into())).into_iter().count(), 0);
+        Ok(())
+    }
+}
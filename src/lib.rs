@@ -20,20 +20,21 @@
 //! as [`OwnedValue`](crate::OwnedValue) will take ownership of the `String` and reference slices of
 //! it, rather than making copies.
 //!
+//! ## `no_std`
+//! The crate supports `no_std` environments with `alloc`, by disabling the default `std` feature.
+//! Only the `std`-only parts, like `OwnedValue`, are unavailable then.
+//!
+//! ## `raw_value`
+//! The `raw_value` feature adds `Value::Raw`, which keeps subtrees unparsed as their verbatim
+//! text, see `from_str_lazy` and `deserialize_raw`.
+//!
+//! ## `base64`
+//! The `base64` feature adds `Value::as_base64_bytes` to decode base64 strings, without dependencies.
+//!
 //! # Limitations
-//! Keys in objects are not allowed to have any JSON escaping characters. So if your keys contain any control characters <https://www.json.org/json-en.html>, this crate will not work for you.
-//! List of _unsupported_ characters in keys.
+//! Strings and keys which contain JSON escape sequences (e.g. `\"` or `\n`) can't be borrowed from
+//! the input, since they need to be unescaped. They are stored as `Cow::Owned` instead.
 //!
-//! ```text
-//! \" represents the quotation mark character (U+0022).
-//! \\ represents the reverse solidus character (U+005C).
-//! \/ represents the solidus character (U+002F).
-//! \b represents the backspace character (U+0008).
-//! \f represents the form feed character (U+000C).
-//! \n represents the line feed character (U+000A).
-//! \r represents the carriage return character (U+000D).
-//! \t represents the character tabulation character (U+0009).
-//! ```
 //! # Usage
 //! ```rust
 //! use std::io;
@@ -56,10 +57,74 @@
 //! On a hadoop file system log data set benchmark, I get _714Mb/s_ JSON deserialization throughput
 //! on my machine.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod array;
+#[cfg(feature = "base64")]
+mod base64;
+mod canonical;
 mod de;
+mod diff;
+mod error;
+mod escape;
+mod flatten;
 mod index;
+mod intern;
+mod iter;
+mod macros;
+mod num;
+mod object;
+#[cfg(feature = "std")]
 mod owned;
+mod parse;
+mod partial_eq;
+mod patch;
+mod pointer;
+mod redact;
+mod scratch;
+mod select;
+mod ser;
+mod shape;
+mod span;
+mod stream;
 mod value;
+mod visit;
+mod write;
 
+#[cfg(feature = "raw_value")]
+pub use de::deserialize_raw;
+pub use diff::Change;
+pub use error::ParseError;
+pub use escape::{escape_str, unescape_str};
+pub use flatten::IndexNotation;
+pub use index::{FromEnd, GetError, PathSegment};
+pub use intern::{from_str_interned, Interner};
+pub use iter::{IntoIter, Iter};
+pub use num::{Number, ParseNumberError};
+pub use object::{Entry, OccupiedEntry, VacantEntry};
+#[cfg(feature = "std")]
 pub use owned::OwnedValue;
+pub use parse::{
+    from_slice, from_slice_lossy, from_slice_with_limit, from_str, from_str_lenient,
+    from_str_partial, from_str_with_limit, parse_all,
+};
+#[cfg(feature = "raw_value")]
+pub use parse::from_str_lazy;
+pub use patch::{PatchError, PatchOp};
+pub use scratch::{from_str_with_scratch, Scratch};
+pub use shape::{Shape, ShapeError};
+pub use span::{from_str_spanned, Spans};
+pub use stream::{from_ndjson, stream_array};
 pub use value::Value;
+pub use visit::Visitor;
+pub use write::{FloatFormat, WriteConfig};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::borrow::Cow;
+    pub use alloc::vec;
+
+    pub use crate::macros::{into_value, new_object, IntoValue};
+}
@@ -0,0 +1,446 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::num::Number;
+use crate::Value;
+
+/// Constructs a [`Value`] from a JSON literal, like `serde_json::json!`.
+///
+/// Objects, arrays, `null`, `true`, `false`, numbers and strings are written in JSON syntax.
+/// Any other Rust expression may be interpolated as a value or as an object key. String
+/// literals and other `&str` values are borrowed, `String`s are moved into the `Value`.
+///
+/// Values can be interpolated if they are strings (`&str`, `String`, `Cow<str>`), numbers,
+/// booleans, [`Number`], `Value`, `Option` (where `None` becomes `null`) or `Vec` of those.
+/// Keys can be any expression which converts into a `Cow<str>`.
+///
+/// Like parsed objects, the constructed objects keep the order of their entries, including
+/// duplicate keys.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::json_borrow;
+///
+/// let name = String::from("Alice");
+/// let key = "age";
+/// let value = json_borrow!({
+///     "name": name,
+///     key: 42,
+///     "tags": ["a", "b", null],
+///     "address": {"city": "Paris", "zip": None::<&str>},
+///     "active": true
+/// });
+///
+/// assert_eq!(value.get("name"), "Alice");
+/// assert_eq!(value.get("age"), 42);
+/// assert_eq!(
+///     value.to_string(),
+///     r#"{"name":"Alice","age":42,"tags":["a","b",null],"address":{"city":"Paris","zip":null},"active":true}"#
+/// );
+/// ```
+#[macro_export]
+macro_rules! json_borrow {
+    ($($json:tt)+) => {
+        $crate::__json_borrow_internal!($($json)+)
+    };
+}
+
+// Based on the `json!` macro of `serde_json`, which munches the tokens of arrays and objects one
+// element at a time.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __json_borrow_internal {
+    //////////////////////////////////////////////////////////////////////////
+    // Array elements, accumulated in `[...]`.
+    //////////////////////////////////////////////////////////////////////////
+
+    // Done with trailing comma.
+    (@array [$($elems:expr,)*]) => {
+        $crate::__private::vec![$($elems,)*]
+    };
+
+    // Done without trailing comma.
+    (@array [$($elems:expr),*]) => {
+        $crate::__private::vec![$($elems),*]
+    };
+
+    // Next element is `null`.
+    (@array [$($elems:expr,)*] null $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!(null)] $($rest)*)
+    };
+
+    // Next element is `true`.
+    (@array [$($elems:expr,)*] true $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!(true)] $($rest)*)
+    };
+
+    // Next element is `false`.
+    (@array [$($elems:expr,)*] false $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!(false)] $($rest)*)
+    };
+
+    // Next element is an array.
+    (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!([$($array)*])] $($rest)*)
+    };
+
+    // Next element is an object.
+    (@array [$($elems:expr,)*] {$($object:tt)*} $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!({$($object)*})] $($rest)*)
+    };
+
+    // Next element is an expression followed by comma.
+    (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!($next),] $($rest)*)
+    };
+
+    // Last element is an expression with no trailing comma.
+    (@array [$($elems:expr,)*] $last:expr) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)* $crate::__json_borrow_internal!($last)])
+    };
+
+    // Comma after the most recent element.
+    (@array [$($elems:expr),*] , $($rest:tt)*) => {
+        $crate::__json_borrow_internal!(@array [$($elems,)*] $($rest)*)
+    };
+
+    // Unexpected token after most recent element.
+    (@array [$($elems:expr),*] $unexpected:tt $($rest:tt)*) => {
+        $crate::__json_borrow_unexpected!($unexpected)
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // Object entries, pushed onto the vector `$object`. The current key is
+    // accumulated in `(...)` and moved to `[...]` once its value is known.
+    //////////////////////////////////////////////////////////////////////////
+
+    // Done.
+    (@object $object:ident () () ()) => {};
+
+    // Insert the current entry followed by trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+        $object.push(($crate::__private::Cow::from($($key)+), $value));
+        $crate::__json_borrow_internal!(@object $object () ($($rest)*) ($($rest)*));
+    };
+
+    // Current entry followed by unexpected token.
+    (@object $object:ident [$($key:tt)+] ($value:expr) $unexpected:tt $($rest:tt)*) => {
+        $crate::__json_borrow_unexpected!($unexpected);
+    };
+
+    // Insert the last entry without trailing comma.
+    (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+        $object.push(($crate::__private::Cow::from($($key)+), $value));
+    };
+
+    // Next value is `null`.
+    (@object $object:ident ($($key:tt)+) (: null $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!(null)) $($rest)*);
+    };
+
+    // Next value is `true`.
+    (@object $object:ident ($($key:tt)+) (: true $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!(true)) $($rest)*);
+    };
+
+    // Next value is `false`.
+    (@object $object:ident ($($key:tt)+) (: false $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!(false)) $($rest)*);
+    };
+
+    // Next value is an array.
+    (@object $object:ident ($($key:tt)+) (: [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!([$($array)*])) $($rest)*);
+    };
+
+    // Next value is an object.
+    (@object $object:ident ($($key:tt)+) (: {$($map:tt)*} $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!({$($map)*})) $($rest)*);
+    };
+
+    // Next value is an expression followed by comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!($value)) , $($rest)*);
+    };
+
+    // Last value is an expression with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:expr) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object [$($key)+] ($crate::__json_borrow_internal!($value)));
+    };
+
+    // Missing value for last entry. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)+) (:) $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::__json_borrow_internal!();
+    };
+
+    // Missing colon and value for last entry. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)+) () $copy:tt) => {
+        // "unexpected end of macro invocation"
+        $crate::__json_borrow_internal!();
+    };
+
+    // Misplaced colon. Trigger a reasonable error message.
+    (@object $object:ident () (: $($rest:tt)*) ($colon:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `:`".
+        $crate::__json_borrow_unexpected!($colon);
+    };
+
+    // Found a comma inside a key. Trigger a reasonable error message.
+    (@object $object:ident ($($key:tt)*) (, $($rest:tt)*) ($comma:tt $($copy:tt)*)) => {
+        // Takes no arguments so "no rules expected the token `,`".
+        $crate::__json_borrow_unexpected!($comma);
+    };
+
+    // Key is fully parenthesized. This avoids clippy double_parens false positives because the
+    // parenthesization may be necessary here.
+    (@object $object:ident () (($key:expr) : $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object ($key) (: $($rest)*) (: $($rest)*));
+    };
+
+    // Refuse to absorb colon token into key expression.
+    (@object $object:ident ($($key:tt)*) (: $($unexpected:tt)+) $copy:tt) => {
+        $crate::__json_borrow_expect_expr_comma!($($unexpected)+);
+    };
+
+    // Munch a token into the current key.
+    (@object $object:ident ($($key:tt)*) ($tt:tt $($rest:tt)*) $copy:tt) => {
+        $crate::__json_borrow_internal!(@object $object ($($key)* $tt) ($($rest)*) ($($rest)*));
+    };
+
+    //////////////////////////////////////////////////////////////////////////
+    // The main implementation.
+    //////////////////////////////////////////////////////////////////////////
+
+    (null) => {
+        $crate::Value::Null
+    };
+
+    (true) => {
+        $crate::Value::Bool(true)
+    };
+
+    (false) => {
+        $crate::Value::Bool(false)
+    };
+
+    ([]) => {
+        $crate::Value::Array($crate::__private::vec![])
+    };
+
+    ([ $($tt:tt)+ ]) => {
+        $crate::Value::Array($crate::__json_borrow_internal!(@array [] $($tt)+))
+    };
+
+    ({}) => {
+        $crate::Value::Object($crate::__private::vec![])
+    };
+
+    ({ $($tt:tt)+ }) => {
+        $crate::Value::Object({
+            let mut object = $crate::__private::new_object();
+            $crate::__json_borrow_internal!(@object object () ($($tt)+) ($($tt)+));
+            object
+        })
+    };
+
+    // Any interpolated value, converted via `IntoValue`. Must be below every other rule.
+    ($other:expr) => {
+        $crate::__private::into_value($other)
+    };
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __json_borrow_unexpected {
+    () => {};
+}
+
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __json_borrow_expect_expr_comma {
+    ($e:expr , $($tt:tt)*) => {};
+}
+
+/// Conversion of values interpolated into [`json_borrow!`].
+pub trait IntoValue<'ctx> {
+    /// Converts `self` into a `Value`.
+    fn into_value(self) -> Value<'ctx>;
+}
+
+/// Converts an interpolated value, see [`IntoValue`].
+#[inline]
+pub fn into_value<'ctx, T: IntoValue<'ctx>>(value: T) -> Value<'ctx> {
+    value.into_value()
+}
+
+/// Creates the entries of an object, without triggering `clippy::vec_init_then_push` in the
+/// caller's code.
+#[inline]
+pub fn new_object<'ctx>() -> Vec<(Cow<'ctx, str>, Value<'ctx>)> {
+    Vec::new()
+}
+
+impl<'ctx> IntoValue<'ctx> for Value<'ctx> {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        self
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for () {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Null
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for bool {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Bool(self)
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for &'ctx str {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Str(Cow::Borrowed(self))
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for &'ctx String {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Str(Cow::Borrowed(self))
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for String {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Str(Cow::Owned(self))
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for Cow<'ctx, str> {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Str(self)
+    }
+}
+
+impl<'ctx> IntoValue<'ctx> for Number {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Number(self)
+    }
+}
+
+macro_rules! into_value_number {
+    ($($ty:ty => $conv:ty),*) => {
+        $(
+            impl<'ctx> IntoValue<'ctx> for $ty {
+                #[inline]
+                fn into_value(self) -> Value<'ctx> {
+                    Value::Number(Number::from(self as $conv))
+                }
+            }
+        )*
+    };
+}
+
+into_value_number! {
+    u8 => u64, u16 => u64, u32 => u64, u64 => u64, usize => u64, u128 => u128,
+    i8 => i64, i16 => i64, i32 => i64, i64 => i64, isize => i64, i128 => i128,
+    f32 => f64, f64 => f64
+}
+
+impl<'ctx, T: IntoValue<'ctx>> IntoValue<'ctx> for Option<T> {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        self.map_or(Value::Null, T::into_value)
+    }
+}
+
+impl<'ctx, T: IntoValue<'ctx>> IntoValue<'ctx> for Vec<T> {
+    #[inline]
+    fn into_value(self) -> Value<'ctx> {
+        Value::Array(self.into_iter().map(T::into_value).collect())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::{Number, Value};
+
+    #[test]
+    fn json_borrow_literals_test() {
+        assert_eq!(json_borrow!(null), Value::Null);
+        assert_eq!(json_borrow!(true), Value::Bool(true));
+        assert_eq!(json_borrow!(false), Value::Bool(false));
+        assert_eq!(json_borrow!(1), Value::Number(1u64.into()));
+        assert_eq!(json_borrow!(-1), Value::Number((-1i64).into()));
+        assert_eq!(json_borrow!(1.5), Value::Number(1.5.into()));
+        assert_eq!(json_borrow!("a"), Value::Str("a".into()));
+        assert_eq!(json_borrow!([]), Value::Array(Vec::new()));
+        assert_eq!(json_borrow!({}), Value::Object(Vec::new()));
+    }
+
+    #[test]
+    fn json_borrow_nested_test() {
+        let value = json_borrow!({
+            "a": [1, -2.5, "x", null, true, false, [], {}, [[{"b": "c"}]],],
+            "d": {"e": {"f": null}},
+            "a": "duplicate",
+        });
+        let expected: Value = serde_json::from_str(
+            r#"{"a": [1, -2.5, "x", null, true, false, [], {}, [[{"b": "c"}]]], "d": {"e": {"f": null}}, "a": "duplicate"}"#,
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn json_borrow_interpolation_test() {
+        let borrowed = String::from("borrowed");
+        let owned = String::from("owned");
+        let key = String::from("dynamic");
+        let nested = json_borrow!([1, 2]);
+        let value = json_borrow!({
+            "borrowed": &borrowed,
+            "owned": owned,
+            "slice": borrowed.as_str(),
+            key: nested,
+            ("com" .to_owned() + "puted"): 1 + 2,
+            "number": Number::from(u128::MAX),
+            "some": Some(1u8),
+            "none": None::<i32>,
+            "vec": vec!["a", "b"],
+            "cow": Cow::Borrowed("c"),
+            "float": 0.5f32,
+        });
+
+        assert!(matches!(
+            value.get("borrowed"),
+            Value::Str(Cow::Borrowed("borrowed"))
+        ));
+        assert!(matches!(value.get("owned"), Value::Str(Cow::Owned(s)) if s == "owned"));
+        assert!(matches!(
+            value.get("slice"),
+            Value::Str(Cow::Borrowed("borrowed"))
+        ));
+        assert_eq!(value.get("dynamic").to_string(), "[1,2]");
+        assert_eq!(value.get("computed"), 3);
+        assert_eq!(value.get("number").as_u128(), Some(u128::MAX));
+        assert_eq!(value.get("some"), 1);
+        assert!(value.get("none").is_null());
+        assert_eq!(value.get("vec").to_string(), r#"["a","b"]"#);
+        assert_eq!(value.get("cow"), "c");
+        assert_eq!(value.get("float"), 0.5);
+    }
+}
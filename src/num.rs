@@ -0,0 +1,927 @@
+#[cfg(feature = "arbitrary_precision")]
+use alloc::boxed::Box;
+#[cfg(feature = "arbitrary_precision")]
+use alloc::string::ToString;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::str::FromStr;
+
+/// Represents a JSON number, whether integer or floating point.
+///
+/// Integers outside of the 64-bit range are stored as 128-bit integers, if the deserializer
+/// provides them as such. Note that `serde_json` parses them as floats, unless the
+/// `arbitrary_precision` feature is enabled.
+///
+/// With the `arbitrary_precision` feature, numbers which can't be represented exactly as integers
+/// or floats, e.g. floats with many significant digits, keep their original text and are
+/// serialized verbatim. `serde_json` normalizes the exponent of the text, e.g. `1E5` to `1e+5`.
+/// Accessors like `as_f64` parse the text on demand. Note that this enables `serde_json`'s
+/// `arbitrary_precision` feature, which affects all users of `serde_json` in the dependency graph.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub(crate) n: N,
+}
+
+#[derive(Clone)]
+pub(crate) enum N {
+    PosInt(u64),
+    /// Always less than zero.
+    NegInt(i64),
+    /// Always finite.
+    Float(f64),
+    /// Always greater than `u64::MAX`.
+    PosInt128(u128),
+    /// Always less than `i64::MIN`.
+    NegInt128(i128),
+    /// The verbatim number token, if it can't be represented by the integer variants.
+    #[cfg(feature = "arbitrary_precision")]
+    Raw(Box<str>),
+}
+
+impl Number {
+    /// If the `Number` is an integer, represent it as u64 if possible. Returns
+    /// None otherwise.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.n {
+            N::PosInt(v) => Some(v),
+            _ => None,
+        }
+    }
+    /// If the `Number` is an integer, represent it as i64 if possible. Returns
+    /// None otherwise.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.n {
+            N::PosInt(n) => {
+                if n <= i64::MAX as u64 {
+                    Some(n as i64)
+                } else {
+                    None
+                }
+            }
+            N::NegInt(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// If the `Number` is an integer, represent it as u128 if possible. Returns
+    /// None otherwise.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self.n {
+            N::PosInt(v) => Some(v as u128),
+            N::PosInt128(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// If the `Number` is an integer, represent it as i128 if possible. Returns
+    /// None otherwise.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self.n {
+            N::PosInt(v) => Some(v as i128),
+            N::NegInt(v) => Some(v as i128),
+            N::PosInt128(v) => i128::try_from(v).ok(),
+            N::NegInt128(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Represents the number as f64 if possible. Returns None otherwise.
+    ///
+    /// 128-bit integers may lose precision. Like in `serde_json`, numbers kept verbatim with the
+    /// `arbitrary_precision` feature return None if they are outside of the f64 range, instead of
+    /// infinity.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.n {
+            N::PosInt(n) => Some(n as f64),
+            N::NegInt(n) => Some(n as f64),
+            N::Float(n) => Some(n),
+            N::PosInt128(n) => Some(n as f64),
+            N::NegInt128(n) => Some(n as f64),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => s.parse().ok().filter(|n: &f64| n.is_finite()),
+        }
+    }
+
+    /// Represents the number as f32 if possible. Returns None otherwise.
+    ///
+    /// The conversion may lose precision, since f32 has fewer significant digits than f64. Numbers
+    /// outside of the f32 range return None instead of infinity.
+    pub fn as_f32(&self) -> Option<f32> {
+        self.as_f64().map(|n| n as f32).filter(|n| n.is_finite())
+    }
+
+    /// If the `Number` is an integer, represent it as i32 if it fits. Returns None otherwise.
+    pub fn as_i32(&self) -> Option<i32> {
+        self.as_i64().and_then(|n| i32::try_from(n).ok())
+    }
+
+    /// If the `Number` is an integer, represent it as u32 if it fits. Returns None otherwise.
+    pub fn as_u32(&self) -> Option<u32> {
+        self.as_u64().and_then(|n| u32::try_from(n).ok())
+    }
+
+    /// Returns true if the `Number` is a f64.
+    ///
+    /// With the `arbitrary_precision` feature, this is also true for numbers stored verbatim.
+    pub fn is_f64(&self) -> bool {
+        self.float_value().is_some()
+    }
+
+    /// Returns true if the `Number` is a u64.
+    pub fn is_u64(&self) -> bool {
+        matches!(self.n, N::PosInt(_))
+    }
+
+    /// Returns true if the `Number` is an integer between `i64::MIN` and
+    /// `i64::MAX`.
+    pub fn is_i64(&self) -> bool {
+        match self.n {
+            N::PosInt(v) => v <= i64::MAX as u64,
+            N::NegInt(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if the `Number` is an integer between zero and `u128::MAX`.
+    pub fn is_u128(&self) -> bool {
+        matches!(self.n, N::PosInt(_) | N::PosInt128(_))
+    }
+
+    /// Returns true if the `Number` is an integer between `i128::MIN` and
+    /// `i128::MAX`.
+    pub fn is_i128(&self) -> bool {
+        match self.n {
+            N::PosInt(_) | N::NegInt(_) | N::NegInt128(_) => true,
+            N::PosInt128(v) => v <= i128::MAX as u128,
+            _ => false,
+        }
+    }
+
+    /// Compares the numeric values of two numbers, regardless of how they are stored.
+    ///
+    /// Integers and floats are compared exactly, without casting integers to `f64`, so e.g.
+    /// `u64::MAX` is less than `u64::MAX as f64`, which is 2^64, and `9007199254740993` is greater
+    /// than `9007199254740992.0`. Unlike `Ord`, an integer and a float with the same value are
+    /// equal, e.g. `1` and `1.0`. Returns `None` if either number is NaN.
+    ///
+    /// With the `arbitrary_precision` feature, numbers stored verbatim are converted to the nearest
+    /// `f64` and only compared approximately: `1.0000000000000000000001` equals `1`, and numbers
+    /// beyond the `f64` range compare like infinity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    ///
+    /// use serde_json_borrow::Number;
+    ///
+    /// let max = Number::from(u64::MAX);
+    /// assert_eq!(max.cmp_value(&Number::from(u64::MAX as f64)), Some(Ordering::Less));
+    /// assert_eq!(max.cmp_value(&Number::from(-1i64)), Some(Ordering::Greater));
+    /// assert_eq!(Number::from(1u64).cmp_value(&Number::from(1.0)), Some(Ordering::Equal));
+    /// ```
+    pub fn cmp_value(&self, other: &Number) -> Option<Ordering> {
+        let is_nan = |n: &Number| n.float_value().is_some_and(f64::is_nan);
+        if is_nan(self) || is_nan(other) {
+            return None;
+        }
+        Some(cmp_numeric(self, other))
+    }
+
+    /// Returns the verbatim number token, if the number is stored as such.
+    #[cfg(feature = "arbitrary_precision")]
+    pub fn as_raw_str(&self) -> Option<&str> {
+        match &self.n {
+            N::Raw(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    pub(crate) fn as_raw_str(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the value of floats and numbers stored verbatim.
+    fn float_value(&self) -> Option<f64> {
+        match self.n {
+            N::Float(f) => Some(f),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => s.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Converts an integral float into the integer variants, keeping other numbers unchanged.
+    pub(crate) fn normalize(&mut self) {
+        if let N::Float(f) = self.n {
+            if let Some(n) = float_as_u128(f) {
+                *self = n.into();
+            } else if let Some(n) = float_as_i128(f) {
+                *self = n.into();
+            }
+        }
+    }
+
+    /// Converts integers and numbers stored verbatim into floats, which may lose precision.
+    /// Numbers stored verbatim which are out of the float range are kept unchanged.
+    pub(crate) fn floatify(&mut self) {
+        let f = match self.n {
+            N::Float(_) => return,
+            N::PosInt(n) => n as f64,
+            N::NegInt(n) => n as f64,
+            N::PosInt128(n) => n as f64,
+            N::NegInt128(n) => n as f64,
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => match s.parse::<f64>() {
+                Ok(f) if f.is_finite() => f,
+                _ => return,
+            },
+        };
+        self.n = N::Float(f);
+    }
+
+    /// Parses a JSON number token, keeping it verbatim if it can't be represented exactly.
+    #[cfg(feature = "arbitrary_precision")]
+    pub(crate) fn from_token(token: &str) -> Self {
+        let is_integer = !token.contains(['.', 'e', 'E']);
+        if is_integer && token != "-0" {
+            if let Ok(n) = token.parse::<u128>() {
+                return n.into();
+            }
+            if let Ok(n) = token.parse::<i128>() {
+                return n.into();
+            }
+        }
+        // Floats which are formatted back to the same text don't need to be kept verbatim.
+        if let Ok(f) = token.parse::<f64>() {
+            if f.is_finite() && serde_json::to_string(&f).ok().as_deref() == Some(token) {
+                return f.into();
+            }
+        }
+        Self {
+            n: N::Raw(token.into()),
+        }
+    }
+}
+
+impl fmt::Debug for Number {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.n {
+            N::PosInt(n) => write!(formatter, "Number({:?})", n),
+            N::NegInt(n) => write!(formatter, "Number({:?})", n),
+            N::Float(n) => write!(formatter, "Number({:?})", n),
+            N::PosInt128(n) => write!(formatter, "Number({:?})", n),
+            N::NegInt128(n) => write!(formatter, "Number({:?})", n),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => write!(formatter, "Number({})", s),
+        }
+    }
+}
+
+/// Numbers are ordered by their numeric value. Comparisons between integers and floats are exact.
+///
+/// To be consistent with `Eq`, an integer orders before a float with the same numeric value, e.g.
+/// `1 < 1.0`. Numbers stored verbatim order after floats with the same value, and by their text
+/// among each other.
+impl Ord for Number {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_numeric(self, other)
+            .then_with(|| self.rank().cmp(&other.rank()))
+            .then_with(|| self.as_raw_str().cmp(&other.as_raw_str()))
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Number {
+    fn rank(&self) -> u8 {
+        match self.n {
+            N::Float(_) => 1,
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(_) => 2,
+            _ => 0,
+        }
+    }
+}
+
+/// Unifies the integer variants for comparisons.
+enum Int {
+    Neg(i128),
+    Pos(u128),
+}
+
+fn cmp_numeric(a: &Number, b: &Number) -> Ordering {
+    let as_int = |n: &Number| match n.n {
+        N::PosInt(n) => Some(Int::Pos(n as u128)),
+        N::PosInt128(n) => Some(Int::Pos(n)),
+        N::NegInt(n) if n >= 0 => Some(Int::Pos(n as u128)),
+        N::NegInt(n) => Some(Int::Neg(n as i128)),
+        N::NegInt128(n) => Some(Int::Neg(n)),
+        _ => None,
+    };
+    match (a.float_value(), b.float_value()) {
+        (Some(fa), Some(fb)) => fa.partial_cmp(&fb).unwrap_or_else(|| fa.total_cmp(&fb)),
+        (Some(fa), None) => cmp_int_float(as_int(b).unwrap(), fa).reverse(),
+        (None, Some(fb)) => cmp_int_float(as_int(a).unwrap(), fb),
+        (None, None) => match (as_int(a).unwrap(), as_int(b).unwrap()) {
+            (Int::Neg(a), Int::Neg(b)) => a.cmp(&b),
+            (Int::Pos(a), Int::Pos(b)) => a.cmp(&b),
+            (Int::Neg(_), Int::Pos(_)) => Ordering::Less,
+            (Int::Pos(_), Int::Neg(_)) => Ordering::Greater,
+        },
+    }
+}
+
+/// Compares an integer with a float exactly, without casting the integer to a float.
+fn cmp_int_float(int: Int, f: f64) -> Ordering {
+    if f.is_nan() {
+        // Floats are always finite, order NaN like `total_cmp` does.
+        return if f.is_sign_negative() {
+            Ordering::Greater
+        } else {
+            Ordering::Less
+        };
+    }
+    let cmp_fract = |ord: Ordering, fract: f64| {
+        ord.then_with(|| 0.0.partial_cmp(&fract).unwrap_or(Ordering::Equal))
+    };
+    match int {
+        Int::Neg(_) if f >= 0.0 => Ordering::Less,
+        Int::Pos(_) if f < 0.0 => Ordering::Greater,
+        Int::Neg(_) if f < -TWO_POW_127 => Ordering::Greater,
+        Int::Pos(_) if f >= TWO_POW_128 => Ordering::Less,
+        // The float is within the range of the integer type, so the cast truncates it exactly and
+        // the integral part converts back to the float without loss.
+        Int::Neg(n) => {
+            let trunc = f as i128;
+            cmp_fract(n.cmp(&trunc), f - trunc as f64)
+        }
+        Int::Pos(n) => {
+            let trunc = f as u128;
+            cmp_fract(n.cmp(&trunc), f - trunc as f64)
+        }
+    }
+}
+
+impl PartialEq for N {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (N::PosInt(a), N::PosInt(b)) => a == b,
+            (N::NegInt(a), N::NegInt(b)) => a == b,
+            (N::Float(a), N::Float(b)) => a == b,
+            (N::PosInt128(a), N::PosInt128(b)) => a == b,
+            (N::NegInt128(a), N::NegInt128(b)) => a == b,
+            #[cfg(feature = "arbitrary_precision")]
+            (N::Raw(a), N::Raw(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+// Implementing Eq is fine since any float values are always finite.
+impl Eq for N {}
+
+impl Hash for N {
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        match *self {
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => s.hash(h),
+            N::PosInt(i) => i.hash(h),
+            N::NegInt(i) => i.hash(h),
+            N::Float(f) => {
+                if f == 0.0f64 {
+                    // There are 2 zero representations, +0 and -0, which
+                    // compare equal but have different bits. We use the +0 hash
+                    // for both so that hash(+0) == hash(-0).
+                    0.0f64.to_bits().hash(h);
+                } else {
+                    f.to_bits().hash(h);
+                }
+            }
+            N::PosInt128(i) => i.hash(h),
+            N::NegInt128(i) => i.hash(h),
+        }
+    }
+}
+
+impl From<u64> for Number {
+    fn from(val: u64) -> Self {
+        Self { n: N::PosInt(val) }
+    }
+}
+
+impl From<i64> for Number {
+    fn from(val: i64) -> Self {
+        if val < 0 {
+            Self { n: N::NegInt(val) }
+        } else {
+            Self {
+                n: N::PosInt(val as u64),
+            }
+        }
+    }
+}
+
+impl From<u128> for Number {
+    fn from(val: u128) -> Self {
+        match u64::try_from(val) {
+            Ok(val) => val.into(),
+            Err(_) => Self {
+                n: N::PosInt128(val),
+            },
+        }
+    }
+}
+
+impl From<i128> for Number {
+    fn from(val: i128) -> Self {
+        if let Ok(val) = u128::try_from(val) {
+            val.into()
+        } else if let Ok(val) = i64::try_from(val) {
+            val.into()
+        } else {
+            Self {
+                n: N::NegInt128(val),
+            }
+        }
+    }
+}
+
+impl From<f64> for Number {
+    fn from(val: f64) -> Self {
+        Self { n: N::Float(val) }
+    }
+}
+
+/// Converts into a `serde_json::value::Number`.
+///
+/// 128-bit integers outside of the 64-bit range can't be represented by `serde_json` and are
+/// converted into a float, which may lose precision, unless the `arbitrary_precision` feature is
+/// enabled.
+impl From<Number> for serde_json::value::Number {
+    fn from(num: Number) -> Self {
+        match num.n {
+            N::PosInt(n) => n.into(),
+            N::NegInt(n) => n.into(),
+            N::Float(n) => serde_json::value::Number::from_f64(n).unwrap(),
+            #[cfg(not(feature = "arbitrary_precision"))]
+            N::PosInt128(n) => serde_json::value::Number::from_f64(n as f64).unwrap(),
+            #[cfg(not(feature = "arbitrary_precision"))]
+            N::NegInt128(n) => serde_json::value::Number::from_f64(n as f64).unwrap(),
+            #[cfg(feature = "arbitrary_precision")]
+            N::PosInt128(n) => serde_json::value::Number::from_string_unchecked(n.to_string()),
+            #[cfg(feature = "arbitrary_precision")]
+            N::NegInt128(n) => serde_json::value::Number::from_string_unchecked(n.to_string()),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(s) => serde_json::value::Number::from_string_unchecked(s.into()),
+        }
+    }
+}
+
+/// 2^127 as float, which is exactly representable. `f64::powi` is not available without `std`.
+const TWO_POW_127: f64 = 170_141_183_460_469_231_731_687_303_715_884_105_728.0;
+/// 2^128 as float, which is exactly representable.
+const TWO_POW_128: f64 = 340_282_366_920_938_463_463_374_607_431_768_211_456.0;
+
+/// Returns the float as i128, if it is an integer within the i128 range.
+fn float_as_i128(f: f64) -> Option<i128> {
+    // The cast is lossless for integral floats in range, and truncates others.
+    if (-TWO_POW_127..TWO_POW_127).contains(&f) && f as i128 as f64 == f {
+        Some(f as i128)
+    } else {
+        None
+    }
+}
+
+/// Returns the float as u128, if it is an integer within the u128 range.
+fn float_as_u128(f: f64) -> Option<u128> {
+    if (0.0..TWO_POW_128).contains(&f) && f as u128 as f64 == f {
+        Some(f as u128)
+    } else {
+        None
+    }
+}
+
+/// Compares the value numerically, an integer-valued float equals the integer.
+impl PartialEq<i64> for Number {
+    fn eq(&self, other: &i64) -> bool {
+        match self.float_value() {
+            Some(f) => float_as_i128(f) == Some(*other as i128),
+            None => self.as_i64() == Some(*other),
+        }
+    }
+}
+
+/// Compares the value numerically, an integer-valued float equals the integer.
+impl PartialEq<u64> for Number {
+    fn eq(&self, other: &u64) -> bool {
+        match self.float_value() {
+            Some(f) => float_as_u128(f) == Some(*other as u128),
+            None => self.as_u64() == Some(*other),
+        }
+    }
+}
+
+/// Compares the value numerically, an integer-valued float equals the integer.
+impl PartialEq<f64> for Number {
+    fn eq(&self, other: &f64) -> bool {
+        match self.n {
+            N::Float(f) => f == *other,
+            N::PosInt(n) => float_as_u128(*other) == Some(n as u128),
+            N::PosInt128(n) => float_as_u128(*other) == Some(n),
+            N::NegInt(n) => float_as_i128(*other) == Some(n as i128),
+            N::NegInt128(n) => float_as_i128(*other) == Some(n),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(_) => self.float_value() == Some(*other),
+        }
+    }
+}
+
+impl From<&serde_json::value::Number> for Number {
+    #[cfg(feature = "arbitrary_precision")]
+    fn from(num: &serde_json::value::Number) -> Self {
+        Number::from_token(&num.to_string())
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    fn from(num: &serde_json::value::Number) -> Self {
+        if let Some(n) = num.as_u64() {
+            n.into()
+        } else if let Some(n) = num.as_i64() {
+            n.into()
+        } else {
+            // A `serde_json::value::Number` is always one of u64, i64 or f64.
+            num.as_f64().unwrap_or_default().into()
+        }
+    }
+}
+
+/// Parses a number following the JSON number grammar.
+///
+/// Integers become integer numbers, using 128 bits if they don't fit into 64 bits. Larger integers
+/// and numbers with a fraction or exponent become floats, like `serde_json` parses them. Numbers
+/// which aren't valid JSON, e.g. `01`, `+1`, `.5`, `NaN` or `1e400` (out of the f64 range), are
+/// rejected. With the `arbitrary_precision` feature, numbers are kept verbatim if they can't be
+/// represented exactly.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::Number;
+///
+/// assert!("42".parse::<Number>().unwrap().is_u64());
+/// assert!("-1.5e3".parse::<Number>().unwrap().is_f64());
+/// assert!("01".parse::<Number>().is_err());
+/// assert!("NaN".parse::<Number>().is_err());
+/// ```
+impl FromStr for Number {
+    type Err = ParseNumberError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let is_integer = validate_number(s)?;
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            let _ = is_integer;
+            Ok(Number::from_token(s))
+        }
+        #[cfg(not(feature = "arbitrary_precision"))]
+        {
+            // Like `serde_json`, `-0` is parsed as a float to keep its sign.
+            if is_integer && s != "-0" {
+                if let Ok(n) = s.parse::<u128>() {
+                    return Ok(n.into());
+                }
+                if let Ok(n) = s.parse::<i128>() {
+                    return Ok(n.into());
+                }
+            }
+            match s.parse::<f64>() {
+                Ok(f) if f.is_finite() => Ok(f.into()),
+                _ => Err(ParseNumberError::OutOfRange),
+            }
+        }
+    }
+}
+
+/// Checks that `s` matches the JSON number grammar and returns whether it is an integer.
+fn validate_number(s: &str) -> Result<bool, ParseNumberError> {
+    fn digits(bytes: &[u8]) -> usize {
+        bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+    }
+    let bytes = s.as_bytes();
+    let mut pos = usize::from(bytes.first() == Some(&b'-'));
+    let int_digits = digits(&bytes[pos..]);
+    if int_digits == 0 || (int_digits > 1 && bytes[pos] == b'0') {
+        return Err(ParseNumberError::Invalid);
+    }
+    pos += int_digits;
+    let mut is_integer = true;
+    if bytes.get(pos) == Some(&b'.') {
+        let frac_digits = digits(&bytes[pos + 1..]);
+        if frac_digits == 0 {
+            return Err(ParseNumberError::Invalid);
+        }
+        pos += 1 + frac_digits;
+        is_integer = false;
+    }
+    if matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        pos += 1;
+        if matches!(bytes.get(pos), Some(b'+' | b'-')) {
+            pos += 1;
+        }
+        let exp_digits = digits(&bytes[pos..]);
+        if exp_digits == 0 {
+            return Err(ParseNumberError::Invalid);
+        }
+        pos += exp_digits;
+        is_integer = false;
+    }
+    if pos != bytes.len() {
+        return Err(ParseNumberError::Invalid);
+    }
+    Ok(is_integer)
+}
+
+/// An error returned when parsing a [`Number`] from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNumberError {
+    /// The string doesn't follow the JSON number grammar.
+    Invalid,
+    /// The number is outside of the range of f64.
+    OutOfRange,
+}
+
+impl fmt::Display for ParseNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseNumberError::Invalid => write!(f, "invalid JSON number"),
+            ParseNumberError::OutOfRange => write!(f, "number out of range"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseNumberError {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_128_test() {
+        let big = Number::from(u64::MAX as u128 + 1);
+        assert!(big.is_u128());
+        assert!(big.is_i128());
+        assert!(!big.is_u64());
+        assert!(!big.is_i64());
+        assert_eq!(big.as_u128(), Some(u64::MAX as u128 + 1));
+        assert_eq!(big.as_i128(), Some(u64::MAX as i128 + 1));
+        assert_eq!(big.as_u64(), None);
+
+        let huge = Number::from(u128::MAX);
+        assert!(huge.is_u128());
+        assert!(!huge.is_i128());
+        assert_eq!(huge.as_i128(), None);
+
+        let small = Number::from(i64::MIN as i128 - 1);
+        assert!(small.is_i128());
+        assert!(!small.is_u128());
+        assert!(!small.is_i64());
+        assert_eq!(small.as_i128(), Some(i64::MIN as i128 - 1));
+        assert_eq!(small.as_u128(), None);
+        assert_eq!(small.as_f64(), Some(i64::MIN as f64));
+
+        // Values in the 64-bit range are stored in the 64-bit representation.
+        assert_eq!(Number::from(5u128), Number::from(5u64));
+        assert_eq!(Number::from(5i128), Number::from(5u64));
+        assert_eq!(Number::from(-5i128), Number::from(-5i64));
+        assert!(Number::from(-5i128).is_i128());
+        assert!(Number::from(5u64).is_u128());
+        assert!(!Number::from(1.5).is_i128());
+    }
+
+    #[test]
+    fn number_eq_primitives_test() {
+        assert_eq!(Number::from(1u64), 1u64);
+        assert_eq!(Number::from(1u64), 1i64);
+        assert_eq!(Number::from(1u64), 1.0);
+        assert_eq!(Number::from(-1i64), -1i64);
+        assert_eq!(Number::from(-1i64), -1.0);
+        assert_ne!(Number::from(-1i64), 1u64);
+        assert_eq!(Number::from(2.0), 2u64);
+        assert_eq!(Number::from(-2.0), -2i64);
+        assert_ne!(Number::from(2.5), 2u64);
+        assert_eq!(Number::from(2.5), 2.5);
+        assert_ne!(Number::from(u64::MAX), -1i64);
+        assert_ne!(Number::from(u64::MAX), u64::MAX as f64);
+        assert_ne!(Number::from(u64::MAX as f64), u64::MAX);
+        assert_eq!(Number::from(u64::MAX as u128 + 1), 2f64.powi(64));
+        assert_ne!(Number::from(f64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn number_32_bit_test() {
+        assert_eq!(Number::from(1.5).as_f32(), Some(1.5f32));
+        assert_eq!(Number::from(3u64).as_f32(), Some(3.0f32));
+        assert_eq!(Number::from(0.1).as_f32(), Some(0.1f32));
+        assert_eq!(Number::from(f64::MAX).as_f32(), None);
+
+        assert_eq!(Number::from(-5i64).as_i32(), Some(-5));
+        assert_eq!(Number::from(i32::MAX as u64).as_i32(), Some(i32::MAX));
+        assert_eq!(Number::from(i32::MAX as u64 + 1).as_i32(), None);
+        assert_eq!(Number::from(i32::MIN as i64 - 1).as_i32(), None);
+        assert_eq!(Number::from(1.0).as_i32(), None);
+
+        assert_eq!(Number::from(u32::MAX as u64).as_u32(), Some(u32::MAX));
+        assert_eq!(Number::from(u32::MAX as u64 + 1).as_u32(), None);
+        assert_eq!(Number::from(-1i64).as_u32(), None);
+
+        let value: crate::Value =
+            serde_json::from_str(r#"{"val1": 123.5, "val2": 123, "val3": -123}"#).unwrap();
+        assert_eq!(value.get("val1").as_f32(), Some(123.5));
+        assert_eq!(value.get("val2").as_u32(), Some(123));
+        assert_eq!(value.get("val3").as_i32(), Some(-123));
+        assert_eq!(value.get("val3").as_u32(), None);
+        assert_eq!(value.get("missing").as_f32(), None);
+    }
+
+    #[test]
+    fn number_ord_test() {
+        let ordered = [
+            Number::from(f64::MIN),
+            Number::from(i128::MIN),
+            Number::from(i64::MIN),
+            Number::from(-1.5),
+            Number::from(-1i64),
+            Number::from(-1.0),
+            Number::from(-0.5),
+            Number::from(0u64),
+            Number::from(0.0),
+            Number::from(0.5),
+            Number::from(1u64),
+            Number::from(1.0),
+            Number::from(u64::MAX - 1),
+            Number::from(u64::MAX),
+            Number::from(u64::MAX as f64),
+            Number::from(u64::MAX as u128 + 2),
+            Number::from(u128::MAX),
+            Number::from(f64::MAX),
+        ];
+        for (i, a) in ordered.iter().enumerate() {
+            for (j, b) in ordered.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+                assert_eq!(a == b, i == j, "{:?} vs {:?}", a, b);
+            }
+        }
+        assert_eq!(Number::from(0.0).cmp(&Number::from(-0.0)), Ordering::Equal);
+        assert_eq!(Number::from(5i64), Number::from(5u64));
+    }
+
+    #[test]
+    fn number_cmp_value_test() {
+        let cmp = |a: Number, b: Number| a.cmp_value(&b);
+        assert_eq!(cmp(1u64.into(), 1.0.into()), Some(Ordering::Equal));
+        assert_eq!(cmp((-1i64).into(), (-1.0).into()), Some(Ordering::Equal));
+        assert_eq!(cmp(0u64.into(), (-0.0).into()), Some(Ordering::Equal));
+        // 2^53 + 1 is not representable as `f64`, a cast would round it to 2^53.
+        let above = Number::from(9_007_199_254_740_993u64);
+        assert_eq!(
+            cmp(above.clone(), 9_007_199_254_740_992.0.into()),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            cmp(9_007_199_254_740_992.0.into(), above),
+            Some(Ordering::Less)
+        );
+        // Above the range of `i64`, and `u64::MAX as f64` rounds up to 2^64.
+        assert_eq!(
+            cmp(u64::MAX.into(), (u64::MAX as f64).into()),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            cmp(u64::MAX.into(), i64::MIN.into()),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            cmp(i128::MIN.into(), (-1e300).into()),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(cmp(u128::MAX.into(), 1e300.into()), Some(Ordering::Less));
+        assert_eq!(cmp(2u64.into(), 1.5.into()), Some(Ordering::Greater));
+        assert_eq!(cmp((-2i64).into(), (-1.5).into()), Some(Ordering::Less));
+        assert_eq!(
+            cmp(
+                Number {
+                    n: N::Float(f64::NAN)
+                },
+                1u64.into()
+            ),
+            None
+        );
+        #[cfg(feature = "arbitrary_precision")]
+        {
+            let raw: Number = "1.0000000000000000000001".parse().unwrap();
+            assert_eq!(cmp(raw.clone(), 2u64.into()), Some(Ordering::Less));
+            assert_eq!(cmp(raw, 1u64.into()), Some(Ordering::Equal));
+            let huge: Number = "1e400".parse().unwrap();
+            assert_eq!(cmp(huge, f64::MAX.into()), Some(Ordering::Greater));
+        }
+    }
+
+    #[test]
+    fn serialize_128_test() {
+        let value = crate::Value::Array(vec![
+            crate::Value::Number(u128::MAX.into()),
+            crate::Value::Number(i128::MIN.into()),
+        ]);
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            format!("[{},{}]", u128::MAX, i128::MIN)
+        );
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn arbitrary_precision_test() {
+        let data = r#"[3.141592653589793238462643383279, 1e+400, 340282366920938463463374607431768211456, 18446744073709551616, -0, 1.50, 1.5, 12]"#;
+        let value: crate::Value = serde_json::from_str(data).unwrap();
+
+        // Numbers round-trip verbatim.
+        assert_eq!(value.to_string(), data.replace(' ', ""));
+
+        let pi = value.get(0);
+        assert!(pi.is_f64());
+        assert_eq!(pi.as_f64(), Some(std::f64::consts::PI));
+        assert!(!pi.is_i64());
+        assert_eq!(value.get(1).as_f64(), None);
+        assert!(value.get(1).is_f64());
+        assert!(value.get(2).is_f64());
+        assert!(!value.get(2).is_u128());
+        assert_eq!(value.get(3).as_u128(), Some(u64::MAX as u128 + 1));
+        assert_eq!(value.get(5), 1.5);
+        assert_ne!(value.get(5), value.get(6));
+        assert_eq!(value.get(6), &crate::Value::Number(1.5.into()));
+        assert_eq!(value.get(7).as_u64(), Some(12));
+
+        let Some(crate::Value::Number(n)) = value.iter_array().unwrap().next() else {
+            panic!("expected a number");
+        };
+        assert_eq!(n.as_raw_str(), Some("3.141592653589793238462643383279"));
+        assert!(n > &Number::from(std::f64::consts::PI));
+        assert!(n < &Number::from(4u64));
+
+        let owned: serde_json::Value = value.into();
+        assert_eq!(owned.to_string(), data.replace(' ', ""));
+        assert_eq!(
+            crate::Value::from(&owned).to_string(),
+            data.replace(' ', "")
+        );
+    }
+
+    #[test]
+    fn number_from_str_test() {
+        assert_eq!("0".parse::<Number>(), Ok(Number::from(0u64)));
+        assert_eq!("-12".parse::<Number>(), Ok(Number::from(-12i64)));
+        assert_eq!("1.5".parse::<Number>(), Ok(Number::from(1.5)));
+        assert_eq!("1.0".parse::<Number>(), Ok(Number::from(1.0)));
+        assert_eq!("-2.5E-3".parse::<Number>().unwrap(), -0.0025);
+        assert_eq!(
+            "340282366920938463463374607431768211455".parse::<Number>(),
+            Ok(Number::from(u128::MAX))
+        );
+        assert_eq!(
+            "-170141183460469231731687303715884105728".parse::<Number>(),
+            Ok(Number::from(i128::MIN))
+        );
+        assert!("-0".parse::<Number>().unwrap().is_f64());
+
+        for invalid in [
+            "", "-", "01", "-01", "+1", ".5", "1.", "1.e5", "1e", "1e+", "NaN", "inf", "0x10",
+            " 1", "1 ", "1_000",
+        ] {
+            assert_eq!(
+                invalid.parse::<Number>(),
+                Err(ParseNumberError::Invalid),
+                "{:?}",
+                invalid
+            );
+        }
+    }
+
+    #[cfg(not(feature = "arbitrary_precision"))]
+    #[test]
+    fn number_from_str_range_test() {
+        assert_eq!("1e400".parse::<Number>(), Err(ParseNumberError::OutOfRange));
+        assert!("1e1000000".parse::<Number>().is_err());
+        assert_eq!(
+            "1000000000000000000000000000000000000000".parse::<Number>(),
+            Ok(Number::from(1e39))
+        );
+    }
+}
@@ -0,0 +1,830 @@
+use alloc::borrow::Cow;
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use crate::value::NULL;
+use crate::Value;
+
+/// The entries of a [`Value::Object`].
+type ObjectEntries<'ctx> = Vec<(Cow<'ctx, str>, Value<'ctx>)>;
+
+impl<'ctx> Value<'ctx> {
+    /// If the Value is an object, inserts `value` under `key`.
+    ///
+    /// If the object already contains `key`, the value of its first entry is replaced and the old
+    /// value is returned. Otherwise a new entry is appended and `None` is returned. If the Value is
+    /// not an object, nothing is inserted and `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+    ///
+    /// assert_eq!(data.insert("a", Value::Bool(true)), Some(Value::Number(1u64.into())));
+    /// assert_eq!(data.insert("b", Value::Null), None);
+    /// assert_eq!(data.to_string(), r#"{"a":true,"b":null}"#);
+    /// ```
+    pub fn insert(
+        &mut self,
+        key: impl Into<Cow<'ctx, str>>,
+        value: Value<'ctx>,
+    ) -> Option<Value<'ctx>> {
+        match self.entry(key)? {
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Looks up several keys with a single pass over the entries of an object.
+    ///
+    /// Returns the values in the order of `keys`, with `Value::Null` for keys which are not
+    /// present or if the Value is not an object. Like [`get`](Value::get), the first entry of a
+    /// duplicate key wins. For large objects this is faster than calling `get` for every key,
+    /// which scans the entries each time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"id": 1, "name": "a", "id": 2}"#).unwrap();
+    ///
+    /// let [name, id, missing] = data.get_many(["name", "id", "missing"]);
+    /// assert_eq!(name, "a");
+    /// assert_eq!(id, 1);
+    /// assert_eq!(missing, &Value::Null);
+    /// ```
+    pub fn get_many<const N: usize>(&self, keys: [&str; N]) -> [&Value<'ctx>; N] {
+        let mut found: [Option<&Value<'ctx>>; N] = [None; N];
+        if let Value::Object(obj) = self {
+            let mut remaining = N;
+            for (key, value) in obj {
+                if remaining == 0 {
+                    break;
+                }
+                for (slot, _) in found.iter_mut().zip(keys).filter(|(_, k)| k == key) {
+                    if slot.is_none() {
+                        *slot = Some(value);
+                        remaining -= 1;
+                    }
+                }
+            }
+        }
+        found.map(|value| value.unwrap_or(&NULL))
+    }
+
+    /// Returns all values of the entries with the key `key`, in document order.
+    ///
+    /// Parsing keeps duplicate keys, and [`get`](Value::get) returns the first one. Use this to
+    /// access all of them. Yields nothing if the key doesn't exist or if the Value is not an
+    /// object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"tag": "a", "id": 1, "tag": "b"}"#).unwrap();
+    ///
+    /// let tags: Vec<&str> = data.get_all("tag").filter_map(Value::as_str).collect();
+    /// assert_eq!(tags, ["a", "b"]);
+    /// assert_eq!(data.get_all("missing").count(), 0);
+    /// ```
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a Value<'ctx>> + 'a {
+        self.as_object()
+            .into_iter()
+            .flatten()
+            .filter(move |(k, _)| k == key)
+            .map(|(_, val)| val)
+    }
+
+    /// Looks up `key` in an object, ignoring ASCII case differences.
+    ///
+    /// Returns `Value::Null` if the Value is not an object or no key matches. If several keys
+    /// match, e.g. `"Content-Type"` and `"content-type"`, the first one in document order wins.
+    /// Non-ASCII characters must match exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let headers: Value =
+    ///     serde_json::from_str(r#"{"Content-Type": "text/html", "content-type": "ignored"}"#).unwrap();
+    ///
+    /// assert_eq!(headers.get_ci("CONTENT-TYPE"), "text/html");
+    /// assert_eq!(headers.get_ci("Accept"), &Value::Null);
+    /// ```
+    pub fn get_ci(&self, key: &str) -> &Value<'ctx> {
+        match self {
+            Value::Object(obj) => obj
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map_or(&NULL, |(_, v)| v),
+            _ => &NULL,
+        }
+    }
+
+    /// Looks up `key` in an object, ignoring ASCII case differences, and returns a mutable
+    /// reference to its value.
+    ///
+    /// Returns `None` if the Value is not an object or no key matches. If several keys match, the
+    /// first one in document order wins, like with [`get_ci`](Value::get_ci).
+    pub fn get_ci_mut(&mut self, key: &str) -> Option<&mut Value<'ctx>> {
+        match self {
+            Value::Object(obj) => obj
+                .iter_mut()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the first entry with `key`, if the Value is an object.
+    pub(crate) fn find_key(&self, key: &str) -> Option<&Value<'ctx>> {
+        match self {
+            Value::Object(obj) => obj.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an object and the value of `key` is a string, returns the associated str.
+    /// Returns None otherwise.
+    ///
+    /// This is a shortcut for `value.get(key).as_str()`, the other `get_*` methods work the same
+    /// way for their types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"name": "a", "id": 1}"#).unwrap();
+    ///
+    /// assert_eq!(data.get_str("name"), Some("a"));
+    /// assert_eq!(data.get_str("id"), None);
+    /// assert_eq!(data.get_i64("id"), Some(1));
+    /// assert_eq!(data.get_i64("missing"), None);
+    /// ```
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.find_key(key)?.as_str()
+    }
+
+    /// If the Value is an object and the value of `key` is an integer, represent it as i64 if
+    /// possible. Returns None otherwise.
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.find_key(key)?.as_i64()
+    }
+
+    /// If the Value is an object and the value of `key` is an integer, represent it as u64 if
+    /// possible. Returns None otherwise.
+    pub fn get_u64(&self, key: &str) -> Option<u64> {
+        self.find_key(key)?.as_u64()
+    }
+
+    /// If the Value is an object and the value of `key` is a number, represent it as f64 if
+    /// possible. Returns None otherwise.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.find_key(key)?.as_f64()
+    }
+
+    /// If the Value is an object and the value of `key` is a boolean, returns the associated bool.
+    /// Returns None otherwise.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.find_key(key)?.as_bool()
+    }
+
+    /// If the Value is an object and the value of `key` is an array, returns the associated Vec.
+    /// Returns None otherwise.
+    pub fn get_array(&self, key: &str) -> Option<&Vec<Value<'ctx>>> {
+        self.find_key(key)?.as_array()
+    }
+
+    /// If the Value is an object and the value of `key` is an object, returns the associated Vec
+    /// of (key, value) pairs. Returns None otherwise.
+    pub fn get_object(&self, key: &str) -> Option<&Vec<(Cow<'ctx, str>, Value<'ctx>)>> {
+        self.find_key(key)?.as_object()
+    }
+
+    /// If the Value is an object, builds a `HashMap` from its keys to its values. Returns None
+    /// otherwise.
+    ///
+    /// Building the map costs O(n) once, after which lookups are O(1) instead of scanning the
+    /// entries like [`get`](Value::get) does. For duplicate keys the map contains the value of the
+    /// last entry, like `serde_json::Value` does, whereas `get` returns the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"a": 1, "b": 2, "a": 3}"#).unwrap();
+    ///
+    /// let map = data.as_object_map().unwrap();
+    /// assert_eq!(map.len(), 2);
+    /// assert_eq!(map["a"], 3);
+    /// assert_eq!(map["b"], 2);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn as_object_map(&self) -> Option<HashMap<&str, &Value<'ctx>>> {
+        match self {
+            Value::Object(obj) => Some(obj.iter().map(|(k, v)| (k.as_ref(), v)).collect()),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an object, removes the first entry with `key` and returns its value.
+    ///
+    /// Returns `None` if the key is not present or the Value is not an object. The order of the
+    /// remaining entries is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"a": 1, "b": 2}"#).unwrap();
+    ///
+    /// assert_eq!(data.remove("a"), Some(Value::Number(1u64.into())));
+    /// assert_eq!(data.remove("a"), None);
+    /// assert_eq!(data.to_string(), r#"{"b":2}"#);
+    /// ```
+    pub fn remove(&mut self, key: &str) -> Option<Value<'ctx>> {
+        match self {
+            Value::Object(obj) => {
+                let pos = obj.iter().position(|(k, _)| k == key)?;
+                Some(obj.remove(pos).1)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the value of `key`, inserting the result of `f` if the key is
+    /// not present.
+    ///
+    /// A `Value::Null` is replaced by an empty object first, which makes it easy to build nested
+    /// objects. If the object contains `key` multiple times, the first entry is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Value is neither an object nor null.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut index = Value::Null;
+    /// for (word, line) in [("a", 1u64), ("b", 2), ("a", 3)] {
+    ///     index
+    ///         .get_or_insert_with(word, || Value::Array(Vec::new()))
+    ///         .push(Value::Number(line.into()))
+    ///         .unwrap();
+    /// }
+    /// assert_eq!(index.to_string(), r#"{"a":[1,3],"b":[2]}"#);
+    /// ```
+    pub fn get_or_insert_with<F>(
+        &mut self,
+        key: impl Into<Cow<'ctx, str>>,
+        f: F,
+    ) -> &mut Value<'ctx>
+    where
+        F: FnOnce() -> Value<'ctx>,
+    {
+        if self.is_null() {
+            *self = Value::Object(Vec::new());
+        }
+        match self.entry(key) {
+            Some(entry) => entry.or_insert_with(f),
+            None => {
+                panic!("get_or_insert_with called on a Value which is neither an object nor null")
+            }
+        }
+    }
+
+    /// If the Value is an object, retains only the entries for which `f` returns true.
+    ///
+    /// The order of the retained entries is preserved. Does nothing if the Value is not an object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"a": 1, "b": null, "_internal": 2}"#).unwrap();
+    ///
+    /// data.retain_object(|key, value| !value.is_null() && !key.starts_with('_'));
+    /// assert_eq!(data.to_string(), r#"{"a":1}"#);
+    /// ```
+    pub fn retain_object<F>(&mut self, mut f: F)
+    where F: FnMut(&str, &Value<'ctx>) -> bool {
+        if let Value::Object(obj) = self {
+            obj.retain(|(key, value)| f(key, value));
+        }
+    }
+
+    /// Collapses duplicate object keys recursively, keeping the value of the last occurrence.
+    ///
+    /// This matches how `serde_json::Value` treats duplicate keys: the entry stays at the position
+    /// of the first occurrence of the key, but gets the value of the last one. Objects nested in
+    /// arrays and in other objects are deduplicated as well.
+    ///
+    /// Parsing keeps all duplicates, and [`get`](Value::get) returns the first one, so call this
+    /// explicitly when last-wins semantics are needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value =
+    ///     serde_json::from_str(r#"{"a": 1, "b": [{"c": 1, "c": 2}], "a": 3}"#).unwrap();
+    /// assert_eq!(data.get("a"), 1);
+    ///
+    /// data.dedup_keys_last_wins();
+    /// assert_eq!(data.to_string(), r#"{"a":3,"b":[{"c":2}]}"#);
+    /// ```
+    pub fn dedup_keys_last_wins(&mut self) {
+        match self {
+            Value::Object(obj) => {
+                dedup_entries(obj);
+                for (_, value) in obj {
+                    value.dedup_keys_last_wins();
+                }
+            }
+            Value::Array(arr) => {
+                for value in arr {
+                    value.dedup_keys_last_wins();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Recursively removes all object entries whose value is `Value::Null`.
+    ///
+    /// Arrays are kept intact: `null` elements stay in place, so the indices of the other elements
+    /// don't change, but objects nested in arrays are pruned as well. Use
+    /// [`prune_all_nulls`](Value::prune_all_nulls) to remove `null` elements from arrays too.
+    /// Objects which become empty are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value =
+    ///     serde_json::from_str(r#"{"a": null, "b": [null, {"c": null, "d": 1}], "e": {"f": null}}"#)
+    ///         .unwrap();
+    ///
+    /// data.prune_nulls();
+    /// assert_eq!(data.to_string(), r#"{"b":[null,{"d":1}],"e":{}}"#);
+    /// ```
+    pub fn prune_nulls(&mut self) {
+        prune_nulls(self, false);
+    }
+
+    /// Recursively removes all object entries whose value is `Value::Null`, and all `null`
+    /// elements of arrays.
+    ///
+    /// Like [`prune_nulls`](Value::prune_nulls), but also removes `null` from arrays, which shifts
+    /// the indices of the following elements. Empty objects and arrays are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"a": null, "b": [null, 1, [null]]}"#).unwrap();
+    ///
+    /// data.prune_all_nulls();
+    /// assert_eq!(data.to_string(), r#"{"b":[1,[]]}"#);
+    /// ```
+    pub fn prune_all_nulls(&mut self) {
+        prune_nulls(self, true);
+    }
+
+    /// If the Value is an object, returns the entry for `key` for in-place manipulation.
+    ///
+    /// If the object contains `key` multiple times, the entry refers to the first one. Returns
+    /// `None` if the Value is not an object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"count": 1}"#).unwrap();
+    ///
+    /// data.entry("list").unwrap().or_insert(Value::Array(Vec::new()));
+    /// let count = data.entry("count").unwrap().or_insert(Value::Null);
+    /// *count = Value::Number(2u64.into());
+    /// assert_eq!(data.to_string(), r#"{"count":2,"list":[]}"#);
+    /// ```
+    pub fn entry(&mut self, key: impl Into<Cow<'ctx, str>>) -> Option<Entry<'_, 'ctx>> {
+        let Value::Object(obj) = self else {
+            return None;
+        };
+        let key = key.into();
+        Some(match obj.iter().position(|(k, _)| *k == key) {
+            Some(index) => Entry::Occupied(OccupiedEntry { obj, index }),
+            None => Entry::Vacant(VacantEntry { obj, key }),
+        })
+    }
+}
+
+/// A view into a single entry of an object, which may either be vacant or occupied.
+///
+/// Returned by [`Value::entry`].
+pub enum Entry<'a, 'ctx> {
+    /// The object doesn't contain the key.
+    Vacant(VacantEntry<'a, 'ctx>),
+    /// The object contains the key.
+    Occupied(OccupiedEntry<'a, 'ctx>),
+}
+
+impl<'a, 'ctx> Entry<'a, 'ctx> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a mutable reference to the value.
+    pub fn or_insert(self, default: Value<'ctx>) -> &'a mut Value<'ctx> {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a mutable reference to
+    /// the value.
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut Value<'ctx>
+    where F: FnOnce() -> Value<'ctx> {
+        match self {
+            Entry::Vacant(entry) => entry.insert(default()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where F: FnOnce(&mut Value<'ctx>) {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+/// A vacant entry of an object. Part of [`Entry`].
+pub struct VacantEntry<'a, 'ctx> {
+    obj: &'a mut ObjectEntries<'ctx>,
+    key: Cow<'ctx, str>,
+}
+
+impl<'a, 'ctx> VacantEntry<'a, 'ctx> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Appends a new entry with `value` to the object and returns a mutable reference to it.
+    pub fn insert(self, value: Value<'ctx>) -> &'a mut Value<'ctx> {
+        self.obj.push((self.key, value));
+        let (_, value) = self.obj.last_mut().expect("entry was just pushed");
+        value
+    }
+}
+
+/// An occupied entry of an object. Part of [`Entry`].
+pub struct OccupiedEntry<'a, 'ctx> {
+    obj: &'a mut ObjectEntries<'ctx>,
+    index: usize,
+}
+
+impl<'a, 'ctx> OccupiedEntry<'a, 'ctx> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &str {
+        &self.obj[self.index].0
+    }
+
+    /// Returns a reference to the value of the entry.
+    pub fn get(&self) -> &Value<'ctx> {
+        &self.obj[self.index].1
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut Value<'ctx> {
+        &mut self.obj[self.index].1
+    }
+
+    /// Converts the entry into a mutable reference to its value, bound to the object's lifetime.
+    pub fn into_mut(self) -> &'a mut Value<'ctx> {
+        &mut self.obj[self.index].1
+    }
+
+    /// Replaces the value of the entry and returns the old value.
+    pub fn insert(&mut self, value: Value<'ctx>) -> Value<'ctx> {
+        core::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the object and returns its value.
+    pub fn remove(self) -> Value<'ctx> {
+        self.obj.remove(self.index).1
+    }
+}
+
+/// Keeps the first entry of every key, with the value of its last entry.
+fn dedup_entries(obj: &mut ObjectEntries<'_>) {
+    // For every entry, the index of the entry holding its value, or `None` if it's a duplicate.
+    let sources: Vec<Option<usize>> = {
+        let mut last = BTreeMap::new();
+        for (i, (key, _)) in obj.iter().enumerate() {
+            last.insert(key.as_ref(), i);
+        }
+        if last.len() == obj.len() {
+            return;
+        }
+        let mut seen = BTreeSet::new();
+        obj.iter()
+            .map(|(key, _)| seen.insert(key.as_ref()).then(|| last[key.as_ref()]))
+            .collect()
+    };
+    let mut entries: Vec<_> = core::mem::take(obj).into_iter().map(Some).collect();
+    for (i, source) in sources.into_iter().enumerate() {
+        let Some(source) = source else { continue };
+        let (key, mut value) = entries[i].take().expect("entry is taken once");
+        if source != i {
+            value = entries[source].take().expect("entry is taken once").1;
+        }
+        obj.push((key, value));
+    }
+}
+
+fn prune_nulls(value: &mut Value<'_>, in_arrays: bool) {
+    match value {
+        Value::Object(obj) => {
+            obj.retain(|(_, val)| !val.is_null());
+            for (_, val) in obj {
+                prune_nulls(val, in_arrays);
+            }
+        }
+        Value::Array(arr) => {
+            if in_arrays {
+                arr.retain(|val| !val.is_null());
+            }
+            for val in arr {
+                prune_nulls(val, in_arrays);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn insert_remove_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"a": 1, "b": 2, "a": 3}"#)?;
+
+        // Duplicate keys: the first match is replaced or removed.
+        assert_eq!(
+            data.insert("a", Value::Bool(true)),
+            Some(Value::Number(1u64.into()))
+        );
+        assert_eq!(data.to_string(), r#"{"a":true,"b":2,"a":3}"#);
+        assert_eq!(data.remove("a"), Some(Value::Bool(true)));
+        assert_eq!(data.to_string(), r#"{"b":2,"a":3}"#);
+
+        assert_eq!(data.insert(String::from("c"), Value::Null), None);
+        assert_eq!(data.to_string(), r#"{"b":2,"a":3,"c":null}"#);
+        assert_eq!(data.remove("missing"), None);
+
+        let mut arr: Value = serde_json::from_str("[1]")?;
+        assert_eq!(arr.insert("a", Value::Null), None);
+        assert_eq!(arr.remove("a"), None);
+        assert_eq!(arr.to_string(), "[1]");
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_ci_test() -> io::Result<()> {
+        let mut data: Value =
+            serde_json::from_str(r#"{"X-Id": 1, "x-id": 2, "ÄB": 3, "list": [{"Key": true}]}"#)?;
+        assert_eq!(data.get_ci("x-ID"), 1);
+        assert_eq!(data.get_ci("äb"), &Value::Null);
+        assert_eq!(data.get_ci("ÄB"), 3);
+        assert_eq!(data.get_ci("LIST").get(0).get_ci("key"), &Value::Bool(true));
+        assert_eq!(data.get_ci("missing"), &Value::Null);
+        assert_eq!(data.get_ci("LIST").get_ci("key"), &Value::Null);
+
+        *data.get_ci_mut("X-ID").unwrap() = Value::Null;
+        assert_eq!(
+            data.to_string(),
+            r#"{"X-Id":null,"x-id":2,"ÄB":3,"list":[{"Key":true}]}"#
+        );
+        assert!(data.get_ci_mut("nope").is_none());
+        assert!(Value::Bool(true).get_ci_mut("a").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn get_many_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": 1, "b": [true], "a": 2, "c": null}"#)?;
+        let [b, a, x, a2] = data.get_many(["b", "a", "x", "a"]);
+        assert_eq!(b, data.get("b"));
+        assert_eq!(a, 1);
+        assert_eq!(x, &Value::Null);
+        assert_eq!(a2, 1);
+
+        let [] = data.get_many([]);
+        assert_eq!(Value::Bool(true).get_many(["a"]), [&Value::Null]);
+        Ok(())
+    }
+
+    #[test]
+    fn get_all_test() -> io::Result<()> {
+        let data: Value =
+            serde_json::from_str(r#"{"a": 1, "b": {"a": 9}, "a": [2], "A": 3, "a": null}"#)?;
+        let all: Vec<&Value> = data.get_all("a").collect();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0], 1);
+        assert_eq!(all[1].to_string(), "[2]");
+        assert_eq!(all[2], &Value::Null);
+        assert_eq!(data.get_all("b").next(), Some(data.get("b")));
+
+        let key = String::from("x");
+        assert_eq!(data.get_all(&key).count(), 0);
+        assert_eq!(data.get("a").get_all("a").count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn typed_getters_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(
+            r#"{"s": "x", "i": -1, "u": 2, "f": 1.5, "b": true, "a": [1], "o": {"k": null}, "s": 1}"#,
+        )?;
+        assert_eq!(data.get_str("s"), Some("x"));
+        assert_eq!(data.get_i64("i"), Some(-1));
+        assert_eq!(data.get_u64("i"), None);
+        assert_eq!(data.get_u64("u"), Some(2));
+        assert_eq!(data.get_f64("f"), Some(1.5));
+        assert_eq!(data.get_f64("u"), Some(2.0));
+        assert_eq!(data.get_bool("b"), Some(true));
+        assert_eq!(data.get_array("a").map(Vec::len), Some(1));
+        assert_eq!(data.get_object("o").unwrap()[0].0, "k");
+
+        assert_eq!(data.get_str("missing"), None);
+        assert_eq!(data.get_bool("s"), None);
+        assert_eq!(data.get_array("o"), None);
+        assert_eq!(data.get_object("a"), None);
+        assert_eq!(Value::Str("s".into()).get_str("s"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn as_object_map_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [1], "b": null, "a": "x", "c": {}}"#)?;
+        let map = data.as_object_map().unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map["a"], "x");
+        assert_eq!(map["b"], &Value::Null);
+        assert_eq!(map.get("c").map(|v| v.is_object()), Some(true));
+        assert!(!map.contains_key("d"));
+
+        assert!(data.get("a").as_object_map().is_none());
+        assert!(Value::Object(Vec::new())
+            .as_object_map()
+            .unwrap()
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn get_or_insert_with_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"a": {"x": 1}, "a": 2}"#)?;
+
+        let a = data.get_or_insert_with("a", || unreachable!());
+        a.get_or_insert_with("y", || Value::Bool(true));
+        let b = data.get_or_insert_with("b", || Value::Null);
+        b.get_or_insert_with("c", || Value::Str("d".into()));
+        assert_eq!(
+            data.to_string(),
+            r#"{"a":{"x":1,"y":true},"a":2,"b":{"c":"d"}}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "neither an object nor null")]
+    fn get_or_insert_with_panics_test() {
+        Value::Array(Vec::new()).get_or_insert_with("a", || Value::Null);
+    }
+
+    #[test]
+    fn retain_object_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"a": 1, "b": null, "a": 2, "c": [null]}"#)?;
+        let mut seen = Vec::new();
+        data.retain_object(|key, value| {
+            seen.push(key.to_string());
+            !value.is_null()
+        });
+        assert_eq!(seen, ["a", "b", "a", "c"]);
+        assert_eq!(data.to_string(), r#"{"a":1,"a":2,"c":[null]}"#);
+
+        let mut arr: Value = serde_json::from_str("[null]")?;
+        arr.retain_object(|_, _| false);
+        assert_eq!(arr.to_string(), "[null]");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dedup_keys_last_wins_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(
+            r#"{"a": 1, "b": 2, "a": 3, "c": {"x": 1, "y": 2, "x": [{"z": 1, "z": 2}]}, "b": 4, "a": 5}"#,
+        )?;
+        data.dedup_keys_last_wins();
+        assert_eq!(
+            data.to_string(),
+            r#"{"a":5,"b":4,"c":{"x":[{"z":2}],"y":2}}"#
+        );
+
+        let expected: serde_json::Value = serde_json::from_str(r#"{"k": 1, "k": null, "j": 0}"#)?;
+        let mut data: Value = serde_json::from_str(r#"{"k": 1, "k": null, "j": 0}"#)?;
+        data.dedup_keys_last_wins();
+        assert_eq!(data, expected);
+
+        let mut data: Value = serde_json::from_str(r#"[{"a": 1}, "s", {}]"#)?;
+        data.dedup_keys_last_wins();
+        assert_eq!(data.to_string(), r#"[{"a":1},"s",{}]"#);
+        Ok(())
+    }
+
+    #[test]
+    fn prune_nulls_test() -> io::Result<()> {
+        let input = r#"{"a": null, "b": [null, {"c": null, "c": 1}, [null, {"d": null}]], "e": {"f": {"g": null}}, "a": 0}"#;
+        let mut data: Value = serde_json::from_str(input)?;
+        data.prune_nulls();
+        assert_eq!(
+            data.to_string(),
+            r#"{"b":[null,{"c":1},[null,{}]],"e":{"f":{}},"a":0}"#
+        );
+
+        let mut data: Value = serde_json::from_str(input)?;
+        data.prune_all_nulls();
+        assert_eq!(
+            data.to_string(),
+            r#"{"b":[{"c":1},[{}]],"e":{"f":{}},"a":0}"#
+        );
+
+        let mut data = Value::Null;
+        data.prune_all_nulls();
+        assert_eq!(data, Value::Null);
+        Ok(())
+    }
+
+    #[test]
+    fn entry_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"a": 1, "a": 2}"#)?;
+
+        let entry = data.entry("a").unwrap();
+        assert_eq!(entry.key(), "a");
+        let Entry::Occupied(mut entry) = entry else {
+            panic!("expected occupied entry");
+        };
+        assert_eq!(entry.get(), &Value::Number(1u64.into()));
+        assert_eq!(entry.insert(Value::Null), Value::Number(1u64.into()));
+        assert_eq!(entry.remove(), Value::Null);
+        assert_eq!(data.to_string(), r#"{"a":2}"#);
+
+        data.entry("a")
+            .unwrap()
+            .and_modify(|v| *v = Value::Bool(false))
+            .or_insert(Value::Null);
+        data.entry("b")
+            .unwrap()
+            .and_modify(|v| *v = Value::Bool(false))
+            .or_insert_with(|| Value::Str("new".into()));
+        assert_eq!(data.to_string(), r#"{"a":false,"b":"new"}"#);
+
+        assert!(matches!(data.entry("c"), Some(Entry::Vacant(e)) if e.key() == "c"));
+        assert!(Value::Null.entry("a").is_none());
+
+        Ok(())
+    }
+}
@@ -20,6 +20,37 @@ impl OwnedValue {
         Ok(Self { _data: data, value })
     }
 
+    /// Reads all of `reader` into an owned buffer and parses it into a DOM borrowing from that
+    /// buffer.
+    ///
+    /// This allows to keep a parsed `Value` around, e.g. in a struct, when the input comes from a
+    /// file or socket instead of a `&str` that outlives the `Value`. Fails if the input is not
+    /// valid UTF-8 or not valid JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io;
+    ///
+    /// use serde_json_borrow::OwnedValue;
+    ///
+    /// struct Config {
+    ///     json: OwnedValue,
+    /// }
+    ///
+    /// fn load(reader: impl io::Read) -> io::Result<Config> {
+    ///     Ok(Config { json: OwnedValue::from_reader(reader)? })
+    /// }
+    ///
+    /// let config = load(r#"{"name": "app"}"#.as_bytes()).unwrap();
+    /// assert_eq!(config.json.get_value().get("name"), "app");
+    /// ```
+    pub fn from_reader<R: io::Read>(mut reader: R) -> io::Result<Self> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        Self::parse_from(data)
+    }
+
     /// Returns the `Value` reference.
     pub fn get_value(&self) -> &Value<'_> {
         &self.value
@@ -29,3 +60,20 @@ impl OwnedValue {
 unsafe fn extend_lifetime<'b>(r: Value<'b>) -> Value<'static> {
     std::mem::transmute::<Value<'b>, Value<'static>>(r)
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_reader_test() -> io::Result<()> {
+        let owned = OwnedValue::from_reader(&b"{\"a\": [\"x\", 1]}"[..])?;
+        assert_eq!(owned.get_value().get("a").get(0), "x");
+        assert!(owned.get_value().is_borrowed());
+
+        let err = OwnedValue::from_reader(&b"\"\xff\""[..]).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(OwnedValue::from_reader(&b"{"[..]).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,549 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::de::{DeserializeSeed, IgnoredAny};
+
+use crate::de::ValueSeed;
+use crate::{ParseError, Value};
+
+/// Parses a `Value` from a `&str`, borrowing strings and object keys from it.
+///
+/// This is equivalent to `serde_json::from_str`, but errors are wrapped in a [`ParseError`],
+/// which resolves the byte offset of the error and keeps a snippet of the input around it.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str;
+///
+/// let value = from_str(r#"{"a": [1, 2]}"#).unwrap();
+/// assert_eq!(value.get("a").get(1), 2);
+///
+/// let err = from_str(r#"{"a": [1, 2}"#).unwrap_err();
+/// assert_eq!(err.offset(), Some(11));
+/// ```
+pub fn from_str(input: &str) -> Result<Value<'_>, ParseError> {
+    serde_json::from_str(input).map_err(|err| ParseError::new(err, input))
+}
+
+/// Parses a `Value` from a byte slice, borrowing strings and object keys from it.
+///
+/// Strings and keys without escape sequences are borrowed directly from `input` as
+/// `Cow::Borrowed`; only those containing escape sequences are unescaped into a `Cow::Owned`. The
+/// input is not validated as UTF-8 up front: each string is validated where it is borrowed, so no
+/// intermediate `&str` or copy of the buffer is needed.
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use serde_json_borrow::{from_slice, Value};
+///
+/// let bytes: &[u8] = br#"{"plain": "text", "escaped": "a\nb"}"#;
+/// let value = from_slice(bytes).unwrap();
+/// assert!(matches!(value.get("plain"), Value::Str(Cow::Borrowed("text"))));
+/// assert!(matches!(value.get("escaped"), Value::Str(Cow::Owned(_))));
+/// ```
+pub fn from_slice(input: &[u8]) -> Result<Value<'_>, ParseError> {
+    serde_json::from_slice(input).map_err(|err| ParseError::within(err, input, 0, 0))
+}
+
+/// Parses a `Value` from bytes which may not be valid UTF-8, replacing invalid UTF-8 sequences with
+/// the replacement character `U+FFFD`.
+///
+/// Unlike [`from_slice`], a single invalid byte doesn't fail parsing. Since the replacement
+/// requires a new buffer, all strings and keys are owned and the result is a `Value<'static>`.
+/// Malformed JSON is still an error, whose position refers to the input after the replacement.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_slice_lossy;
+///
+/// let bytes: &[u8] = b"{\"name\": \"caf\xe9\"}";
+/// let value = from_slice_lossy(bytes).unwrap();
+/// assert_eq!(value.get("name"), "caf\u{fffd}");
+/// ```
+pub fn from_slice_lossy(input: &[u8]) -> Result<Value<'static>, ParseError> {
+    let text = String::from_utf8_lossy(input);
+    Ok(from_str(&text)?.into_owned())
+}
+
+/// Parses a single `Value` from the start of `input` and returns it together with the remaining
+/// unparsed input.
+///
+/// Leading whitespace is skipped, and the returned tail starts right after the parsed value. This
+/// allows to parse concatenated or whitespace-separated values one at a time. Numbers, `true`,
+/// `false` and `null` must be followed by whitespace, a delimiter like `[` or `,`, or the end of
+/// the input, otherwise an error is returned. Empty or whitespace-only input is an error as well.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str_partial;
+///
+/// let input = r#" {"id": 1} [2]"x" 3"#;
+/// let (first, rest) = from_str_partial(input).unwrap();
+/// assert_eq!(first.get("id"), 1);
+/// assert_eq!(rest, r#" [2]"x" 3"#);
+///
+/// let (second, rest) = from_str_partial(rest).unwrap();
+/// assert_eq!(second.to_string(), "[2]");
+/// assert_eq!(rest, r#""x" 3"#);
+/// ```
+pub fn from_str_partial(input: &str) -> Result<(Value<'_>, &str), ParseError> {
+    let mut values = serde_json::Deserializer::from_str(input).into_iter::<Value>();
+    match values.next() {
+        Some(Ok(value)) => Ok((value, &input[values.byte_offset()..])),
+        Some(Err(err)) => Err(ParseError::new(err, input)),
+        // Only whitespace is left, which fails with an EOF error.
+        None => Err(ParseError::new(
+            serde_json::from_str::<IgnoredAny>(input)
+                .expect_err("input without a value is invalid"),
+            input,
+        )),
+    }
+}
+
+/// Parses all concatenated or whitespace-separated values in `input`, borrowing from it.
+///
+/// This is the eager counterpart of calling [`from_str_partial`] until the input is exhausted.
+/// Numbers, `true`, `false` and `null` must be separated from the next value by whitespace or a
+/// delimiter, e.g. `1 2` or `1[2]`, but not `12`. Empty or whitespace-only input yields an empty
+/// `Vec`.
+///
+/// If any value is malformed, the error reports its line and column within the whole input.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::parse_all;
+///
+/// let values = parse_all("{\"id\": 1}\n{\"id\": 2}\n[3]").unwrap();
+/// assert_eq!(values.len(), 3);
+/// assert_eq!(values[1].get("id"), 2);
+///
+/// let err = parse_all("{\"id\": 1}\n{\"id\": }").unwrap_err();
+/// assert_eq!((err.line(), err.column()), (2, 8));
+/// ```
+pub fn parse_all(input: &str) -> Result<Vec<Value<'_>>, ParseError> {
+    serde_json::Deserializer::from_str(input)
+        .into_iter::<Value>()
+        .collect::<Result<_, _>>()
+        .map_err(|err| ParseError::new(err, input))
+}
+
+/// Parses a `Value` from human-authored JSON, which may contain comments and trailing commas.
+///
+/// On top of strict JSON, exactly these JSON5 features are supported:
+/// - Line comments, from `//` to the end of the line.
+/// - Block comments, from `/*` to the next `*/`. Block comments don't nest.
+/// - A single trailing comma after the last element of an array or the last entry of an object,
+///   e.g. `[1, 2,]` or `{"a": 1,}`.
+///
+/// Other JSON5 extensions, like unquoted keys, single-quoted strings, hexadecimal numbers,
+/// `Infinity` and `NaN` or leading `+` signs, are rejected just like in strict JSON.
+///
+/// Comments and trailing commas are blanked out with whitespace in a copy of the input, so line
+/// and column numbers and offsets in errors refer to the original input. Strings and keys are still
+/// borrowed from `input`. Input without comments and trailing commas is parsed directly, without a
+/// copy.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str_lenient;
+///
+/// let config = r#"{
+///     // The port to listen on.
+///     "port": 8080,
+///     /* Multiple
+///        hosts */
+///     "hosts": ["a", "b",],
+/// }"#;
+/// let value = from_str_lenient(config).unwrap();
+/// assert_eq!(value.to_string(), r#"{"port":8080,"hosts":["a","b"]}"#);
+/// assert!(value.is_borrowed());
+/// ```
+pub fn from_str_lenient(input: &str) -> Result<Value<'_>, ParseError> {
+    let Some(stripped) = strip_lenient_syntax(input) else {
+        return from_str(input);
+    };
+    let value: Value =
+        serde_json::from_str(&stripped).map_err(|err| ParseError::new(err, input))?;
+    Ok(rebase(value, &stripped, input))
+}
+
+/// Replaces comments and trailing commas outside of strings with spaces, keeping newlines and the
+/// byte offsets of everything else. Returns `None` if there is nothing to replace.
+///
+/// Unterminated block comments are kept, so parsing reports an error at their start.
+fn strip_lenient_syntax(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut blanked: Option<Vec<u8>> = None;
+    let mut blank = |range: core::ops::Range<usize>| {
+        let out = blanked.get_or_insert_with(|| bytes.to_vec());
+        for b in &mut out[range] {
+            if *b != b'\n' {
+                *b = b' ';
+            }
+        }
+    };
+    // The last significant byte, and the position of a comma which may be trailing.
+    let mut last = 0u8;
+    let mut comma = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                last = b'"';
+                comma = None;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map_or(bytes.len(), |len| i + len);
+                blank(i..end);
+                i = end;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                if let Some(len) = bytes[i + 2..].windows(2).position(|w| w == b"*/") {
+                    let end = i + 2 + len + 2;
+                    blank(i..end);
+                    i = end;
+                    continue;
+                }
+                // Not a comment, parsing fails here.
+                last = b'/';
+                comma = None;
+            }
+            b' ' | b'\t' | b'\n' | b'\r' => {}
+            b',' => {
+                comma = (!matches!(last, b'[' | b'{' | b',' | b':')).then_some(i);
+                last = b',';
+            }
+            b @ (b']' | b'}') => {
+                if let Some(pos) = comma.take() {
+                    blank(pos..pos + 1);
+                }
+                last = b;
+            }
+            b => {
+                last = b;
+                comma = None;
+            }
+        }
+        i += 1;
+    }
+    // Only ASCII bytes of the valid UTF-8 input were replaced with ASCII spaces.
+    blanked.map(|out| String::from_utf8(out).expect("blanking keeps the input valid UTF-8"))
+}
+
+/// Moves strings borrowed from `buffer` to the same byte range of `input`, which has the same
+/// content there.
+fn rebase<'ctx>(value: Value<'_>, buffer: &str, input: &'ctx str) -> Value<'ctx> {
+    let rebase_slice = |s: &str| -> &'ctx str {
+        let start = s.as_ptr() as usize - buffer.as_ptr() as usize;
+        &input[start..start + s.len()]
+    };
+    let rebase_str = |text: Cow<'_, str>| -> Cow<'ctx, str> {
+        match text {
+            Cow::Borrowed(s) => Cow::Borrowed(rebase_slice(s)),
+            Cow::Owned(s) => Cow::Owned(s),
+        }
+    };
+    match value {
+        Value::Null => Value::Null,
+        Value::Bool(b) => Value::Bool(b),
+        Value::Number(n) => Value::Number(n),
+        Value::Str(s) => Value::Str(rebase_str(s)),
+        #[cfg(feature = "raw_value")]
+        Value::Raw(text) => Value::Raw(rebase_slice(text)),
+        Value::Array(arr) => Value::Array(
+            arr.into_iter()
+                .map(|val| rebase(val, buffer, input))
+                .collect(),
+        ),
+        Value::Object(obj) => Value::Object(
+            obj.into_iter()
+                .map(|(key, val)| (rebase_str(key), rebase(val, buffer, input)))
+                .collect(),
+        ),
+    }
+}
+
+/// Parses a `Value` from a `&str`, but keeps arrays and objects nested deeper than `depth` unparsed
+/// as [`Value::Raw`]. Requires the `raw_value` feature.
+///
+/// The depth is counted like for [`from_str_with_limit`]: with a depth of 1, the entries of the
+/// top-level object or array are kept raw, with a depth of 0 the whole document is. Scalars are
+/// always parsed. This avoids building the DOM of large subtrees which are only passed through or
+/// needed occasionally; they can be parsed on demand with [`Value::parse`]. The input is still
+/// fully validated.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::{from_str_lazy, Value};
+///
+/// let input = r#"{"id": 7, "payload": {"items": [1, 2, 3]}}"#;
+/// let value = from_str_lazy(input, 1).unwrap();
+///
+/// assert_eq!(value.get("id"), 7);
+/// assert_eq!(value.get("payload"), &Value::Raw(r#"{"items": [1, 2, 3]}"#));
+/// assert_eq!(value.to_string(), r#"{"id":7,"payload":{"items": [1, 2, 3]}}"#);
+/// assert_eq!(value.get("payload").parse().unwrap().get("items").get(2), 3);
+/// ```
+#[cfg(feature = "raw_value")]
+pub fn from_str_lazy(input: &str, depth: usize) -> Result<Value<'_>, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    deserialize_seed(ValueSeed::lazy(depth), &mut deserializer)
+        .map_err(|err| ParseError::new(err, input))
+}
+
+/// Parses a `Value` from a `&str`, failing if arrays and objects are nested deeper than
+/// `max_depth`.
+///
+/// This protects against stack overflows and excessive resource usage when parsing untrusted
+/// input. A scalar has a depth of 0, `[]` and `{}` have a depth of 1, `[[]]` a depth of 2 and so
+/// on. Note that `serde_json` additionally limits the depth to 128.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str_with_limit;
+///
+/// assert!(from_str_with_limit(r#"{"a": [1]}"#, 2).is_ok());
+/// assert!(from_str_with_limit(r#"{"a": [[1]]}"#, 2).is_err());
+/// ```
+pub fn from_str_with_limit(input: &str, max_depth: usize) -> Result<Value<'_>, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    deserialize_seed(ValueSeed::with_max_depth(max_depth), &mut deserializer)
+        .map_err(|err| ParseError::new(err, input))
+}
+
+/// Parses a `Value` from a byte slice, failing if arrays and objects are nested deeper than
+/// `max_depth`.
+///
+/// See [`from_str_with_limit`] for details.
+pub fn from_slice_with_limit(input: &[u8], max_depth: usize) -> Result<Value<'_>, ParseError> {
+    let mut deserializer = serde_json::Deserializer::from_slice(input);
+    deserialize_seed(ValueSeed::with_max_depth(max_depth), &mut deserializer)
+        .map_err(|err| ParseError::within(err, input, 0, 0))
+}
+
+/// Deserializes a whole document with the seed, failing on trailing characters.
+fn deserialize_seed<'de, R: serde_json::de::Read<'de>>(
+    seed: ValueSeed,
+    deserializer: &mut serde_json::Deserializer<R>,
+) -> Result<Value<'de>, serde_json::Error> {
+    let value = seed.deserialize(&mut *deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::*;
+
+    #[test]
+    fn from_slice_test() {
+        let json = r#"{"key": "val", "esc\"aped": ["\u00e9", "é"]}"#;
+        let bytes = json.as_bytes();
+        let value = from_slice(bytes).unwrap();
+        assert!(matches!(value.get("key"), Value::Str(Cow::Borrowed("val"))));
+        assert!(
+            matches!(value.get("esc\"aped").get(0), Value::Str(Cow::Owned(s)) if s == "\u{e9}")
+        );
+        assert!(matches!(
+            value.get("esc\"aped").get(1),
+            Value::Str(Cow::Borrowed("\u{e9}"))
+        ));
+
+        assert!(from_slice(b"\"\xff\"").is_err());
+        assert!(from_slice(b"[1, 2").is_err());
+        let err = from_slice(b"[\"\xc3\xa9\",\n x]").unwrap_err();
+        assert_eq!((err.line(), err.column(), err.offset()), (2, 2, Some(8)));
+        assert_eq!(err.snippet(), " x]");
+    }
+
+    #[test]
+    fn from_slice_lossy_test() {
+        let value =
+            from_slice_lossy(b"{\"k\xff\": [\"a\xc3\", \"\xc3\xa9\", \"\\u00e9\"]}").unwrap();
+        assert_eq!(
+            value.to_string(),
+            "{\"k\u{fffd}\":[\"a\u{fffd}\",\"\u{e9}\",\"\u{e9}\"]}"
+        );
+        assert!(!value.get("k\u{fffd}").get(1).is_borrowed());
+
+        assert_eq!(from_slice_lossy(b"1").unwrap(), 1);
+        assert!(from_slice_lossy(b"[\"\xff\"").is_err());
+        assert!(from_slice_lossy(b"\xff").is_err());
+    }
+
+    #[test]
+    fn from_str_partial_test() {
+        let input = String::from("1 \"a\"\n{\"k\": [true]}null ");
+        let mut rest = input.as_str();
+        let mut values = Vec::new();
+        while !rest.trim().is_empty() {
+            let (value, tail) = from_str_partial(rest).unwrap();
+            values.push(value);
+            rest = tail;
+        }
+        assert_eq!(rest, " ");
+        assert_eq!(
+            Value::Array(values).to_string(),
+            r#"[1,"a",{"k":[true]},null]"#
+        );
+
+        let (value, rest) = from_str_partial("\"borrowed\"").unwrap();
+        assert!(value.is_borrowed());
+        assert_eq!(rest, "");
+
+        let err = from_str_partial("  ").unwrap_err();
+        assert!(err.inner().is_eof());
+        assert!(from_str_partial("12x").is_err());
+        assert!(from_str_partial("[1, ").unwrap_err().inner().is_eof());
+        let err = from_str_partial("\n{\"a\" 1}").unwrap_err();
+        assert_eq!((err.line(), err.column()), (2, 6));
+    }
+
+    #[test]
+    fn parse_all_test() {
+        let values = parse_all(" 1 \"a\"{\"k\": [true]}[]null\n").unwrap();
+        assert_eq!(
+            Value::Array(values).to_string(),
+            r#"[1,"a",{"k":[true]},[],null]"#
+        );
+        assert!(parse_all("").unwrap().is_empty());
+        assert!(parse_all(" \n\t").unwrap().is_empty());
+        assert!(parse_all("\"borrowed\" \"x\"")
+            .unwrap()
+            .iter()
+            .all(Value::is_borrowed));
+
+        assert!(parse_all("12x").is_err());
+        assert!(parse_all("[1] [2").unwrap_err().inner().is_eof());
+        let err = parse_all("1\n2\n  {\"a\" 1}").unwrap_err();
+        assert_eq!((err.line(), err.column()), (3, 8));
+    }
+
+    #[test]
+    fn from_str_lenient_test() {
+        let input = "// leading\n{\"a\": [1, 2, /* inline */ ],\n \"b\": \"// not a comment,\", \
+                     /**/\n \"c\": {\"d\": null,},}// end";
+        let value = from_str_lenient(input).unwrap();
+        assert_eq!(
+            value.to_string(),
+            r#"{"a":[1,2],"b":"// not a comment,","c":{"d":null}}"#
+        );
+        assert!(value.is_borrowed());
+        // Strings are borrowed from the input, not from the blanked copy.
+        assert!(matches!(value.get("b"), Value::Str(Cow::Borrowed(s))
+            if input.as_bytes().as_ptr_range().contains(&s.as_ptr())));
+
+        let value = from_str_lenient("[\"esc\\\"aped,]\", /* \u{e9} */ \"x\",]").unwrap();
+        assert_eq!(value.get(0), "esc\"aped,]");
+        assert_eq!(value.get(1), "x");
+
+        assert_eq!(from_str_lenient(" 1 ").unwrap(), 1);
+        assert_eq!(from_str_lenient("[]").unwrap().to_string(), "[]");
+        for invalid in [
+            "[,]",
+            "{,}",
+            "[1,,]",
+            "[1 /* open",
+            "{'a': 1}",
+            "{a: 1}",
+            "[1] ,",
+        ] {
+            assert!(from_str_lenient(invalid).is_err(), "{}", invalid);
+        }
+        let err = from_str_lenient("/* a\nb */\n[1,\n  x]").unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 3));
+        assert_eq!((err.offset(), err.snippet()), (Some(16), "  x]"));
+    }
+
+    #[cfg(feature = "raw_value")]
+    #[test]
+    fn from_str_lazy_test() {
+        let input = r#" {"a": [1, {"b": 2}], "c": "s", "d": {"e": [ ]}, "f": null} "#;
+        let value = from_str_lazy(input, 0).unwrap();
+        assert_eq!(value, Value::Raw(input.trim()));
+
+        let value = from_str_lazy(input, 1).unwrap();
+        assert_eq!(value.get("a"), &Value::Raw(r#"[1, {"b": 2}]"#));
+        assert_eq!(value.get("c"), "s");
+        assert!(value.get("f").is_null());
+        assert_eq!(
+            value.to_string(),
+            r#"{"a":[1, {"b": 2}],"c":"s","d":{"e": [ ]},"f":null}"#
+        );
+
+        let value = from_str_lazy(input, 2).unwrap();
+        assert_eq!(value.get("a").get(0), 1);
+        assert_eq!(value.get("a").get(1), &Value::Raw(r#"{"b": 2}"#));
+        assert_eq!(value.get("d").get("e"), &Value::Raw("[ ]"));
+
+        // Parsing on demand yields the same value as parsing eagerly.
+        let eager: Value = serde_json::from_str(input).unwrap();
+        let a = value.get("a").get(1).parse().unwrap();
+        assert_eq!(&a, eager.get("a").get(1));
+        assert!(a.is_borrowed());
+        assert_eq!(value.to_canonical_string(), eager.to_canonical_string());
+        assert_eq!(
+            serde_json::Value::from(value.clone()),
+            serde_json::Value::from(eager.clone())
+        );
+        assert_eq!(value.clone().into_owned().get("a"), eager.get("a"));
+
+        assert!(from_str_lazy(r#"{"a": [1,]}"#, 1).is_err());
+        assert!(from_str_lazy("[1] x", 1).is_err());
+    }
+
+    #[test]
+    fn depth_limit_test() {
+        assert!(from_str_with_limit("1", 0).is_ok());
+        assert!(from_str_with_limit("[]", 0).is_err());
+        assert!(from_str_with_limit("{}", 0).is_err());
+        assert!(from_str_with_limit("[]", 1).is_ok());
+        assert!(from_str_with_limit("{}", 1).is_ok());
+        assert!(from_str_with_limit(r#"{"a": 1, "b": [2]}"#, 1).is_err());
+        assert!(from_str_with_limit(r#"{"a": 1, "b": [2]}"#, 2).is_ok());
+        assert!(from_str_with_limit(r#"[{"a": [1, {"b": null}]}]"#, 3).is_err());
+        assert!(from_str_with_limit(r#"[{"a": [1, {"b": null}]}]"#, 4).is_ok());
+
+        let err = from_str_with_limit(r#"[[[["deep"]]]]"#, 3).unwrap_err();
+        assert!(
+            err.to_string().contains("maximum nesting depth exceeded"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn depth_limit_slice_test() {
+        let value = from_slice_with_limit(br#"{"a": ["b"]}"#, 2).unwrap();
+        assert_eq!(value.get("a").get(0), "b");
+        assert!(from_slice_with_limit(br#"{"a": ["b"]}"#, 1).is_err());
+        assert!(from_slice_with_limit(br#"{"a": ["b"]} x"#, 2).is_err());
+    }
+
+    #[test]
+    fn depth_limit_deep_input_test() {
+        let deep = "[".repeat(100_000) + &"]".repeat(100_000);
+        assert!(from_str_with_limit(&deep, 64).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use alloc::string::String;
+
+use crate::Value;
+
+impl<'ctx> PartialEq<str> for Value<'ctx> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl<'a, 'ctx> PartialEq<&'a str> for Value<'ctx> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+impl<'ctx> PartialEq<Value<'ctx>> for str {
+    fn eq(&self, other: &Value<'ctx>) -> bool {
+        other.as_str() == Some(self)
+    }
+}
+
+impl<'ctx> PartialEq<Value<'ctx>> for &str {
+    fn eq(&self, other: &Value<'ctx>) -> bool {
+        other.as_str() == Some(*self)
+    }
+}
+
+impl<'ctx> PartialEq<String> for Value<'ctx> {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == Some(other.as_str())
+    }
+}
+
+impl<'ctx> PartialEq<Value<'ctx>> for String {
+    fn eq(&self, other: &Value<'ctx>) -> bool {
+        other.as_str() == Some(self.as_str())
+    }
+}
+
+/// Compares structurally with a `serde_json::Value`.
+///
+/// Numbers are equal if they have the same representation, e.g. `1` is not equal to `1.0`. Objects
+/// are equal if they contain the same keys with equal values, regardless of their order. Since a
+/// `serde_json::Map` can't contain duplicate keys, an object with duplicate keys is never equal to
+/// one.
+impl<'ctx> PartialEq<serde_json::Value> for Value<'ctx> {
+    fn eq(&self, other: &serde_json::Value) -> bool {
+        match (self, other) {
+            (Value::Null, serde_json::Value::Null) => true,
+            (Value::Bool(a), serde_json::Value::Bool(b)) => a == b,
+            (Value::Number(a), serde_json::Value::Number(b)) => *a == crate::Number::from(b),
+            (Value::Str(a), serde_json::Value::String(b)) => a == b,
+            (Value::Array(a), serde_json::Value::Array(b)) => a == b,
+            (Value::Object(a), serde_json::Value::Object(b)) => {
+                // The keys of `b` are unique, so finding each of them in `a` of the same length
+                // means that `a` has no duplicate keys either.
+                a.len() == b.len()
+                    && b.iter().all(|(key, b_val)| {
+                        a.iter()
+                            .find(|(k, _)| k == key)
+                            .is_some_and(|(_, a_val)| a_val == b_val)
+                    })
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'ctx> PartialEq<Value<'ctx>> for serde_json::Value {
+    fn eq(&self, other: &Value<'ctx>) -> bool {
+        other == self
+    }
+}
+
+macro_rules! partialeq_numeric {
+    ($($eq:ident [$($ty:ty)*])*) => {
+        $($(
+            impl<'ctx> PartialEq<$ty> for Value<'ctx> {
+                fn eq(&self, other: &$ty) -> bool {
+                    $eq(self, *other as _)
+                }
+            }
+
+            impl<'ctx> PartialEq<Value<'ctx>> for $ty {
+                fn eq(&self, other: &Value<'ctx>) -> bool {
+                    $eq(other, *self as _)
+                }
+            }
+
+            impl<'a, 'ctx> PartialEq<$ty> for &'a Value<'ctx> {
+                fn eq(&self, other: &$ty) -> bool {
+                    $eq(*self, *other as _)
+                }
+            }
+
+            impl<'a, 'ctx> PartialEq<$ty> for &'a mut Value<'ctx> {
+                fn eq(&self, other: &$ty) -> bool {
+                    $eq(*self, *other as _)
+                }
+            }
+        )*)*
+    }
+}
+
+fn eq_i64(value: &Value, other: i64) -> bool {
+    matches!(value, Value::Number(n) if *n == other)
+}
+
+fn eq_u64(value: &Value, other: u64) -> bool {
+    matches!(value, Value::Number(n) if *n == other)
+}
+
+fn eq_f64(value: &Value, other: f64) -> bool {
+    matches!(value, Value::Number(n) if *n == other)
+}
+
+fn eq_bool(value: &Value, other: bool) -> bool {
+    value.as_bool() == Some(other)
+}
+
+partialeq_numeric! {
+    eq_i64[i8 i16 i32 i64 isize]
+    eq_u64[u8 u16 u32 u64 usize]
+    eq_f64[f32 f64]
+    eq_bool[bool]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn partial_eq_serde_json_value_test() {
+        let data = r#"{"a": [1, -2, 1.5, "s", null, true], "b": {"c": {}, "d": []}}"#;
+        let value: Value = serde_json::from_str(data).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(value, expected);
+        assert_eq!(expected, value);
+
+        // Key order doesn't matter.
+        let reordered: serde_json::Value = serde_json::from_str(
+            r#"{"b": {"d": [], "c": {}}, "a": [1, -2, 1.5, "s", null, true]}"#,
+        )
+        .unwrap();
+        assert_eq!(value, reordered);
+
+        let other: serde_json::Value = serde_json::from_str(r#"{"a": [1.0]}"#).unwrap();
+        assert_ne!(value, other);
+        assert_ne!(*value.get("a").get(0), other["a"][0]);
+        assert_ne!(value, serde_json::Value::Null);
+
+        // Duplicate keys are never equal to a map.
+        let dup: Value = serde_json::from_str(r#"{"a": 1, "a": 1}"#).unwrap();
+        let one: serde_json::Value = serde_json::from_str(r#"{"a": 1, "b": 1}"#).unwrap();
+        assert_ne!(dup, one);
+        let single: serde_json::Value = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+        assert_ne!(dup, single);
+    }
+
+    #[test]
+    fn partial_eq_primitives_test() {
+        let data = r#"{"key": "123", "n": 42, "neg": -1, "f": 1.5, "int_f": 2.0, "b": true}"#;
+        let value: Value = serde_json::from_str(data).unwrap();
+
+        assert_eq!(value.get("key"), "123");
+        assert_eq!(*value.get("key"), "123");
+        assert_eq!("123", *value.get("key"));
+        assert_eq!(value.get("key"), &String::from("123"));
+        assert_ne!(value.get("n"), "42");
+
+        assert_eq!(value.get("n"), 42i64);
+        assert_eq!(value.get("n"), 42u64);
+        assert_eq!(value.get("n"), 42);
+        assert_eq!(value.get("n"), 42.0);
+        assert_eq!(42u8, *value.get("n"));
+        assert_eq!(value.get("neg"), -1);
+        assert_ne!(value.get("neg"), u64::MAX);
+        assert_eq!(value.get("f"), 1.5);
+        assert_eq!(value.get("f"), 1.5f32);
+        assert_ne!(value.get("f"), 1);
+        assert_eq!(value.get("int_f"), 2);
+        assert_eq!(value.get("int_f"), 2u64);
+
+        assert_eq!(value.get("b"), true);
+        assert_ne!(value.get("b"), false);
+        assert_ne!(value.get("missing"), false);
+        assert_ne!(value.get("missing"), 0);
+        assert_ne!(value.get("missing"), "");
+    }
+}
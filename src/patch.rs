@@ -0,0 +1,947 @@
+use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::{fmt, mem};
+
+use crate::pointer::{parse_index, split_last};
+use crate::Value;
+
+/// A single operation of a JSON Patch, as defined in
+/// [RFC 6902](https://tools.ietf.org/html/rfc6902).
+///
+/// Paths are JSON Pointers, see [`Value::pointer`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatchOp<'ctx> {
+    /// Adds a value to an object or inserts it into an array. The path `-` appends to an array.
+    Add {
+        /// Location to add the value at.
+        path: Cow<'ctx, str>,
+        /// Value to add.
+        value: Value<'ctx>,
+    },
+    /// Removes the value at the path.
+    Remove {
+        /// Location of the value to remove.
+        path: Cow<'ctx, str>,
+    },
+    /// Replaces the value at the path, which must exist.
+    Replace {
+        /// Location of the value to replace.
+        path: Cow<'ctx, str>,
+        /// Value to replace with.
+        value: Value<'ctx>,
+    },
+    /// Removes the value at `from` and adds it at `path`.
+    Move {
+        /// Location of the value to move.
+        from: Cow<'ctx, str>,
+        /// Location to move the value to.
+        path: Cow<'ctx, str>,
+    },
+    /// Copies the value at `from` and adds it at `path`.
+    Copy {
+        /// Location of the value to copy.
+        from: Cow<'ctx, str>,
+        /// Location to copy the value to.
+        path: Cow<'ctx, str>,
+    },
+    /// Tests that the value at the path is equal to `value`, as compared by
+    /// [`semantic_eq`](Value::semantic_eq).
+    Test {
+        /// Location of the value to test.
+        path: Cow<'ctx, str>,
+        /// Expected value.
+        value: Value<'ctx>,
+    },
+}
+
+impl<'ctx> PatchOp<'ctx> {
+    /// Parses a single operation from its JSON form, e.g.
+    /// `{"op": "add", "path": "/a", "value": 1}`.
+    pub fn from_value(op: &Value<'ctx>) -> Result<Self, PatchError> {
+        let invalid = |msg: &str| PatchError::InvalidOperation(msg.to_string());
+        let field = |name: &str| -> Result<&Value<'ctx>, PatchError> {
+            op.iter_object()
+                .ok_or_else(|| invalid("operation is not an object"))?
+                .find(|(k, _)| k == name)
+                .map(|(_, v)| v)
+                .ok_or_else(|| PatchError::InvalidOperation(format!("missing field `{}`", name)))
+        };
+        let str_field = |name: &str| -> Result<Cow<'ctx, str>, PatchError> {
+            match field(name)? {
+                Value::Str(s) => Ok(s.clone()),
+                _ => Err(PatchError::InvalidOperation(format!(
+                    "field `{}` is not a string",
+                    name
+                ))),
+            }
+        };
+        let path = str_field("path")?;
+        let op = match str_field("op")?.as_ref() {
+            "add" => PatchOp::Add {
+                path,
+                value: field("value")?.clone(),
+            },
+            "remove" => PatchOp::Remove { path },
+            "replace" => PatchOp::Replace {
+                path,
+                value: field("value")?.clone(),
+            },
+            "move" => PatchOp::Move {
+                from: str_field("from")?,
+                path,
+            },
+            "copy" => PatchOp::Copy {
+                from: str_field("from")?,
+                path,
+            },
+            "test" => PatchOp::Test {
+                path,
+                value: field("value")?.clone(),
+            },
+            other => {
+                return Err(PatchError::InvalidOperation(format!(
+                    "unknown op `{}`",
+                    other
+                )))
+            }
+        };
+        Ok(op)
+    }
+
+    /// Parses a JSON Patch document, which is an array of operations.
+    pub fn from_patch(patch: &Value<'ctx>) -> Result<Vec<Self>, PatchError> {
+        patch
+            .iter_array()
+            .ok_or_else(|| PatchError::InvalidOperation("patch is not an array".to_string()))?
+            .map(PatchOp::from_value)
+            .collect()
+    }
+}
+
+/// Error returned when a JSON Patch can't be parsed or applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// The operation is not a valid JSON Patch operation.
+    InvalidOperation(String),
+    /// The path is not a valid JSON Pointer for the operation.
+    InvalidPath(String),
+    /// The path does not resolve to a location in the document.
+    PathNotFound(String),
+    /// The array index in the path is invalid or out of bounds.
+    InvalidIndex(String),
+    /// The value at the path of a `test` operation is not equal to the expected value.
+    TestFailed(String),
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::InvalidOperation(msg) => write!(f, "invalid patch operation: {}", msg),
+            PatchError::InvalidPath(path) => write!(f, "invalid path `{}`", path),
+            PatchError::PathNotFound(path) => write!(f, "path `{}` not found", path),
+            PatchError::InvalidIndex(path) => write!(f, "invalid array index in path `{}`", path),
+            PatchError::TestFailed(path) => write!(f, "test failed for path `{}`", path),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+impl<'ctx> Value<'ctx> {
+    /// Applies a JSON Merge Patch, as defined in [RFC 7386](https://tools.ietf.org/html/rfc7386).
+    ///
+    /// If the patch is an object, its entries are merged recursively into `self`: a `null` value
+    /// removes the key, any other value is merged into the existing value of the key or appended.
+    /// If `self` is not an object, it is replaced by an empty object first. Any other patch value
+    /// replaces `self` entirely.
+    ///
+    /// Since objects may contain duplicate keys, a `null` removes all entries of the key, while
+    /// other values are merged into the first entry of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut config: Value =
+    ///     serde_json::from_str(r#"{"name": "a", "opts": {"x": 1, "y": 2}}"#).unwrap();
+    /// let patch: Value = serde_json::from_str(r#"{"opts": {"x": null, "z": 3}}"#).unwrap();
+    ///
+    /// config.merge_patch(&patch);
+    /// assert_eq!(config.to_string(), r#"{"name":"a","opts":{"y":2,"z":3}}"#);
+    /// ```
+    pub fn merge_patch(&mut self, patch: &Value<'ctx>) {
+        let Value::Object(patch_entries) = patch else {
+            *self = patch.clone();
+            return;
+        };
+        if !self.is_object() {
+            *self = Value::Object(Vec::new());
+        }
+        let Value::Object(entries) = self else {
+            unreachable!("self was replaced by an object");
+        };
+        for (key, patch_value) in patch_entries {
+            if patch_value.is_null() {
+                entries.retain(|(k, _)| k != key);
+            } else if let Some((_, value)) = entries.iter_mut().find(|(k, _)| k == key) {
+                value.merge_patch(patch_value);
+            } else {
+                let mut value = Value::Null;
+                value.merge_patch(patch_value);
+                entries.push((key.clone(), value));
+            }
+        }
+    }
+
+    /// Deep-merges `other` into `self`, with `other` taking precedence.
+    ///
+    /// If both values are objects, the entries of `other` are merged recursively into the entries
+    /// of `self` with the same key, and entries with new keys are appended. In all other cases
+    /// `self` is replaced by `other`.
+    ///
+    /// Unlike [`merge_patch`](Value::merge_patch), `null` has no special meaning: it overrides the
+    /// existing value instead of removing the key. Since objects may contain duplicate keys, values
+    /// are merged into the first entry of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut config: Value =
+    ///     serde_json::from_str(r#"{"name": "a", "opts": {"x": 1, "y": 2}}"#).unwrap();
+    /// let other: Value = serde_json::from_str(r#"{"opts": {"x": null, "z": 3}}"#).unwrap();
+    ///
+    /// config.merge(other);
+    /// assert_eq!(config.to_string(), r#"{"name":"a","opts":{"x":null,"y":2,"z":3}}"#);
+    /// ```
+    pub fn merge(&mut self, other: Value<'ctx>) {
+        match (self, other) {
+            (Value::Object(entries), Value::Object(other_entries)) => {
+                for (key, other_value) in other_entries {
+                    if let Some((_, value)) = entries.iter_mut().find(|(k, _)| *k == key) {
+                        value.merge(other_value);
+                    } else {
+                        entries.push((key, other_value));
+                    }
+                }
+            }
+            (this, other) => *this = other,
+        }
+    }
+
+    /// Deep-merges `other` into `self` like [`merge`](Value::merge), but merges arrays of objects
+    /// by matching their elements on the field `key`, e.g. `"id"`.
+    ///
+    /// If both values are arrays, each element of `other` which is an object with the field `key`
+    /// is merged recursively into the first element of `self` which is an object with an equal
+    /// value of that field. All other elements of `other` are appended: elements without a match,
+    /// elements lacking the field `key`, and elements which are not objects. Elements of `self`
+    /// without a counterpart in `other` are kept unchanged, including those lacking the field.
+    /// Objects are merged like with `merge`, and nested arrays are merged by key as well. The
+    /// field values are compared with `==`, and for duplicate keys the first entry is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut config: Value = serde_json::from_str(
+    ///     r#"{"items": [{"id": 1, "on": true}, {"id": 2, "on": true}, {"name": "x"}]}"#,
+    /// )
+    /// .unwrap();
+    /// let other: Value =
+    ///     serde_json::from_str(r#"{"items": [{"id": 2, "on": false}, {"id": 3}, {"name": "y"}]}"#)
+    ///         .unwrap();
+    ///
+    /// config.merge_arrays_by_key(&other, "id");
+    /// assert_eq!(
+    ///     config.to_string(),
+    ///     r#"{"items":[{"id":1,"on":true},{"id":2,"on":false},{"name":"x"},{"id":3},{"name":"y"}]}"#
+    /// );
+    /// ```
+    pub fn merge_arrays_by_key(&mut self, other: &Value<'ctx>, key: &str) {
+        match (self, other) {
+            (Value::Object(entries), Value::Object(other_entries)) => {
+                for (entry_key, other_value) in other_entries {
+                    if let Some((_, value)) = entries.iter_mut().find(|(k, _)| k == entry_key) {
+                        value.merge_arrays_by_key(other_value, key);
+                    } else {
+                        entries.push((entry_key.clone(), other_value.clone()));
+                    }
+                }
+            }
+            (Value::Array(elements), Value::Array(other_elements)) => {
+                for other_element in other_elements {
+                    let matching = other_element.find_key(key).and_then(|id| {
+                        elements
+                            .iter_mut()
+                            .find(|element| element.find_key(key) == Some(id))
+                    });
+                    match matching {
+                        Some(element) => element.merge_arrays_by_key(other_element, key),
+                        None => elements.push(other_element.clone()),
+                    }
+                }
+            }
+            (this, other) => *this = other.clone(),
+        }
+    }
+
+    /// Applies a JSON Patch, as defined in [RFC 6902](https://tools.ietf.org/html/rfc6902).
+    ///
+    /// The operations are applied in order. If any operation fails, including a failing `test`
+    /// operation, an error is returned and `self` is left unchanged.
+    ///
+    /// Since objects may contain duplicate keys, operations on an object key apply to its first
+    /// entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::{PatchOp, Value};
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"a": [1, 2], "b": "x"}"#).unwrap();
+    /// let patch: Value = serde_json::from_str(
+    ///     r#"[
+    ///         {"op": "test", "path": "/b", "value": "x"},
+    ///         {"op": "add", "path": "/a/-", "value": 3},
+    ///         {"op": "move", "from": "/b", "path": "/c"}
+    ///     ]"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// data.apply_patch(&PatchOp::from_patch(&patch).unwrap()).unwrap();
+    /// assert_eq!(data.to_string(), r#"{"a":[1,2,3],"c":"x"}"#);
+    /// ```
+    pub fn apply_patch(&mut self, ops: &[PatchOp<'ctx>]) -> Result<(), PatchError> {
+        let mut undos = Vec::new();
+        for op in ops {
+            if let Err(err) = patch_apply(self, op, &mut undos) {
+                let mut taken = None;
+                for undo in undos.into_iter().rev() {
+                    taken = undo.revert(self, taken);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Records how to revert a single change made by [`Value::apply_patch`].
+///
+/// Operations are applied in place, and on failure the recorded changes are reverted in reverse
+/// order, which avoids cloning the whole document up front.
+enum Undo<'p, 'ctx> {
+    /// Puts back the value that was overwritten at `path`.
+    Restore { path: &'p str, value: Value<'ctx> },
+    /// Removes the element or entry that was inserted at `index` of `parent`.
+    Remove { parent: &'p str, index: usize },
+    /// Inserts back the element or entry that was removed from `index` of `parent`. A `value` of
+    /// `None` stands for the value taken out by the previously reverted change, i.e. the value
+    /// that a `move` operation added elsewhere.
+    Insert {
+        parent: &'p str,
+        index: usize,
+        key: Option<Cow<'ctx, str>>,
+        value: Option<Value<'ctx>>,
+    },
+}
+
+impl<'ctx> Undo<'_, 'ctx> {
+    /// Reverts the change on `doc`, returning the value it took out of the document.
+    fn revert(self, doc: &mut Value<'ctx>, taken: Option<Value<'ctx>>) -> Option<Value<'ctx>> {
+        match self {
+            Undo::Restore { path, value } => doc
+                .pointer_mut(path)
+                .map(|target| mem::replace(target, value)),
+            Undo::Remove { parent, index } => match doc.pointer_mut(parent)? {
+                Value::Object(obj) => Some(obj.remove(index).1),
+                Value::Array(arr) => Some(arr.remove(index)),
+                _ => None,
+            },
+            Undo::Insert {
+                parent,
+                index,
+                key,
+                value,
+            } => {
+                let value = value.or(taken)?;
+                match (doc.pointer_mut(parent)?, key) {
+                    (Value::Object(obj), Some(key)) => obj.insert(index, (key, value)),
+                    (Value::Array(arr), None) => arr.insert(index, value),
+                    _ => {}
+                }
+                None
+            }
+        }
+    }
+}
+
+/// Applies a single operation in place, recording how to revert it in `undos`.
+fn patch_apply<'p, 'ctx>(
+    doc: &mut Value<'ctx>,
+    op: &'p PatchOp<'ctx>,
+    undos: &mut Vec<Undo<'p, 'ctx>>,
+) -> Result<(), PatchError> {
+    match op {
+        PatchOp::Add { path, value } => {
+            undos.push(patch_add(doc, path, value.clone()).map_err(|(err, _)| err)?);
+        }
+        PatchOp::Remove { path } => {
+            let (mut undo, removed) = patch_remove(doc, path)?;
+            if let Undo::Insert { value, .. } = &mut undo {
+                *value = Some(removed);
+            }
+            undos.push(undo);
+        }
+        PatchOp::Replace { path, value } => {
+            let old = mem::replace(patch_locate(doc, path)?, value.clone());
+            undos.push(Undo::Restore { path, value: old });
+        }
+        PatchOp::Move { from, path } => {
+            if path.starts_with(from.as_ref()) && path[from.len()..].starts_with('/') {
+                return Err(PatchError::InvalidPath(path.to_string()));
+            }
+            let (mut undo, removed) = patch_remove(doc, from)?;
+            match patch_add(doc, path, removed) {
+                Ok(add_undo) => {
+                    undos.push(undo);
+                    undos.push(add_undo);
+                }
+                Err((err, removed)) => {
+                    if let Undo::Insert { value, .. } = &mut undo {
+                        *value = Some(removed);
+                    }
+                    undos.push(undo);
+                    return Err(err);
+                }
+            }
+        }
+        PatchOp::Copy { from, path } => {
+            let value = doc
+                .pointer(from)
+                .ok_or_else(|| PatchError::PathNotFound(from.to_string()))?
+                .clone();
+            undos.push(patch_add(doc, path, value).map_err(|(err, _)| err)?);
+        }
+        PatchOp::Test { path, value } => {
+            let actual = doc
+                .pointer(path)
+                .ok_or_else(|| PatchError::PathNotFound(path.to_string()))?;
+            if !actual.semantic_eq(value) {
+                return Err(PatchError::TestFailed(path.to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the parent of the location the path points to.
+fn patch_parent<'a, 'ctx, 'p>(
+    doc: &'a mut Value<'ctx>,
+    path: &'p str,
+) -> Result<(&'a mut Value<'ctx>, &'p str, Cow<'p, str>), PatchError> {
+    let (parent_path, last) =
+        split_last(path).ok_or_else(|| PatchError::InvalidPath(path.to_string()))?;
+    let parent = doc
+        .pointer_mut(parent_path)
+        .ok_or_else(|| PatchError::PathNotFound(path.to_string()))?;
+    Ok((parent, parent_path, last))
+}
+
+/// Resolves the existing value the path points to.
+fn patch_locate<'a, 'ctx>(
+    doc: &'a mut Value<'ctx>,
+    path: &str,
+) -> Result<&'a mut Value<'ctx>, PatchError> {
+    if path.is_empty() {
+        return Ok(doc);
+    }
+    let (parent, _, last) = patch_parent(doc, path)?;
+    match parent {
+        Value::Object(obj) => obj
+            .iter_mut()
+            .find(|(k, _)| *k == last)
+            .map(|(_, v)| v)
+            .ok_or_else(|| PatchError::PathNotFound(path.to_string())),
+        Value::Array(arr) => parse_index(&last)
+            .and_then(|i| arr.get_mut(i))
+            .ok_or_else(|| PatchError::InvalidIndex(path.to_string())),
+        _ => Err(PatchError::PathNotFound(path.to_string())),
+    }
+}
+
+/// Adds the value at the path. On failure, the value is handed back along with the error.
+fn patch_add<'p, 'ctx>(
+    doc: &mut Value<'ctx>,
+    path: &'p str,
+    value: Value<'ctx>,
+) -> Result<Undo<'p, 'ctx>, (PatchError, Value<'ctx>)> {
+    if path.is_empty() {
+        let old = mem::replace(doc, value);
+        return Ok(Undo::Restore { path, value: old });
+    }
+    let (parent, parent_path, last) = match patch_parent(doc, path) {
+        Ok(resolved) => resolved,
+        Err(err) => return Err((err, value)),
+    };
+    match parent {
+        Value::Object(obj) => {
+            if let Some((_, v)) = obj.iter_mut().find(|(k, _)| *k == last) {
+                let old = mem::replace(v, value);
+                Ok(Undo::Restore { path, value: old })
+            } else {
+                obj.push((Cow::Owned(last.into_owned()), value));
+                Ok(Undo::Remove {
+                    parent: parent_path,
+                    index: obj.len() - 1,
+                })
+            }
+        }
+        Value::Array(arr) => {
+            let index = if last == "-" {
+                Some(arr.len())
+            } else {
+                parse_index(&last).filter(|i| *i <= arr.len())
+            };
+            match index {
+                Some(index) => {
+                    arr.insert(index, value);
+                    Ok(Undo::Remove {
+                        parent: parent_path,
+                        index,
+                    })
+                }
+                None => Err((PatchError::InvalidIndex(path.to_string()), value)),
+            }
+        }
+        _ => Err((PatchError::PathNotFound(path.to_string()), value)),
+    }
+}
+
+/// Removes the value at the path, returning it along with the change that inserts it back once
+/// the value is given.
+fn patch_remove<'p, 'ctx>(
+    doc: &mut Value<'ctx>,
+    path: &'p str,
+) -> Result<(Undo<'p, 'ctx>, Value<'ctx>), PatchError> {
+    if path.is_empty() {
+        return Err(PatchError::InvalidPath(path.to_string()));
+    }
+    let (parent, parent_path, last) = patch_parent(doc, path)?;
+    let (index, key, value) = match parent {
+        Value::Object(obj) => {
+            let pos = obj
+                .iter()
+                .position(|(k, _)| *k == last)
+                .ok_or_else(|| PatchError::PathNotFound(path.to_string()))?;
+            let (key, value) = obj.remove(pos);
+            (pos, Some(key), value)
+        }
+        Value::Array(arr) => {
+            let index = parse_index(&last)
+                .filter(|i| *i < arr.len())
+                .ok_or_else(|| PatchError::InvalidIndex(path.to_string()))?;
+            (index, None, arr.remove(index))
+        }
+        _ => return Err(PatchError::PathNotFound(path.to_string())),
+    };
+    let undo = Undo::Insert {
+        parent: parent_path,
+        index,
+        key,
+        value: None,
+    };
+    Ok((undo, value))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{PatchError, PatchOp, Value};
+
+    fn merge(target: &str, patch: &str) -> String {
+        let mut target: Value = serde_json::from_str(target).unwrap();
+        let patch: Value = serde_json::from_str(patch).unwrap();
+        target.merge_patch(&patch);
+        target.to_string()
+    }
+
+    #[test]
+    fn merge_patch_rfc7386_examples_test() {
+        let cases = [
+            (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+            (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+            (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+            (
+                r#"{"a":{"b":"c"}}"#,
+                r#"{"a":{"b":"d","c":null}}"#,
+                r#"{"a":{"b":"d"}}"#,
+            ),
+            (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+            (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+            (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+            (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+            (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+            (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+            (
+                r#"{}"#,
+                r#"{"a":{"bb":{"ccc":null}}}"#,
+                r#"{"a":{"bb":{}}}"#,
+            ),
+        ];
+        for (target, patch, expected) in cases {
+            assert_eq!(merge(target, patch), expected, "{} + {}", target, patch);
+        }
+    }
+
+    #[test]
+    fn merge_patch_nested_test() {
+        assert_eq!(
+            merge(
+                r#"{"a":{"b":{"c":1,"d":2},"e":[1,2]},"f":true}"#,
+                r#"{"a":{"b":{"c":null,"x":{"y":null}},"e":3}}"#
+            ),
+            r#"{"a":{"b":{"d":2,"x":{}},"e":3},"f":true}"#
+        );
+    }
+
+    #[test]
+    fn merge_patch_duplicate_keys_test() {
+        assert_eq!(
+            merge(r#"{"a":1,"b":2,"a":3}"#, r#"{"a":null}"#),
+            r#"{"b":2}"#
+        );
+        assert_eq!(
+            merge(r#"{"a":1,"b":2,"a":3}"#, r#"{"a":4}"#),
+            r#"{"a":4,"b":2,"a":3}"#
+        );
+    }
+
+    fn deep_merge(target: &str, other: &str) -> String {
+        let mut target: Value = serde_json::from_str(target).unwrap();
+        let other: Value = serde_json::from_str(other).unwrap();
+        target.merge(other);
+        target.to_string()
+    }
+
+    #[test]
+    fn merge_test() {
+        let cases = [
+            (
+                r#"{"a":{"b":{"c":1,"d":2},"e":[1,2]},"f":true}"#,
+                r#"{"a":{"b":{"c":null,"x":{"y":null}},"e":[3]},"g":"h"}"#,
+                r#"{"a":{"b":{"c":null,"d":2,"x":{"y":null}},"e":[3]},"f":true,"g":"h"}"#,
+            ),
+            // Type mismatches replace the value.
+            (r#"{"a":{"b":1}}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"{"a":[1]}"#, r#"{"a":{"b":1}}"#, r#"{"a":{"b":1}}"#),
+            (r#"{"a":1}"#, r#"null"#, r#"null"#),
+            (r#"[1,2]"#, r#"{"a":1}"#, r#"{"a":1}"#),
+            (r#""x""#, r#"5"#, r#"5"#),
+            // Duplicate keys merge into the first entry.
+            (
+                r#"{"a":{"x":1},"a":2}"#,
+                r#"{"a":{"y":3}}"#,
+                r#"{"a":{"x":1,"y":3},"a":2}"#,
+            ),
+        ];
+        for (target, other, expected) in cases {
+            assert_eq!(
+                deep_merge(target, other),
+                expected,
+                "{} + {}",
+                target,
+                other
+            );
+        }
+    }
+
+    #[test]
+    fn merge_arrays_by_key_test() {
+        let merge_by_id = |target: &str, other: &str| {
+            let mut target: Value = serde_json::from_str(target).unwrap();
+            let other: Value = serde_json::from_str(other).unwrap();
+            target.merge_arrays_by_key(&other, "id");
+            target.to_string()
+        };
+        let cases = [
+            // Nested arrays are merged by key as well.
+            (
+                r#"[{"id":1,"sub":[{"id":"a","v":1}],"x":0}]"#,
+                r#"[{"id":1,"sub":[{"id":"a","v":2},{"id":"b"}]}]"#,
+                r#"[{"id":1,"sub":[{"id":"a","v":2},{"id":"b"}],"x":0}]"#,
+            ),
+            // Elements lacking the key, and non-objects, are appended.
+            (
+                r#"[{"id":1},{"n":1},2]"#,
+                r#"[{"n":1},2,{"id":null}]"#,
+                r#"[{"id":1},{"n":1},2,{"n":1},2,{"id":null}]"#,
+            ),
+            (
+                r#"[{"id":null}]"#,
+                r#"[{"id":null,"v":1}]"#,
+                r#"[{"id":null,"v":1}]"#,
+            ),
+            // The first match wins, also for repeated ids in `other`.
+            (
+                r#"[{"id":1,"a":1},{"id":1,"a":2}]"#,
+                r#"[{"id":1,"b":1},{"id":1,"b":2}]"#,
+                r#"[{"id":1,"a":1,"b":2},{"id":1,"a":2}]"#,
+            ),
+            // Ids are compared with `==`, so an integer and a float differ.
+            (
+                r#"[{"id":1}]"#,
+                r#"[{"id":1.0}]"#,
+                r#"[{"id":1},{"id":1.0}]"#,
+            ),
+            (r#"[{"id":1}]"#, r#"{"id":1}"#, r#"{"id":1}"#),
+            (
+                r#"{"a":[1]}"#,
+                r#"{"a":null,"b":[]}"#,
+                r#"{"a":null,"b":[]}"#,
+            ),
+        ];
+        for (target, other, expected) in cases {
+            assert_eq!(
+                merge_by_id(target, other),
+                expected,
+                "{} + {}",
+                target,
+                other
+            );
+        }
+    }
+
+    #[test]
+    fn merge_patch_escaped_keys_test() {
+        assert_eq!(
+            merge(r#"{"a\"b":1}"#, r#"{"a\"b":null,"c\nd":2}"#),
+            r#"{"c\nd":2}"#
+        );
+    }
+
+    fn apply(target: &str, patch: &str) -> Result<String, PatchError> {
+        let mut target: Value = serde_json::from_str(target).unwrap();
+        let patch: Value = serde_json::from_str(patch).unwrap();
+        target.apply_patch(&PatchOp::from_patch(&patch)?)?;
+        Ok(target.to_string())
+    }
+
+    #[test]
+    fn apply_patch_add_test() {
+        let doc = r#"{"foo":"bar","arr":[1,2]}"#;
+        let ok = |patch: &str| apply(doc, patch).unwrap();
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/baz","value":"qux"}]"#),
+            r#"{"foo":"bar","arr":[1,2],"baz":"qux"}"#
+        );
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/foo","value":null}]"#),
+            r#"{"foo":null,"arr":[1,2]}"#
+        );
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/arr/1","value":"x"}]"#),
+            r#"{"foo":"bar","arr":[1,"x",2]}"#
+        );
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/arr/2","value":3}]"#),
+            r#"{"foo":"bar","arr":[1,2,3]}"#
+        );
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/arr/-","value":3}]"#),
+            r#"{"foo":"bar","arr":[1,2,3]}"#
+        );
+        assert_eq!(ok(r#"[{"op":"add","path":"","value":[]}]"#), r#"[]"#);
+        assert_eq!(
+            ok(r#"[{"op":"add","path":"/a~1b","value":1}]"#),
+            r#"{"foo":"bar","arr":[1,2],"a/b":1}"#
+        );
+
+        assert_eq!(
+            apply(doc, r#"[{"op":"add","path":"/arr/3","value":3}]"#),
+            Err(PatchError::InvalidIndex("/arr/3".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"add","path":"/arr/01","value":3}]"#),
+            Err(PatchError::InvalidIndex("/arr/01".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"add","path":"/missing/a","value":3}]"#),
+            Err(PatchError::PathNotFound("/missing/a".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"add","path":"foo","value":3}]"#),
+            Err(PatchError::InvalidPath("foo".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_patch_remove_replace_test() {
+        let doc = r#"{"foo":"bar","arr":[1,2]}"#;
+        assert_eq!(
+            apply(doc, r#"[{"op":"remove","path":"/foo"}]"#).unwrap(),
+            r#"{"arr":[1,2]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"remove","path":"/arr/0"}]"#).unwrap(),
+            r#"{"foo":"bar","arr":[2]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"remove","path":"/arr/2"}]"#),
+            Err(PatchError::InvalidIndex("/arr/2".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"remove","path":"/baz"}]"#),
+            Err(PatchError::PathNotFound("/baz".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"replace","path":"/foo","value":{"a":1}}]"#).unwrap(),
+            r#"{"foo":{"a":1},"arr":[1,2]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"replace","path":"/arr/1","value":3}]"#).unwrap(),
+            r#"{"foo":"bar","arr":[1,3]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"replace","path":"","value":1}]"#).unwrap(),
+            r#"1"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"replace","path":"/arr/2","value":3}]"#),
+            Err(PatchError::InvalidIndex("/arr/2".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"replace","path":"/baz","value":3}]"#),
+            Err(PatchError::PathNotFound("/baz".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_patch_move_copy_test() {
+        let doc = r#"{"foo":{"bar":"baz","waldo":"fred"},"qux":{"corge":"grault"},"arr":[1,2,3]}"#;
+        assert_eq!(
+            apply(
+                doc,
+                r#"[{"op":"move","from":"/foo/waldo","path":"/qux/thud"}]"#
+            )
+            .unwrap(),
+            r#"{"foo":{"bar":"baz"},"qux":{"corge":"grault","thud":"fred"},"arr":[1,2,3]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"move","from":"/arr/0","path":"/arr/2"}]"#).unwrap(),
+            r#"{"foo":{"bar":"baz","waldo":"fred"},"qux":{"corge":"grault"},"arr":[2,3,1]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"copy","from":"/qux","path":"/arr/-"}]"#).unwrap(),
+            r#"{"foo":{"bar":"baz","waldo":"fred"},"qux":{"corge":"grault"},"arr":[1,2,3,{"corge":"grault"}]}"#
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"move","from":"/foo","path":"/foo/child"}]"#),
+            Err(PatchError::InvalidPath("/foo/child".to_string()))
+        );
+        assert_eq!(
+            apply(doc, r#"[{"op":"copy","from":"/nope","path":"/a"}]"#),
+            Err(PatchError::PathNotFound("/nope".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_patch_test_op_is_atomic_test() {
+        let doc = r#"{"baz":"qux","foo":["a",2,"c"]}"#;
+        assert_eq!(
+            apply(
+                doc,
+                r#"[{"op":"test","path":"/baz","value":"qux"},{"op":"test","path":"/foo/1","value":2}]"#
+            )
+            .unwrap(),
+            doc
+        );
+
+        let mut target: Value = serde_json::from_str(doc).unwrap();
+        let patch: Value = serde_json::from_str(
+            r#"[{"op":"remove","path":"/baz"},{"op":"test","path":"/foo/1","value":"2"}]"#,
+        )
+        .unwrap();
+        let err = target
+            .apply_patch(&PatchOp::from_patch(&patch).unwrap())
+            .unwrap_err();
+        assert_eq!(err, PatchError::TestFailed("/foo/1".to_string()));
+        // The failed patch left the document unchanged.
+        assert_eq!(target.to_string(), doc);
+    }
+
+    #[test]
+    fn apply_patch_test_op_is_semantic_test() {
+        let doc = r#"{"obj":{"b":2,"a":1},"n":1.0}"#;
+        let patch = r#"[{"op":"test","path":"/obj","value":{"a":1,"b":2}},{"op":"test","path":"/n","value":1}]"#;
+        assert_eq!(apply(doc, patch).unwrap(), doc);
+        assert_eq!(
+            apply(doc, r#"[{"op":"test","path":"/n","value":2}]"#),
+            Err(PatchError::TestFailed("/n".to_string()))
+        );
+    }
+
+    #[test]
+    fn apply_patch_rollback_test() {
+        let doc = r#"{"a":1,"b":[1,2,3],"a":2,"c":{"d":null}}"#;
+        let patches = [
+            r#"[{"op":"remove","path":"/a"},{"op":"test","path":"/x","value":1}]"#,
+            r#"[{"op":"add","path":"/b/1","value":9},{"op":"replace","path":"/c/d","value":true},{"op":"add","path":"/e","value":0},{"op":"remove","path":"/nope"}]"#,
+            r#"[{"op":"move","from":"/b/0","path":"/c/d"},{"op":"move","from":"/a","path":"/b/1"},{"op":"copy","from":"/c","path":"/f"},{"op":"test","path":"/f/d","value":2}]"#,
+            r#"[{"op":"move","from":"/a","path":"/b/9"}]"#,
+            r#"[{"op":"remove","path":"/b/0"},{"op":"add","path":"","value":[]},{"op":"test","path":"/0","value":1}]"#,
+        ];
+        for patch in patches {
+            let mut target: Value = serde_json::from_str(doc).unwrap();
+            let patch: Value = serde_json::from_str(patch).unwrap();
+            assert!(target
+                .apply_patch(&PatchOp::from_patch(&patch).unwrap())
+                .is_err());
+            // Entry order and duplicate keys are restored exactly.
+            assert_eq!(target.to_string(), doc);
+        }
+    }
+
+    #[test]
+    fn parse_patch_errors_test() {
+        let parse = |patch: &str| {
+            let patch: Value = serde_json::from_str(patch).unwrap();
+            PatchOp::from_patch(&patch).map(|ops| ops.len())
+        };
+        assert_eq!(parse(r#"[]"#), Ok(0));
+        assert!(matches!(
+            parse(r#"{}"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            parse(r#"[{"op":"add","path":"/a"}]"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            parse(r#"[{"op":"move","path":"/a"}]"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            parse(r#"[{"op":"nope","path":"/a"}]"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            parse(r#"[{"op":"remove","path":1}]"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+        assert!(matches!(
+            parse(r#"[1]"#),
+            Err(PatchError::InvalidOperation(_))
+        ));
+    }
+}
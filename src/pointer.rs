@@ -0,0 +1,242 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::{format, vec};
+
+use crate::Value;
+
+impl<'ctx> Value<'ctx> {
+    /// Looks up a value by a JSON Pointer, as defined in
+    /// [RFC 6901](https://tools.ietf.org/html/rfc6901).
+    ///
+    /// A JSON Pointer is a string of tokens, each prefixed by `/`. A token is either an object key
+    /// or an array index. Within a key, `~1` is unescaped to `/` and `~0` is unescaped to `~`.
+    /// The empty pointer `""` refers to the whole document.
+    ///
+    /// Returns `None` if any token can't be resolved. If an object contains a key multiple times,
+    /// the first matching value is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"x": {"y": ["z", "zz"]}, "a/b": 1}"#).unwrap();
+    ///
+    /// assert_eq!(data.pointer("/x/y/1"), Some(&Value::Str("zz".into())));
+    /// assert_eq!(data.pointer("/a~1b"), Some(&Value::Number(1u64.into())));
+    /// assert_eq!(data.pointer("/x/y/2"), None);
+    /// assert_eq!(data.pointer(""), Some(&data));
+    /// ```
+    pub fn pointer<'a>(&'a self, pointer: &str) -> Option<&'a Value<'ctx>> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Value::Object(obj) => obj.iter().find(|(k, _)| *k == token).map(|(_, v)| v)?,
+                Value::Array(arr) => parse_index(&token).and_then(|i| arr.get(i))?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+
+    /// Looks up a value by a JSON Pointer and returns a mutable reference to it.
+    ///
+    /// See [`pointer`](Value::pointer) for the pointer syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"x": {"y": ["z", "zz"]}}"#).unwrap();
+    ///
+    /// *data.pointer_mut("/x/y/0").unwrap() = Value::Bool(false);
+    /// assert_eq!(data.pointer("/x/y/0"), Some(&Value::Bool(false)));
+    /// ```
+    pub fn pointer_mut<'a>(&'a mut self, pointer: &str) -> Option<&'a mut Value<'ctx>> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Value::Object(obj) => obj.iter_mut().find(|(k, _)| *k == token).map(|(_, v)| v)?,
+                Value::Array(arr) => parse_index(&token).and_then(move |i| arr.get_mut(i))?,
+                _ => return None,
+            };
+        }
+        Some(target)
+    }
+
+    /// Iterates over all values of the document in pre-order, each paired with its JSON Pointer,
+    /// e.g. to build an index of the document.
+    ///
+    /// Arrays and objects are yielded before their children, starting with the whole document
+    /// with the empty pointer `""`. Children are visited in document order. Keys are escaped as
+    /// described in [`pointer`](Value::pointer). Entries of a duplicate key yield the same
+    /// pointer, which only resolves to the first of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"a": [1, {"b/c": null}], "d": true}"#).unwrap();
+    ///
+    /// let pointers: Vec<String> = data.pointer_iter().map(|(pointer, _)| pointer).collect();
+    /// assert_eq!(pointers, ["", "/a", "/a/0", "/a/1", "/a/1/b~1c", "/d"]);
+    /// ```
+    pub fn pointer_iter(&self) -> impl Iterator<Item = (String, &Value<'ctx>)> {
+        let mut stack = vec![(String::new(), self)];
+        core::iter::from_fn(move || {
+            let (pointer, value) = stack.pop()?;
+            match value {
+                Value::Array(arr) => stack.extend(
+                    arr.iter()
+                        .enumerate()
+                        .rev()
+                        .map(|(i, val)| (format!("{}/{}", pointer, i), val)),
+                ),
+                Value::Object(obj) => stack.extend(
+                    obj.iter()
+                        .rev()
+                        .map(|(key, val)| (format!("{}/{}", pointer, escape_token(key)), val)),
+                ),
+                _ => {}
+            }
+            Some((pointer, value))
+        })
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped tokens.
+///
+/// Returns `None` if the pointer is not empty and doesn't start with `/`.
+pub(crate) fn tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+    Some(pointer.split('/').skip(1).map(unescape_token))
+}
+
+/// Splits a JSON Pointer into the pointer of the parent and the unescaped last token.
+///
+/// Returns `None` for the empty pointer, which has no parent, and for invalid pointers.
+pub(crate) fn split_last(pointer: &str) -> Option<(&str, Cow<'_, str>)> {
+    if !pointer.starts_with('/') {
+        return None;
+    }
+    let pos = pointer.rfind('/')?;
+    Some((&pointer[..pos], unescape_token(&pointer[pos + 1..])))
+}
+
+/// Escapes a key for use as a JSON Pointer token, the reverse of [`unescape_token`].
+pub(crate) fn escape_token(key: &str) -> Cow<'_, str> {
+    if key.contains(['~', '/']) {
+        Cow::Owned(key.replace('~', "~0").replace('/', "~1"))
+    } else {
+        Cow::Borrowed(key)
+    }
+}
+
+pub(crate) fn unescape_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Parses an array index token. Leading zeros and signs are not allowed.
+pub(crate) fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() != 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::Value;
+
+    #[test]
+    fn pointer_rfc6901_examples_test() {
+        let json_obj = r#"
+            {
+                "foo": ["bar", "baz"],
+                "": 0,
+                "a/b": 1,
+                "c%d": 2,
+                "e^f": 3,
+                "g|h": 4,
+                "i\\j": 5,
+                "k\"l": 6,
+                " ": 7,
+                "m~n": 8
+            }
+       "#;
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+
+        assert_eq!(val.pointer(""), Some(&val));
+        assert!(val.pointer("/foo").unwrap().is_array());
+        assert_eq!(val.pointer("/foo/0"), Some(&Value::Str("bar".into())));
+        for (pointer, expected) in [
+            ("/", 0u64),
+            ("/a~1b", 1),
+            ("/c%d", 2),
+            ("/e^f", 3),
+            ("/g|h", 4),
+            ("/i\\j", 5),
+            ("/k\"l", 6),
+            ("/ ", 7),
+            ("/m~0n", 8),
+        ] {
+            assert_eq!(val.pointer(pointer), Some(&Value::Number(expected.into())));
+        }
+    }
+
+    #[test]
+    fn pointer_unresolved_test() {
+        let val: Value = serde_json::from_str(r#"{"arr": [1, 2], "obj": {"k": null}}"#).unwrap();
+
+        assert_eq!(val.pointer("arr"), None);
+        assert_eq!(val.pointer("/missing"), None);
+        assert_eq!(val.pointer("/arr/2"), None);
+        assert_eq!(val.pointer("/arr/01"), None);
+        assert_eq!(val.pointer("/arr/+1"), None);
+        assert_eq!(val.pointer("/arr/-"), None);
+        assert_eq!(val.pointer("/arr/0/x"), None);
+        assert_eq!(val.pointer("/obj/0"), None);
+        assert_eq!(val.pointer("/obj/k"), Some(&Value::Null));
+    }
+
+    #[test]
+    fn pointer_mut_test() {
+        let mut val: Value = serde_json::from_str(r#"{"arr": [1, {"a~b": 2}]}"#).unwrap();
+
+        *val.pointer_mut("/arr/1/a~0b").unwrap() = Value::Str("x".into());
+        assert_eq!(val.pointer("/arr/1/a~0b"), Some(&Value::Str("x".into())));
+        assert!(val.pointer_mut("/arr/2").is_none());
+        assert!(val.pointer_mut("/arr/1/missing").is_none());
+    }
+
+    #[test]
+    fn pointer_iter_test() {
+        let val: Value =
+            serde_json::from_str(r#"{"a": [[], {"~": 1}], "": {"k": [null]}, "a": 2}"#).unwrap();
+        let pairs: Vec<(String, &Value)> = val.pointer_iter().collect();
+        let pointers: Vec<&str> = pairs.iter().map(|(p, _)| p.as_str()).collect();
+        assert_eq!(
+            pointers,
+            ["", "/a", "/a/0", "/a/1", "/a/1/~0", "/", "//k", "//k/0", "/a"]
+        );
+        // Each pointer resolves to its value, except for later duplicate keys.
+        for (pointer, value) in &pairs[..pairs.len() - 1] {
+            assert_eq!(val.pointer(pointer), Some(*value), "{}", pointer);
+        }
+        assert_eq!(pairs[8].1, &Value::Number(2u64.into()));
+
+        let scalar = Value::Bool(true);
+        assert_eq!(
+            scalar.pointer_iter().collect::<Vec<_>>(),
+            [(String::new(), &scalar)]
+        );
+    }
+}
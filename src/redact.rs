@@ -0,0 +1,169 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::pointer::{parse_index, tokens};
+use crate::Value;
+
+impl<'ctx> Value<'ctx> {
+    /// Replaces the values at the given JSON Pointers with a clone of `replacement`, e.g. to mask
+    /// sensitive fields before logging a document.
+    ///
+    /// Pointers which don't resolve are silently skipped, as are invalid pointers. Unlike
+    /// [`pointer`](Value::pointer), every entry of a duplicate key is redacted, so no copy of a
+    /// sensitive field is left behind. See [`pointer`](Value::pointer) for the pointer syntax.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value =
+    ///     serde_json::from_str(r#"{"user": {"name": "a", "password": "secret"}, "cards": [{"number": "4111"}]}"#)
+    ///         .unwrap();
+    ///
+    /// data.redact_paths(&["/user/password", "/cards/0/number", "/missing"], Value::Str("***".into()));
+    /// assert_eq!(
+    ///     data.to_string(),
+    ///     r#"{"user":{"name":"a","password":"***"},"cards":[{"number":"***"}]}"#
+    /// );
+    /// ```
+    pub fn redact_paths(&mut self, pointers: &[&str], replacement: Value<'ctx>) {
+        for pointer in pointers {
+            if let Some(tokens) = tokens(pointer) {
+                let tokens: Vec<_> = tokens.collect();
+                replace_at(self, &tokens, &replacement);
+            }
+        }
+    }
+
+    /// Removes the values at the given JSON Pointers, e.g. to strip sensitive fields before
+    /// logging a document.
+    ///
+    /// Pointers which don't resolve are silently skipped, as are invalid pointers and the empty
+    /// pointer, since the whole document can't be removed. Every entry of a duplicate key is
+    /// removed. The pointers are applied one after the other, so removing an array element shifts
+    /// the indices of the following elements for later pointers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value =
+    ///     serde_json::from_str(r#"{"user": {"name": "a", "password": "secret"}, "token": "t"}"#).unwrap();
+    ///
+    /// data.remove_paths(&["/user/password", "/token", "/missing/field"]);
+    /// assert_eq!(data.to_string(), r#"{"user":{"name":"a"}}"#);
+    /// ```
+    pub fn remove_paths(&mut self, pointers: &[&str]) {
+        for pointer in pointers {
+            if let Some(tokens) = tokens(pointer) {
+                let tokens: Vec<_> = tokens.collect();
+                remove_at(self, &tokens);
+            }
+        }
+    }
+}
+
+fn replace_at<'ctx>(target: &mut Value<'ctx>, tokens: &[Cow<'_, str>], replacement: &Value<'ctx>) {
+    let Some((token, rest)) = tokens.split_first() else {
+        *target = replacement.clone();
+        return;
+    };
+    match target {
+        Value::Object(obj) => {
+            for (_, val) in obj.iter_mut().filter(|(k, _)| k == token) {
+                replace_at(val, rest, replacement);
+            }
+        }
+        Value::Array(arr) => {
+            if let Some(val) = parse_index(token).and_then(|i| arr.get_mut(i)) {
+                replace_at(val, rest, replacement);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn remove_at(target: &mut Value<'_>, tokens: &[Cow<'_, str>]) {
+    let Some((token, rest)) = tokens.split_first() else {
+        return;
+    };
+    match target {
+        Value::Object(obj) if rest.is_empty() => obj.retain(|(k, _)| k != token),
+        Value::Object(obj) => {
+            for (_, val) in obj.iter_mut().filter(|(k, _)| k == token) {
+                remove_at(val, rest);
+            }
+        }
+        Value::Array(arr) => match parse_index(token) {
+            Some(i) if i < arr.len() && rest.is_empty() => {
+                arr.remove(i);
+            }
+            Some(i) if i < arr.len() => remove_at(&mut arr[i], rest),
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn redact_paths_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(
+            r#"{"a": {"pw": 1}, "a": {"pw": 2}, "list": [{"k/~": 3}, 4], "s": "x"}"#,
+        )?;
+        data.redact_paths(
+            &[
+                "/a/pw",
+                "/list/0/k~1~0",
+                "/list/1",
+                "/list/2",
+                "/list/01",
+                "/s/x",
+                "a",
+                "",
+            ],
+            Value::Null,
+        );
+        assert_eq!(data.to_string(), "null");
+
+        let mut data: Value = serde_json::from_str(
+            r#"{"a": {"pw": 1}, "a": {"pw": 2}, "list": [{"k/~": 3}, 4], "s": "x"}"#,
+        )?;
+        data.redact_paths(
+            &[
+                "/a/pw",
+                "/list/0/k~1~0",
+                "/list/1",
+                "/list/2",
+                "/list/01",
+                "/s/x",
+                "a",
+            ],
+            Value::Str("***".into()),
+        );
+        assert_eq!(
+            data.to_string(),
+            r#"{"a":{"pw":"***"},"a":{"pw":"***"},"list":[{"k/~":"***"},"***"],"s":"x"}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove_paths_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(
+            r#"{"a": {"pw": 1, "x": 0}, "a": {"pw": 2}, "list": [1, 2, 3], "pw": 5, "pw": 6}"#,
+        )?;
+        data.remove_paths(&[
+            "/a/pw", "/pw", "/list/0", "/list/0", "/list/5", "", "x", "/a/x/y",
+        ]);
+        assert_eq!(data.to_string(), r#"{"a":{"x":0},"a":{},"list":[3]}"#);
+        Ok(())
+    }
+}
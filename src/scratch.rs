@@ -0,0 +1,192 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::mem;
+
+use serde::de::{DeserializeSeed, MapAccess, SeqAccess};
+
+use crate::de::{Collect, KeySeed, ValueVisitor};
+use crate::{ParseError, Value};
+
+/// Reusable buffers for [`from_str_with_scratch`].
+///
+/// While parsing, the elements of arrays and the entries of objects are collected on a stack
+/// kept in the scratch space, and each array or object is then allocated once with its exact
+/// length. The stack is kept between parses on a best-effort basis, see
+/// [`from_str_with_scratch`]. Unlike an [`Interner`](crate::Interner),
+/// the scratch space is not borrowed by the returned `Value`.
+#[derive(Debug, Default)]
+pub struct Scratch {
+    values: Vec<Value<'static>>,
+    entries: Vec<(Cow<'static, str>, Value<'static>)>,
+}
+
+impl Scratch {
+    /// Creates an empty scratch space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a scratch space with room for `capacity` array elements and as many object entries
+    /// on the stack, e.g. the number of values in a typical document.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(capacity),
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+}
+
+/// Parses a `Value` from a `&str`, using `scratch` to avoid growing the `Vec`s of arrays and
+/// objects.
+///
+/// A regular parse pushes the elements of each array and object into a new `Vec`, which is
+/// reallocated whenever it grows beyond its capacity, and usually ends up with unused capacity.
+/// This function allocates each array and object once, with its exact length, and reuses the
+/// buffers of `scratch` for the elements while they are being parsed, so the same `Scratch` should
+/// be used for many documents, e.g. one per thread.
+///
+/// Don't expect a large speedup: modern allocators grow small `Vec`s cheaply, and the elements are
+/// copied once more from the scratch buffers. In the benchmarks of the repository, the throughput
+/// is within about 10% of [`from_str`](crate::from_str) in both directions, depending on the data
+/// set; it tends to win for documents with large arrays and objects. The main gain is that the
+/// returned `Value` holds no unused capacity, which reduces its memory usage and thereby the
+/// allocator pressure when many values are kept alive.
+///
+/// The result is the same as for [`from_str`](crate::from_str).
+///
+/// Reusing the buffers is best-effort: since the stacks hold values borrowing from the input,
+/// they are converted between lifetimes by collecting the emptied `Vec` into a new one, which the
+/// standard library currently does in place, keeping the allocation. This is an optimization of
+/// the standard library, not a guarantee, so a future version may start each parse with empty
+/// buffers, which is still correct but allocates more.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::{from_str_with_scratch, Scratch};
+///
+/// let mut scratch = Scratch::new();
+/// for line in [r#"{"id": 1, "tags": ["a", "b"]}"#, r#"{"id": 2, "tags": []}"#] {
+///     let value = from_str_with_scratch(line, &mut scratch).unwrap();
+///     assert!(value.get("id").as_u64().is_some());
+///     let tags = value.get("tags").as_array().unwrap();
+///     assert_eq!(tags.capacity(), tags.len());
+/// }
+/// ```
+pub fn from_str_with_scratch<'a>(
+    input: &'a str,
+    scratch: &mut Scratch,
+) -> Result<Value<'a>, ParseError> {
+    let mut values = recycle(mem::take(&mut scratch.values));
+    let mut entries = recycle(mem::take(&mut scratch.entries));
+    let mut deserializer = serde_json::Deserializer::from_str(input);
+    let result = ScratchSeed {
+        values: &mut values,
+        entries: &mut entries,
+    }
+    .deserialize(&mut deserializer)
+    .and_then(|value| deserializer.end().map(|()| value));
+    scratch.values = recycle(values);
+    scratch.entries = recycle(entries);
+    result.map_err(|err| ParseError::new(err, input))
+}
+
+/// Clears the `Vec` and changes its element type. The allocation is kept if the standard library
+/// collects in place, which it currently does for types of the same layout, like `Value`s of
+/// different lifetimes, but doesn't guarantee.
+fn recycle<T, U>(mut vec: Vec<T>) -> Vec<U> {
+    vec.clear();
+    vec.into_iter().map(|_| unreachable!()).collect()
+}
+
+/// Deserializes a `Value`, collecting the elements of arrays and objects on the stacks.
+struct ScratchSeed<'s, 'de> {
+    values: &'s mut Vec<Value<'de>>,
+    entries: &'s mut Vec<(Cow<'de, str>, Value<'de>)>,
+}
+
+impl<'de> ScratchSeed<'_, 'de> {
+    /// Returns the seed for the children of an array or object, pushing onto the same stacks.
+    #[inline]
+    fn child(&mut self) -> ScratchSeed<'_, 'de> {
+        ScratchSeed {
+            values: self.values,
+            entries: self.entries,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ScratchSeed<'_, 'de> {
+    type Value = Value<'de>;
+
+    #[inline]
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: serde::Deserializer<'de> {
+        deserializer.deserialize_any(ValueVisitor(self))
+    }
+}
+
+impl<'de> Collect<'de> for ScratchSeed<'_, 'de> {
+    #[inline]
+    fn collect_seq<A>(mut self, mut seq: A) -> Result<Value<'de>, A::Error>
+    where A: SeqAccess<'de> {
+        let start = self.values.len();
+        while let Some(elem) = seq.next_element_seed(self.child())? {
+            self.values.push(elem);
+        }
+        Ok(Value::Array(self.values.drain(start..).collect()))
+    }
+
+    #[inline]
+    fn collect_map<A>(
+        mut self,
+        first: Option<Cow<'de, str>>,
+        mut map: A,
+    ) -> Result<Value<'de>, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let start = self.entries.len();
+        let mut next = first;
+        while let Some(key) = next {
+            let value = map.next_value_seed(self.child())?;
+            self.entries.push((key, value));
+            next = map.next_key_seed(KeySeed)?;
+        }
+        Ok(Value::Object(self.entries.drain(start..).collect()))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn from_str_with_scratch_test() -> io::Result<()> {
+        let docs = [
+            r#"{"a": [1, [2, {"b": "c\n", "d": []}], {}], "e": {"f": [null, true]}, "g": -1.5}"#,
+            "[]",
+            r#""s""#,
+            r#"[{"x": 1}, {"x": 2, "y": [3, 4, 5]}]"#,
+            "18446744073709551616",
+        ];
+        let mut scratch = Scratch::with_capacity(4);
+        for doc in docs {
+            let value = from_str_with_scratch(doc, &mut scratch)?;
+            assert_eq!(value, serde_json::from_str::<Value>(doc)?);
+            assert!(scratch.values.is_empty() && scratch.entries.is_empty());
+        }
+
+        let value = from_str_with_scratch(docs[0], &mut scratch)?;
+        let arr = value.get("a").as_array().unwrap();
+        assert_eq!(arr.capacity(), 3);
+        assert_eq!(value.get("a").get(1).get(1).get("b").as_str(), Some("c\n"));
+
+        assert!(from_str_with_scratch(r#"{"a": [1, 2"#, &mut scratch).is_err());
+        assert!(from_str_with_scratch("[1] 2", &mut scratch).is_err());
+        assert!(scratch.values.is_empty());
+        Ok(())
+    }
+}
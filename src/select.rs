@@ -0,0 +1,175 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::Value;
+
+/// A single step of a query, see [`Value::select`].
+enum Selector<'q> {
+    Key(&'q str),
+    Index(usize),
+    Wildcard,
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Selects all values matching a query in a minimal subset of JSONPath, in document order.
+    ///
+    /// The query starts with `$`, which refers to `self`, followed by any number of selectors:
+    ///
+    /// - `.key` selects the value of `key` in an object. The key extends until the next `.` or `[`.
+    /// - `['key']` or `["key"]` selects the value of `key`, which may contain any character except
+    ///   the enclosing quote.
+    /// - `[n]` selects the element at index `n` of an array.
+    /// - `.*` and `[*]` select all elements of an array or all values of an object.
+    ///
+    /// Selectors which don't apply to a value, e.g. a key on an array, select nothing for that
+    /// value. If an object contains a key multiple times, the first entry is selected, like
+    /// [`get`](Value::get) does. Filters, slices, recursive descent and negative indices are not
+    /// supported; a query which is not valid in the supported syntax matches nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(
+    ///     r#"{"items": [{"id": 1, "tags": ["a"]}, {"id": 2, "tags": []}, {"name": "x"}]}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(data.select("$.items[*].id"), [&Value::Number(1u64.into()), &Value::Number(2u64.into())]);
+    /// assert_eq!(data.select("$.items[0].tags[0]"), [&Value::Str("a".into())]);
+    /// assert_eq!(data.select("$['items'][2].*"), [&Value::Str("x".into())]);
+    /// assert!(data.select("$.missing").is_empty());
+    /// ```
+    pub fn select<'a>(&'a self, query: &str) -> Vec<&'a Value<'ctx>> {
+        let Some(selectors) = parse_query(query) else {
+            return Vec::new();
+        };
+        let mut current = vec![self];
+        for selector in selectors {
+            let mut next = Vec::new();
+            for value in current {
+                match (&selector, value) {
+                    (Selector::Key(key), Value::Object(obj)) => {
+                        next.extend(obj.iter().find(|(k, _)| k == key).map(|(_, v)| v));
+                    }
+                    (Selector::Index(i), Value::Array(arr)) => next.extend(arr.get(*i)),
+                    (Selector::Wildcard, Value::Array(arr)) => next.extend(arr),
+                    (Selector::Wildcard, Value::Object(obj)) => {
+                        next.extend(obj.iter().map(|(_, v)| v))
+                    }
+                    _ => {}
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+/// Parses a query into its selectors, or returns `None` if it is invalid.
+fn parse_query(query: &str) -> Option<Vec<Selector<'_>>> {
+    let mut rest = query.strip_prefix('$')?;
+    let mut selectors = Vec::new();
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            let key = &after_dot[..end];
+            selectors.push(match key {
+                "" => return None,
+                "*" => Selector::Wildcard,
+                key => Selector::Key(key),
+            });
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let (selector, len) = match after_bracket.as_bytes().first()? {
+                quote @ (b'\'' | b'"') => {
+                    let quoted = &after_bracket[1..];
+                    let end = quoted.find(*quote as char)?;
+                    (Selector::Key(&quoted[..end]), end + 2)
+                }
+                _ => {
+                    let end = after_bracket.find(']')?;
+                    match &after_bracket[..end] {
+                        "*" => (Selector::Wildcard, end),
+                        index if index.bytes().all(|b| b.is_ascii_digit()) => {
+                            (Selector::Index(index.parse().ok()?), end)
+                        }
+                        _ => return None,
+                    }
+                }
+            };
+            rest = after_bracket[len..].strip_prefix(']')?;
+            selectors.push(selector);
+        } else {
+            return None;
+        }
+    }
+    Some(selectors)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn select(data: &Value, query: &str) -> Vec<String> {
+        data.select(query).iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn select_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(
+            r#"{
+                "store": {
+                    "books": [
+                        {"title": "A", "price": 8, "authors": ["x", "y"]},
+                        {"title": "B", "price": 12},
+                        {"title": "C", "authors": ["z"]}
+                    ],
+                    "bike": {"color": "red", "price": 20}
+                },
+                "a.b": 1,
+                "dup": 1,
+                "dup": 2
+            }"#,
+        )?;
+
+        assert_eq!(select(&data, "$").len(), 1);
+        assert_eq!(
+            select(&data, "$.store.books[*].title"),
+            [r#""A""#, r#""B""#, r#""C""#]
+        );
+        assert_eq!(select(&data, "$.store.books.*.price"), ["8", "12"]);
+        assert_eq!(
+            select(&data, "$.store.books[*].authors[*]"),
+            [r#""x""#, r#""y""#, r#""z""#]
+        );
+        assert_eq!(select(&data, "$.store.*.price"), ["20"]);
+        assert_eq!(select(&data, "$.store.bike.*"), [r#""red""#, "20"]);
+        assert_eq!(select(&data, "$['store'][\"bike\"].color"), [r#""red""#]);
+        assert_eq!(select(&data, "$['a.b']"), ["1"]);
+        assert_eq!(select(&data, "$.store.books[1].title"), [r#""B""#]);
+        assert_eq!(select(&data, "$.dup"), ["1"]);
+
+        assert!(select(&data, "$.store.books[3]").is_empty());
+        assert!(select(&data, "$.store.bike[0]").is_empty());
+        assert!(select(&data, "$.store.books.title").is_empty());
+        assert!(select(&data, "$.missing[*]").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_invalid_query_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [1, 2]}"#)?;
+        for query in [
+            "", "a", "$a", "$.", "$..a", "$.a[", "$.a[1", "$.a[-1]", "$.a[x]", "$['a]", "$.a[*",
+        ] {
+            assert!(data.select(query).is_empty(), "{}", query);
+        }
+
+        Ok(())
+    }
+}
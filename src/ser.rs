@@ -0,0 +1,388 @@
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "arbitrary_precision")]
+use alloc::string::ToString;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+
+use crate::num::{Number, N};
+use crate::write::{write_value, JsonFormat};
+use crate::Value;
+
+/// Serializes the `Value` through `serde`.
+///
+/// `serde` serializes nested values recursively, so extremely deep values which were built by hand
+/// may overflow the stack. Values parsed by `serde_json` are limited to a depth of 128.
+impl<'ctx> Serialize for Value<'ctx> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        match self {
+            Value::Null => serializer.serialize_unit(),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Number(n) => n.serialize(serializer),
+            Value::Str(s) => serializer.serialize_str(s),
+            // Emitted verbatim by `serde_json`. Invalid text is serialized as a string, like
+            // `parse_raw_lossy` does.
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => match serde_json::from_str::<&serde_json::value::RawValue>(text) {
+                Ok(raw) => raw.serialize(serializer),
+                Err(_) => serializer.serialize_str(text),
+            },
+            Value::Array(vec) => {
+                let mut seq = serializer.serialize_seq(Some(vec.len()))?;
+                for element in vec {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+            Value::Object(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Number {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        match self.n {
+            N::PosInt(n) => serializer.serialize_u64(n),
+            N::NegInt(n) => serializer.serialize_i64(n),
+            N::Float(n) => serializer.serialize_f64(n),
+            N::PosInt128(n) => serializer.serialize_u128(n),
+            N::NegInt128(n) => serializer.serialize_i128(n),
+            // Emitted verbatim by `serde_json`.
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => serde_json::value::Number::from_string_unchecked(s.to_string())
+                .serialize(serializer),
+        }
+    }
+}
+
+/// Adapts a `fmt::Formatter` to `io::Write`, so `serde_json` can serialize into it.
+#[cfg(feature = "std")]
+struct WriterFormatter<'a, 'b: 'a> {
+    inner: &'a mut fmt::Formatter<'b>,
+}
+
+#[cfg(feature = "std")]
+impl<'a, 'b> io::Write for WriterFormatter<'a, 'b> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The serializer only emits valid UTF-8.
+        let s = core::str::from_utf8(buf)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        self.inner.write_str(s).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Serializes `value` as compact JSON into the formatter.
+fn write_json<T: Serialize + ?Sized>(f: &mut fmt::Formatter, value: &T) -> fmt::Result {
+    #[cfg(feature = "std")]
+    {
+        let mut wr = WriterFormatter { inner: f };
+        serde_json::to_writer(&mut wr, value).map_err(|_| fmt::Error)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        // Without `std`, `serde_json` can only serialize into a buffer.
+        f.write_str(&serde_json::to_string(value).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Adapts an `io::Write` to `fmt::Write`, keeping the underlying error.
+#[cfg(feature = "std")]
+struct IoWriter<W> {
+    inner: W,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: io::Write> fmt::Write for IoWriter<W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Writes the `Value` like `serde_json` does, with raw values emitted verbatim.
+struct DisplayFormat {
+    pretty: bool,
+}
+
+impl JsonFormat for DisplayFormat {
+    fn pretty(&self) -> bool {
+        self.pretty
+    }
+
+    fn write_number<W: fmt::Write + ?Sized>(&self, w: &mut W, n: &Number) -> fmt::Result {
+        write!(w, "{}", n)
+    }
+
+    // Invalid text is written as a string, like `parse_raw_lossy` does.
+    #[cfg(feature = "raw_value")]
+    fn write_raw<W: fmt::Write + ?Sized>(&self, w: &mut W, text: &str) -> fmt::Result {
+        if serde_json::from_str::<&serde_json::value::RawValue>(text).is_ok() {
+            w.write_str(text)
+        } else {
+            crate::escape::write_escaped_str(w, text)
+        }
+    }
+}
+
+/// Writes the number as JSON, formatting floats the same way `serde_json` does.
+///
+/// Integers are written plainly, floats always contain a fraction or an exponent, e.g. `1.0` or
+/// `1e+100`, so they are read back as floats.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Number;
+/// #
+/// assert_eq!(Number::from(-12i64).to_string(), "-12");
+/// assert_eq!(Number::from(1.0).to_string(), "1.0");
+/// assert_eq!(Number::from(0.1).to_string(), "0.1");
+/// ```
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.n {
+            N::PosInt(n) => fmt::Display::fmt(&n, f),
+            N::NegInt(n) => fmt::Display::fmt(&n, f),
+            N::PosInt128(n) => fmt::Display::fmt(&n, f),
+            N::NegInt128(n) => fmt::Display::fmt(&n, f),
+            #[cfg(feature = "arbitrary_precision")]
+            N::Raw(ref s) => f.write_str(s),
+            N::Float(_) => write_json(f, self),
+        }
+    }
+}
+
+/// Writes the `Value` as compact JSON, or as pretty-printed JSON with the alternate flag `{:#}`.
+///
+/// Since `Value` implements `Display`, `value.to_string()` returns the compact JSON `String`. The
+/// output is the same as serializing with `serde_json`, but nested arrays and objects are written
+/// iteratively, so deeply nested values don't overflow the stack.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let value: Value = serde_json::from_str(r#"{ "a": [1, "b\nc"] }"#).unwrap();
+/// assert_eq!(value.to_string(), r#"{"a":[1,"b\nc"]}"#);
+/// assert_eq!(format!("{}", value.get("a")), r#"[1,"b\nc"]"#);
+/// ```
+impl<'ctx> fmt::Display for Value<'ctx> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let format = DisplayFormat {
+            pretty: f.alternate(),
+        };
+        write_value(f, self, &format)
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Serializes the `Value` into a pretty-printed JSON `String`, indented with two spaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"{"a":[1]}"#).unwrap();
+    /// assert_eq!(value.to_string_pretty(), "{\n  \"a\": [\n    1\n  ]\n}");
+    /// ```
+    pub fn to_string_pretty(&self) -> String {
+        format!("{:#}", self)
+    }
+
+    /// Serializes the `Value` as compact JSON directly into `writer`, without building an
+    /// intermediate `String`.
+    ///
+    /// The writer is not buffered, so wrap unbuffered writers like files or sockets in an
+    /// `io::BufWriter`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"{ "a": [1, "b"] }"#).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// value.to_writer(&mut out).unwrap();
+    /// assert_eq!(out, br#"{"a":[1,"b"]}"#);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_writer<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        write_io(writer, self, false)
+    }
+
+    /// Serializes the `Value` as pretty-printed JSON directly into `writer`, indented with two
+    /// spaces like [`to_string_pretty`](Value::to_string_pretty).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"{"a":[1]}"#).unwrap();
+    ///
+    /// let mut out = Vec::new();
+    /// value.to_writer_pretty(&mut out).unwrap();
+    /// assert_eq!(out, b"{\n  \"a\": [\n    1\n  ]\n}");
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_writer_pretty<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        write_io(writer, self, true)
+    }
+}
+
+/// Writes `value` like `Display` does into `writer`.
+#[cfg(feature = "std")]
+fn write_io<W: io::Write>(writer: W, value: &Value, pretty: bool) -> io::Result<()> {
+    let mut wr = IoWriter {
+        inner: writer,
+        error: None,
+    };
+    write_value(&mut wr, value, &DisplayFormat { pretty }).map_err(|_| {
+        wr.error
+            .take()
+            .unwrap_or_else(|| io::Error::other("formatting failed"))
+    })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Number, Value};
+
+    #[test]
+    fn display_number_test() {
+        assert_eq!(Number::from(0u64).to_string(), "0");
+        assert_eq!(Number::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Number::from(i64::MIN).to_string(), "-9223372036854775808");
+        assert_eq!(Number::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Number::from(i128::MIN).to_string(), i128::MIN.to_string());
+        assert_eq!(Number::from(1.0).to_string(), "1.0");
+        assert_eq!(Number::from(-0.0).to_string(), "-0.0");
+        assert_eq!(Number::from(1.5e300).to_string(), "1.5e+300");
+        assert_eq!(Number::from(1e-7).to_string(), "1e-7");
+        assert_eq!(format!("[{}]", Number::from(2.5)), "[2.5]");
+
+        let value: Value = serde_json::from_str(r#"[1, -2, 3.25, 1.0]"#).unwrap();
+        let numbers: Vec<String> = value
+            .iter_array()
+            .unwrap()
+            .map(|v| match v {
+                Value::Number(n) => n.to_string(),
+                _ => unreachable!(),
+            })
+            .collect();
+        assert_eq!(numbers, ["1", "-2", "3.25", "1.0"]);
+    }
+
+    #[test]
+    fn serialize_roundtrip_test() {
+        let json_obj = r#"{"bool":true,"null":null,"str":"a\"b\n","float":1.23,"i64":-123,"u64":18446744073709551615,"arr":[1,{"k":[]}],"obj":{}}"#;
+
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+        assert_eq!(serde_json::to_string(&val).unwrap(), json_obj);
+    }
+
+    #[test]
+    fn serialize_matches_serde_json_test() {
+        let json_obj = r#"
+            {
+                "exp": 1e100,
+                "float": 1.0,
+                "neg": -0.5,
+                "nested": {"a": [true, false, null]}
+            }
+       "#;
+
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+        let owned: serde_json::Value = serde_json::from_str(json_obj).unwrap();
+        assert_eq!(
+            serde_json::to_string(&val).unwrap(),
+            serde_json::to_string(&owned).unwrap()
+        );
+    }
+
+    #[test]
+    fn to_writer_test() {
+        let val: Value = serde_json::from_str(r#"{"a": [1, "x\n"], "b": {}}"#).unwrap();
+        let mut out = Vec::new();
+        val.to_writer(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), val.to_string());
+        let mut out = Vec::new();
+        val.to_writer_pretty(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), val.to_string_pretty());
+
+        struct Failing;
+        impl std::io::Write for Failing {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("closed"))
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let err = val.to_writer(Failing).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    }
+
+    #[test]
+    fn serialize_keeps_duplicate_keys_test() {
+        let val: Value = serde_json::from_str(r#"{"a":1,"a":2}"#).unwrap();
+        assert_eq!(serde_json::to_string(&val).unwrap(), r#"{"a":1,"a":2}"#);
+    }
+
+    #[test]
+    fn display_escapes_strings_test() {
+        let json_obj = r#"{"k\"ey": ["new\nline", "tab\t", "quote\"", "back\\slash", "\u0001", "\u00e9\ud83d\ude00"]}"#;
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+
+        let out = format!("{}", val);
+        assert_eq!(
+            out,
+            r#"{"k\"ey":["new\nline","tab\t","quote\"","back\\slash","\u0001","é😀"]}"#
+        );
+        let reparsed: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(reparsed, val);
+    }
+
+    #[test]
+    fn display_alternate_is_pretty_test() {
+        let val: Value = serde_json::from_str(r#"{"a": [1]}"#).unwrap();
+        assert_eq!(format!("{:#}", val), val.to_string_pretty());
+    }
+
+    #[test]
+    fn to_string_test() {
+        let json_obj = r#"{"a": {"b": [1, -2.5, "c"]}, "d": null}"#;
+        let val: Value = serde_json::from_str(json_obj).unwrap();
+        let owned: serde_json::Value = serde_json::from_str(json_obj).unwrap();
+
+        assert_eq!(val.to_string(), serde_json::to_string(&owned).unwrap());
+        assert_eq!(
+            val.to_string_pretty(),
+            serde_json::to_string_pretty(&owned).unwrap()
+        );
+    }
+}
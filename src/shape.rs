@@ -0,0 +1,282 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::pointer::escape_token;
+use crate::Value;
+
+/// A lightweight description of the expected structure of a document, checked with
+/// [`Value::matches_shape`].
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::Shape;
+///
+/// let user = Shape::object([
+///     ("id", Shape::Number),
+///     ("name", Shape::String),
+///     ("tags", Shape::array(Shape::String)),
+///     ("email", Shape::optional(Shape::String)),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Shape {
+    /// Any value.
+    Any,
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool,
+    /// Any number.
+    Number,
+    /// Any string.
+    String,
+    /// An array whose elements all match the shape.
+    Array(Box<Shape>),
+    /// An object which has all of the listed fields, with values matching their shapes. Other
+    /// fields are allowed. For duplicate keys the first entry is checked, like with
+    /// [`Value::get`].
+    Object(Vec<(String, Shape)>),
+    /// Either the shape, `null`, or a missing field in an object.
+    Optional(Box<Shape>),
+}
+
+impl Shape {
+    /// Creates a [`Shape::Array`] of elements matching `element`.
+    pub fn array(element: Shape) -> Self {
+        Shape::Array(Box::new(element))
+    }
+
+    /// Creates a [`Shape::Object`] with the required `fields`.
+    pub fn object<K: Into<String>>(fields: impl IntoIterator<Item = (K, Shape)>) -> Self {
+        Shape::Object(
+            fields
+                .into_iter()
+                .map(|(key, shape)| (key.into(), shape))
+                .collect(),
+        )
+    }
+
+    /// Creates a [`Shape::Optional`], which also accepts `null` and missing fields.
+    pub fn optional(shape: Shape) -> Self {
+        Shape::Optional(Box::new(shape))
+    }
+
+    /// The name of the expected type, for error messages.
+    fn expected(&self) -> &'static str {
+        match self {
+            Shape::Any => "any value",
+            Shape::Null => "null",
+            Shape::Bool => "bool",
+            Shape::Number => "number",
+            Shape::String => "string",
+            Shape::Array(_) => "array",
+            Shape::Object(_) => "object",
+            Shape::Optional(shape) => shape.expected(),
+        }
+    }
+}
+
+/// Error returned by [`Value::matches_shape`] for the first part of the document which doesn't
+/// match the expected [`Shape`].
+///
+/// Paths are JSON Pointers, see [`Value::pointer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// A required field of an object is missing.
+    MissingField {
+        /// Location of the missing field.
+        path: String,
+    },
+    /// A value has a different type than expected.
+    WrongType {
+        /// Location of the value.
+        path: String,
+        /// The expected type, e.g. `"string"`.
+        expected: &'static str,
+        /// The actual type of the value, e.g. `"number"`.
+        found: &'static str,
+    },
+}
+
+impl ShapeError {
+    /// Returns the JSON Pointer of the value which doesn't match.
+    pub fn path(&self) -> &str {
+        match self {
+            ShapeError::MissingField { path } | ShapeError::WrongType { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShapeError::MissingField { path } => write!(f, "missing field `{}`", path),
+            ShapeError::WrongType {
+                path,
+                expected,
+                found,
+            } => write!(f, "expected {} at `{}`, found {}", expected, path, found),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ShapeError {}
+
+impl<'ctx> Value<'ctx> {
+    /// Checks that the document has the structure described by `shape`.
+    ///
+    /// Returns the first mismatch in the order of the shape's fields, with the JSON Pointer of the
+    /// offending value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json_borrow::{Shape, ShapeError, Value};
+    ///
+    /// let shape = Shape::object([
+    ///     ("id", Shape::Number),
+    ///     ("items", Shape::array(Shape::object([("name", Shape::String)]))),
+    /// ]);
+    ///
+    /// let valid: Value = serde_json::from_str(r#"{"id": 1, "items": [{"name": "a"}]}"#).unwrap();
+    /// assert_eq!(valid.matches_shape(&shape), Ok(()));
+    ///
+    /// let invalid: Value = serde_json::from_str(r#"{"id": 1, "items": [{"name": 2}]}"#).unwrap();
+    /// let err = invalid.matches_shape(&shape).unwrap_err();
+    /// assert_eq!(err.path(), "/items/0/name");
+    /// assert_eq!(err.to_string(), "expected string at `/items/0/name`, found number");
+    /// ```
+    pub fn matches_shape(&self, shape: &Shape) -> Result<(), ShapeError> {
+        check_shape(self, shape, &mut String::new())
+    }
+}
+
+fn check_shape(value: &Value<'_>, shape: &Shape, path: &mut String) -> Result<(), ShapeError> {
+    let len = path.len();
+    match (shape, value) {
+        (Shape::Any, _)
+        | (Shape::Null, Value::Null)
+        | (Shape::Bool, Value::Bool(_))
+        | (Shape::Number, Value::Number(_))
+        | (Shape::String, Value::Str(_))
+        | (Shape::Optional(_), Value::Null) => Ok(()),
+        (Shape::Optional(shape), _) => check_shape(value, shape, path),
+        (Shape::Array(element), Value::Array(arr)) => {
+            for (i, val) in arr.iter().enumerate() {
+                path.push('/');
+                path.push_str(&i.to_string());
+                check_shape(val, element, path)?;
+                path.truncate(len);
+            }
+            Ok(())
+        }
+        (Shape::Object(fields), Value::Object(obj)) => {
+            for (key, field) in fields {
+                path.push('/');
+                path.push_str(&escape_token(key));
+                match obj.iter().find(|(k, _)| k == key) {
+                    Some((_, val)) => check_shape(val, field, path)?,
+                    None if matches!(field, Shape::Optional(_)) => {}
+                    None => {
+                        return Err(ShapeError::MissingField { path: path.clone() });
+                    }
+                }
+                path.truncate(len);
+            }
+            Ok(())
+        }
+        _ => Err(ShapeError::WrongType {
+            path: path.clone(),
+            expected: shape.expected(),
+            found: value.type_name(),
+        }),
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn check(input: &str, shape: &Shape) -> io::Result<Result<(), ShapeError>> {
+        let value: Value = serde_json::from_str(input)?;
+        Ok(value.matches_shape(shape))
+    }
+
+    #[test]
+    fn matches_shape_scalars_test() -> io::Result<()> {
+        let cases = [
+            ("null", Shape::Null),
+            ("true", Shape::Bool),
+            ("1.5", Shape::Number),
+            (r#""s""#, Shape::String),
+            ("[]", Shape::array(Shape::Null)),
+            ("{}", Shape::object::<&str>([])),
+            (r#"{"a": [1]}"#, Shape::Any),
+        ];
+        for (input, shape) in &cases {
+            assert_eq!(check(input, shape)?, Ok(()), "{}", input);
+        }
+        assert_eq!(
+            check("1", &Shape::String)?,
+            Err(ShapeError::WrongType {
+                path: String::new(),
+                expected: "string",
+                found: "number",
+            })
+        );
+        assert!(check("null", &Shape::optional(Shape::Bool))?.is_ok());
+        assert!(check("false", &Shape::optional(Shape::Bool))?.is_ok());
+        let err = check("0", &Shape::optional(Shape::Bool))?.unwrap_err();
+        assert_eq!(err.to_string(), "expected bool at ``, found number");
+        Ok(())
+    }
+
+    #[test]
+    fn matches_shape_nested_test() -> io::Result<()> {
+        let shape = Shape::object([
+            ("id", Shape::Number),
+            ("a/b", Shape::array(Shape::array(Shape::Bool))),
+            ("note", Shape::optional(Shape::String)),
+            ("extra", Shape::Any),
+        ]);
+        assert!(check(
+            r#"{"extra": null, "a/b": [[true], []], "id": 1, "other": "x"}"#,
+            &shape
+        )?
+        .is_ok());
+        assert!(check(r#"{"extra": 1, "a/b": [], "id": 1, "note": "n"}"#, &shape)?.is_ok());
+
+        let err = check(
+            r#"{"id": 1, "a/b": [[true], [false, 1]], "extra": 0}"#,
+            &shape,
+        )?
+        .unwrap_err();
+        assert_eq!(err.path(), "/a~1b/1/1");
+        assert_eq!(
+            err.to_string(),
+            "expected bool at `/a~1b/1/1`, found number"
+        );
+
+        let err = check(r#"{"id": 1, "a/b": []}"#, &shape)?.unwrap_err();
+        assert_eq!(
+            err,
+            ShapeError::MissingField {
+                path: "/extra".to_string()
+            }
+        );
+        assert_eq!(err.to_string(), "missing field `/extra`");
+
+        // The first mismatch in the order of the shape's fields is reported.
+        let err = check(r#"{"id": "1", "a/b": 1, "note": 2}"#, &shape)?.unwrap_err();
+        assert_eq!(err.path(), "/id");
+        // Duplicate keys: the first entry is checked.
+        assert!(check(r#"{"id": 1, "id": "x", "a/b": [], "extra": 1}"#, &shape)?.is_ok());
+        Ok(())
+    }
+}
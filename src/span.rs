@@ -0,0 +1,264 @@
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::pointer::{parse_index, tokens};
+use crate::{ParseError, Value};
+
+/// The byte offsets of a parsed value and of its children in the input, as returned by
+/// [`from_str_spanned`].
+///
+/// `Spans` form a tree parallel to the `Value`: the children of an array or object are in the same
+/// order as its elements or entries, including duplicate keys, so the spans of a child are found
+/// at the same position as the child itself. Scalars have no children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spans {
+    span: Range<usize>,
+    key_span: Option<Range<usize>>,
+    children: Vec<Spans>,
+}
+
+impl Spans {
+    /// Returns the byte range of the value in the input, from its first to its last character.
+    /// Strings include their quotes, arrays and objects their brackets.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Returns the byte range of the key including its quotes, if the value is the value of an
+    /// object entry.
+    pub fn key_span(&self) -> Option<Range<usize>> {
+        self.key_span.clone()
+    }
+
+    /// Returns the spans of the elements of an array or the entries of an object, in order. Empty
+    /// for scalars.
+    pub fn children(&self) -> &[Spans] {
+        &self.children
+    }
+
+    /// Returns the spans of the value at the JSON Pointer in `value`, which must be the `Value`
+    /// these spans were returned with.
+    ///
+    /// The pointer is resolved like [`Value::pointer`], so for a duplicate key the first entry is
+    /// used. Returns None if the pointer does not resolve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json_borrow::from_str_spanned;
+    ///
+    /// let input = r#"{"a": [1, "x"]}"#;
+    /// let (value, spans) = from_str_spanned(input).unwrap();
+    ///
+    /// let a = spans.pointer(&value, "/a").unwrap();
+    /// assert_eq!(&input[a.span()], r#"[1, "x"]"#);
+    /// assert_eq!(&input[a.key_span().unwrap()], r#""a""#);
+    /// assert_eq!(spans.pointer(&value, "/a/1").unwrap().span(), 10..13);
+    /// assert!(spans.pointer(&value, "/b").is_none());
+    /// ```
+    pub fn pointer(&self, value: &Value<'_>, pointer: &str) -> Option<&Spans> {
+        let mut spans = self;
+        let mut value = value;
+        for token in tokens(pointer)? {
+            let (pos, child) = match value {
+                Value::Array(arr) => {
+                    let pos = parse_index(&token)?;
+                    (pos, arr.get(pos)?)
+                }
+                Value::Object(obj) => {
+                    let pos = obj.iter().position(|(key, _)| *key == token)?;
+                    (pos, &obj[pos].1)
+                }
+                _ => return None,
+            };
+            spans = spans.children.get(pos)?;
+            value = child;
+        }
+        Some(spans)
+    }
+}
+
+/// Parses a `Value` from a `&str` like [`from_str`](crate::from_str), and additionally returns
+/// the byte offsets of every value in the input, e.g. to highlight values in an editor or linter.
+///
+/// The spans are recorded in a separate tree, see [`Spans`], so the `Value` is the same as for a
+/// regular parse. After parsing, the input is scanned a second time to record the spans, which
+/// roughly doubles the cost of parsing and allocates one `Spans` per value, so only use this
+/// function when the spans are needed.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_str_spanned;
+///
+/// let input = r#"{"name": "a", "tags": ["x", "y"]}"#;
+/// let (value, spans) = from_str_spanned(input).unwrap();
+///
+/// assert_eq!(spans.span(), 0..input.len());
+/// let tags = &spans.children()[1];
+/// assert_eq!(&input[tags.key_span().unwrap()], r#""tags""#);
+/// assert_eq!(&input[tags.children()[1].span()], r#""y""#);
+/// assert_eq!(value.get("tags").get(1), "y");
+/// ```
+pub fn from_str_spanned(input: &str) -> Result<(Value<'_>, Spans), ParseError> {
+    let value = crate::from_str(input)?;
+    let mut scanner = Scanner {
+        bytes: input.as_bytes(),
+        pos: 0,
+    };
+    let spans = scanner.scan_value(None);
+    Ok((value, spans))
+}
+
+/// Finds the spans of values in JSON which is known to be valid.
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips whitespace and the given separator, if present.
+    fn skip_separator(&mut self, separator: u8) {
+        self.skip_whitespace();
+        if self.peek() == Some(separator) {
+            self.pos += 1;
+        }
+    }
+
+    fn scan_value(&mut self, key_span: Option<Range<usize>>) -> Spans {
+        self.skip_whitespace();
+        let start = self.pos;
+        let mut children = Vec::new();
+        match self.peek() {
+            Some(b'[') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    if matches!(self.peek(), Some(b']') | None) {
+                        break;
+                    }
+                    children.push(self.scan_value(None));
+                    self.skip_separator(b',');
+                }
+                self.pos += 1;
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                loop {
+                    self.skip_whitespace();
+                    if matches!(self.peek(), Some(b'}') | None) {
+                        break;
+                    }
+                    let key_start = self.pos;
+                    self.scan_string();
+                    let key_span = key_start..self.pos;
+                    self.skip_separator(b':');
+                    children.push(self.scan_value(Some(key_span)));
+                    self.skip_separator(b',');
+                }
+                self.pos += 1;
+            }
+            Some(b'"') => self.scan_string(),
+            _ => {
+                while !matches!(
+                    self.peek(),
+                    None | Some(b' ' | b'\t' | b'\n' | b'\r' | b',' | b']' | b'}')
+                ) {
+                    self.pos += 1;
+                }
+            }
+        }
+        Spans {
+            span: start..self.pos,
+            key_span,
+            children,
+        }
+    }
+
+    /// Skips a string including its quotes.
+    fn scan_string(&mut self) {
+        self.pos += 1;
+        while let Some(byte) = self.peek() {
+            self.pos += if byte == b'\\' { 2 } else { 1 };
+            if byte == b'"' {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    /// Checks that the text of each span parses to the value it belongs to.
+    fn check_spans(input: &str, value: &Value, spans: &Spans) {
+        let text = &input[spans.span()];
+        assert_eq!(&serde_json::from_str::<Value>(text).unwrap(), value);
+        let children: Vec<&Value> = match value {
+            Value::Array(arr) => arr.iter().collect(),
+            Value::Object(obj) => {
+                for ((key, _), child) in obj.iter().zip(spans.children()) {
+                    let key_text = &input[child.key_span().unwrap()];
+                    assert_eq!(serde_json::from_str::<String>(key_text).unwrap(), *key);
+                }
+                obj.iter().map(|(_, val)| val).collect()
+            }
+            _ => Vec::new(),
+        };
+        assert_eq!(children.len(), spans.children().len(), "{}", text);
+        for (child, child_spans) in children.into_iter().zip(spans.children()) {
+            check_spans(input, child, child_spans);
+        }
+    }
+
+    #[test]
+    fn from_str_spanned_test() -> io::Result<()> {
+        let inputs = [
+            "  1.5e3 ",
+            r#""s\"q""#,
+            "[]",
+            " { } ",
+            "[null,true,false,-0,[[]],{}]",
+            "{\n  \"a\\\"b\": [1, \"x\\\\\", {\"c\" : null}],\r\n\t\"a\\\"b\": \
+             \"\u{e9}\\u00e9\"\n}",
+            r#"{"k": {"k": {"k": [1, 2, {"": ""}]}}, "k": 2}"#,
+        ];
+        for input in inputs {
+            let (value, spans) = from_str_spanned(input).unwrap();
+            assert!(spans.key_span().is_none());
+            check_spans(input, &value, &spans);
+        }
+
+        assert!(from_str_spanned("[1, 2").is_err());
+        assert!(from_str_spanned("[1] x").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn spans_pointer_test() -> io::Result<()> {
+        let input = r#"{"a": [0, {"b/c": true}], "d": 1, "d": 2}"#;
+        let (value, spans) = from_str_spanned(input).unwrap();
+        let text = |pointer| spans.pointer(&value, pointer).map(|s| &input[s.span()]);
+        assert_eq!(text(""), Some(input));
+        assert_eq!(text("/a/1/b~1c"), Some("true"));
+        assert_eq!(text("/d"), Some("1"));
+        assert_eq!(text("/a/2"), None);
+        assert_eq!(text("/a/01"), None);
+        assert_eq!(text("/d/0"), None);
+        assert_eq!(text("a"), None);
+        Ok(())
+    }
+}
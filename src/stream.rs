@@ -0,0 +1,267 @@
+use alloc::vec::Vec;
+
+use serde::de::{Error as _, IgnoredAny};
+
+use crate::{ParseError, Value};
+
+/// Lazily parses the elements of a top-level JSON array, borrowing from the input.
+///
+/// Elements are parsed one at a time as the iterator advances, so only a single element needs to
+/// be held in memory. Whitespace around elements is skipped. Trailing commas are not valid JSON
+/// and are reported as errors, like any other malformed input.
+///
+/// After the first error the iterator yields `None`. Errors report the line and column within the
+/// whole input.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::stream_array;
+///
+/// let input = r#"[{"id": 1}, {"id": 2}, {"id": }]"#;
+/// let mut records = stream_array(input);
+/// assert_eq!(records.next().unwrap().unwrap().get("id"), 1);
+/// assert_eq!(records.next().unwrap().unwrap().get("id"), 2);
+/// assert!(records.next().unwrap().is_err());
+/// assert!(records.next().is_none());
+/// ```
+pub fn stream_array(input: &str) -> impl Iterator<Item = Result<Value<'_>, ParseError>> {
+    ArrayStream {
+        input,
+        pos: 0,
+        state: State::Start,
+    }
+}
+
+enum State {
+    /// Before the opening bracket.
+    Start,
+    /// After an element.
+    Next,
+    /// After the closing bracket or an error.
+    Done,
+}
+
+struct ArrayStream<'ctx> {
+    input: &'ctx str,
+    pos: usize,
+    state: State,
+}
+
+impl<'ctx> ArrayStream<'ctx> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    /// Ends the iteration with an error.
+    fn fail(&mut self, fallback: &str) -> Option<Result<Value<'ctx>, ParseError>> {
+        self.state = State::Done;
+        let err = locate_error(self.input, fallback);
+        Some(Err(ParseError::new(err, self.input)))
+    }
+
+    /// Ends the iteration after the closing bracket, which must only be followed by whitespace.
+    fn finish(&mut self) -> Option<Result<Value<'ctx>, ParseError>> {
+        self.pos += 1;
+        self.skip_whitespace();
+        if self.pos != self.input.len() {
+            return self.fail("trailing characters");
+        }
+        self.state = State::Done;
+        None
+    }
+}
+
+impl<'ctx> Iterator for ArrayStream<'ctx> {
+    type Item = Result<Value<'ctx>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.state {
+            State::Done => return None,
+            State::Start => {
+                self.skip_whitespace();
+                if self.peek() != Some(b'[') {
+                    return self.fail("expected a JSON array");
+                }
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek() == Some(b']') {
+                    return self.finish();
+                }
+            }
+            State::Next => {
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(b',') => self.pos += 1,
+                    Some(b']') => return self.finish(),
+                    _ => return self.fail("expected `,` or `]`"),
+                }
+            }
+        }
+        let mut elements =
+            serde_json::Deserializer::from_str(&self.input[self.pos..]).into_iter::<Value>();
+        match elements.next() {
+            Some(Ok(value)) => {
+                self.pos += elements.byte_offset();
+                self.state = State::Next;
+                Some(Ok(value))
+            }
+            _ => self.fail("expected a value"),
+        }
+    }
+}
+
+/// Returns the first error in `input` when parsed as a JSON array, so that its line and column
+/// refer to the whole input rather than to a single element.
+///
+/// This re-parses the input up to the error without allocating values, which only happens once
+/// per stream.
+fn locate_error(input: &str, fallback: &str) -> serde_json::Error {
+    match serde_json::from_str::<Vec<IgnoredAny>>(input) {
+        Err(err) => err,
+        Ok(_) => serde_json::Error::custom(fallback),
+    }
+}
+
+/// Parses newline-delimited JSON (also known as JSON Lines), yielding one value per line.
+///
+/// Every line is parsed as an independent JSON value that borrows from its slice of the input.
+/// Blank lines, including lines consisting only of whitespace, are skipped, and `\r\n` line
+/// endings are supported. An invalid line yields an error, with the line number counted within the
+/// whole input, but doesn't stop the iteration, so callers can skip malformed records. The
+/// [`ParseError`] also resolves the byte offset of the error within the whole input.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::from_ndjson;
+///
+/// let input = "{\"id\": 1}\n\n{\"id\": 2}\n{\"id\"}\n";
+/// let records: Vec<_> = from_ndjson(input).collect();
+/// assert_eq!(records.len(), 3);
+/// assert_eq!(records[1].as_ref().unwrap().get("id"), 2);
+/// assert_eq!(records[2].as_ref().unwrap_err().line(), 4);
+/// ```
+pub fn from_ndjson(input: &str) -> impl Iterator<Item = Result<Value<'_>, ParseError>> {
+    let mut line_start = 0;
+    input
+        .split('\n')
+        .enumerate()
+        .map(move |(index, line)| {
+            let start = line_start;
+            line_start += line.len() + 1;
+            (index, start, line)
+        })
+        .filter(|(_, _, line)| !line.trim().is_empty())
+        .map(move |(index, start, line)| {
+            serde_json::from_str(line)
+                .map_err(|err| ParseError::within(err, input.as_bytes(), start, index))
+        })
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn collect(input: &str) -> Vec<Result<String, String>> {
+        stream_array(input)
+            .map(|res| {
+                res.map(|v| v.to_string())
+                    .map_err(|e| e.inner().to_string())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stream_array_test() {
+        let input = " [ 1 ,\"a\\\"b\", {\"k\": [null]},[], true\n]\n ";
+        let values: Vec<Value> = stream_array(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            Value::Array(values).to_string(),
+            r#"[1,"a\"b",{"k":[null]},[],true]"#
+        );
+
+        assert!(collect("[]").is_empty());
+        assert!(collect(" [\n] ").is_empty());
+        assert_eq!(collect(r#"["x"]"#), [Ok(r#""x""#.to_string())]);
+    }
+
+    #[test]
+    fn stream_array_borrows_test() {
+        let input = String::from(r#"["a", "b"]"#);
+        let first = stream_array(&input).next().unwrap().unwrap();
+        assert!(first.is_borrowed());
+        assert_eq!(first, "a");
+    }
+
+    #[test]
+    fn stream_array_errors_test() {
+        let res = collect("[1,\n2,\n]");
+        assert_eq!(res.len(), 3);
+        assert!(res[0].is_ok() && res[1].is_ok());
+        assert_eq!(res[2], Err("trailing comma at line 3 column 1".to_string()));
+        let err = stream_array("[1,\n2,\n]").nth(2).unwrap().unwrap_err();
+        assert_eq!((err.offset(), err.snippet()), (Some(7), "]"));
+
+        let res = collect("[1 2]");
+        assert_eq!(
+            res,
+            [
+                Ok("1".to_string()),
+                Err("expected `,` or `]` at line 1 column 4".to_string())
+            ]
+        );
+
+        let res = collect("[1, {\"a\": tru}]");
+        assert_eq!(
+            res[1],
+            Err("expected ident at line 1 column 14".to_string())
+        );
+
+        assert_eq!(
+            collect("[1] x")[1],
+            Err("trailing characters at line 1 column 5".to_string())
+        );
+        assert!(collect("{}")[0].is_err());
+        assert!(collect("")[0].is_err());
+        assert!(collect("[1,").last().unwrap().is_err());
+        assert!(collect("[1").last().unwrap().is_err());
+    }
+
+    #[test]
+    fn from_ndjson_test() {
+        let input = "{\"a\": \"x\"}\r\n\n  \n[1, 2]\n\"s\"";
+        let values: Vec<Value> = from_ndjson(input).collect::<Result<_, _>>().unwrap();
+        assert_eq!(Value::Array(values).to_string(), r#"[{"a":"x"},[1,2],"s"]"#);
+
+        let line = String::from("{\"key\": \"val\"}\n");
+        let value = from_ndjson(&line).next().unwrap().unwrap();
+        assert!(value.is_borrowed());
+
+        assert_eq!(from_ndjson("").count(), 0);
+        assert_eq!(from_ndjson("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn from_ndjson_errors_test() {
+        let res: Vec<_> = from_ndjson("1\n\n{\"a\": }\n2 3\n4").collect();
+        assert_eq!(res.len(), 4);
+        let err = res[1].as_ref().unwrap_err();
+        assert_eq!((err.line(), err.column()), (3, 7));
+        assert_eq!(err.offset(), Some(9));
+        assert_eq!(err.snippet(), "{\"a\": }");
+        assert_eq!(
+            err.to_string(),
+            "expected value at line 3 column 7, near `{\"a\": }`"
+        );
+        let err = res[2].as_ref().unwrap_err();
+        assert_eq!((err.line(), err.column()), (4, 3));
+        assert_eq!(err.offset(), Some(13));
+        assert_eq!(res[3].as_ref().unwrap(), &Value::Number(4u64.into()));
+    }
+}
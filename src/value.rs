@@ -1,12 +1,48 @@
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
 use core::fmt;
-use core::hash::{Hash, Hasher};
-use std::borrow::Cow;
-use std::fmt::Debug;
+use core::fmt::Debug;
 
-use crate::index::Index;
+use crate::index::{GetError, Index, PathSegment};
+use crate::num::Number;
+use crate::ParseError;
+
+/// Returned by lookups which don't find a value.
+pub(crate) static NULL: Value<'static> = Value::Null;
+
+/// Parses the text of a [`Value::Raw`], falling back to a string holding the text if it is not
+/// valid JSON.
+///
+/// Only used by conversions which can't fail, each of which documents this fallback.
+#[cfg(feature = "raw_value")]
+pub(crate) fn parse_raw_lossy(text: &str) -> Value<'_> {
+    serde_json::from_str(text).unwrap_or(Value::Str(Cow::Borrowed(text)))
+}
+
+/// Converts the text of a [`Value::Raw`] into an owned value, see [`parse_raw_lossy`].
+#[cfg(feature = "raw_value")]
+fn raw_to_owned(text: &str) -> Value<'static> {
+    parse_raw_lossy(text).into_owned()
+}
 
 /// Represents any valid JSON value.
 ///
+/// Objects keep the order of their entries, so equality, ordering and hashing of objects depend on
+/// key order: `{"a":1,"b":2}` and `{"b":2,"a":1}` are different values with (most likely)
+/// different hashes.
+///
+/// # Deeply nested values
+///
+/// Parsing with `serde_json` limits the nesting depth to 128, but values built by hand can be
+/// nested much deeper. Writing them as JSON with `Display`, [`write_to`](Value::write_to) or
+/// [`to_canonical_string`](Value::to_canonical_string), formatting them with `Debug`, and
+/// converting them into a `serde_json::Value` work iteratively. Dropping, cloning, comparing,
+/// hashing, [`into_owned`](Value::into_owned) and serializing through `serde` recurse into nested
+/// values, so values nested tens of thousands of levels deep may overflow the stack there.
+///
 /// # Example
 /// ```
 /// use std::io;
@@ -19,7 +55,7 @@ use crate::index::Index;
 ///     Ok(())
 /// }
 /// ```
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Hash)]
 pub enum Value<'ctx> {
     /// Represents a JSON null value.
     ///
@@ -65,15 +101,113 @@ pub enum Value<'ctx> {
     /// By default the map is backed by a Vec. Allows very fast deserialization.
     /// Ideal when wanting to iterate over the values, in contrast to look up by key.
     ///
+    /// Keys are borrowed from the input, unless they contain JSON escape sequences.
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let v = Value::Object([("key".into(), Value::Str("value".into()))].into_iter().collect());
+    /// ```
+    Object(Vec<(Cow<'ctx, str>, Value<'ctx>)>),
+
+    /// Represents an unparsed JSON value, holding its verbatim text. Requires the `raw_value`
+    /// feature.
+    ///
+    /// Raw values are produced by [`from_str_lazy`](crate::from_str_lazy) and
+    /// [`deserialize_raw`](crate::deserialize_raw), to skip building the DOM of subtrees which are
+    /// only passed through. They are serialized verbatim, and can be parsed on demand with
+    /// [`parse`](Value::parse). Until then they are opaque: accessors like `get` or `is_object`
+    /// treat them like a scalar.
+    ///
+    /// Raw values produced by this crate are always valid JSON. For a hand-built `Raw` with
+    /// invalid text, only [`parse`](Value::parse) fails. Everything else silently treats the
+    /// text as a string instead, including serializing, `Display`,
+    /// [`to_string_pretty`](Value::to_string_pretty), [`into_owned`](Value::into_owned),
+    /// [`to_owned_value`](Value::to_owned_value), [`is_truthy`](Value::is_truthy),
+    /// [`write_to`](Value::write_to), [`to_canonical_string`](Value::to_canonical_string) and the
+    /// conversion into `serde_json::Value`.
+    ///
     /// ```
     /// # use serde_json_borrow::Value;
     /// #
-    /// let v = Value::Object([("key", Value::Str("value".into()))].into_iter().collect());
+    /// let v = Value::Raw(r#"{"key": [1, 2]}"#);
+    /// assert_eq!(v.parse().unwrap().get("key").get(1), 2);
     /// ```
-    Object(Vec<(&'ctx str, Value<'ctx>)>),
+    #[cfg(feature = "raw_value")]
+    Raw(&'ctx str),
 }
 
 impl<'ctx> Value<'ctx> {
+    /// Creates a `Value::Str` from a borrowed or owned string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// assert_eq!(Value::string("a"), Value::Str("a".into()));
+    /// assert!(!Value::string(String::from("b")).is_borrowed());
+    /// ```
+    pub fn string(s: impl Into<Cow<'ctx, str>>) -> Self {
+        Value::Str(s.into())
+    }
+
+    /// Creates a `Value::Number` from anything which converts into a [`Number`], e.g. `u64`,
+    /// `i64` or `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// assert_eq!(Value::number(-1i64).to_string(), "-1");
+    /// assert_eq!(Value::number(1.5).to_string(), "1.5");
+    /// ```
+    pub fn number(n: impl Into<Number>) -> Self {
+        Value::Number(n.into())
+    }
+
+    /// Creates a `Value::Bool`.
+    pub fn bool(b: bool) -> Self {
+        Value::Bool(b)
+    }
+
+    /// Creates a `Value::Array` from an iterator of values. Same as collecting the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value = Value::array([Value::bool(true), Value::Null]);
+    /// assert_eq!(value.to_string(), "[true,null]");
+    /// ```
+    pub fn array(values: impl IntoIterator<Item = Value<'ctx>>) -> Self {
+        Value::Array(values.into_iter().collect())
+    }
+
+    /// Creates a `Value::Object` from an iterator of key/value pairs, keeping their order and
+    /// duplicate keys. Same as collecting the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value = Value::object([("a", Value::number(1u64)), ("b", Value::string("x"))]);
+    /// assert_eq!(value.to_string(), r#"{"a":1,"b":"x"}"#);
+    /// ```
+    pub fn object<K: Into<Cow<'ctx, str>>>(
+        entries: impl IntoIterator<Item = (K, Value<'ctx>)>,
+    ) -> Self {
+        Value::Object(
+            entries
+                .into_iter()
+                .map(|(key, val)| (key.into(), val))
+                .collect(),
+        )
+    }
+
     /// Index into a `serde_json_borrow::Value` using the syntax `value.get(0)` or
     /// `value.get("k")`.
     ///
@@ -104,334 +238,2523 @@ impl<'ctx> Value<'ctx> {
     /// assert_eq!(data.get("a"), &Value::Null);
     /// assert_eq!(data.get("a").get("b"), &Value::Null);
     /// ```
+    ///
+    /// The returned reference borrows from `self`, so the `Value` may live in a shorter scope than
+    /// the input it borrows from:
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// fn first_name<'a, 'ctx>(users: &'a Value<'ctx>) -> &'a Value<'ctx> {
+    ///     users.get(0).get("name")
+    /// }
+    ///
+    /// let input = String::from(r#"[{"name": "a"}]"#);
+    /// let name = {
+    ///     let users: Value = serde_json::from_str(&input).unwrap();
+    ///     first_name(&users).as_str().map(str::to_owned)
+    /// };
+    /// assert_eq!(name.as_deref(), Some("a"));
+    /// ```
     #[inline]
-    pub fn get<I: Index<'ctx>>(&'ctx self, index: I) -> &'ctx Value<'ctx> {
-        static NULL: Value = Value::Null;
+    pub fn get<I: Index>(&self, index: I) -> &Value<'ctx> {
         index.index_into(self).unwrap_or(&NULL)
     }
 
-    /// Returns true if `Value` is Value::Null.
-    pub fn is_null(&self) -> bool {
-        matches!(self, Value::Null)
+    /// If the Value is [`Raw`](Value::Raw), parses its text into a `Value` borrowing from the same
+    /// input. Returns a clone of the Value otherwise. Requires the `raw_value` feature.
+    ///
+    /// Raw values nested in arrays and objects are not parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let raw = Value::Raw(r#"[1, "a"]"#);
+    /// assert_eq!(raw.parse().unwrap().to_string(), r#"[1,"a"]"#);
+    /// assert_eq!(Value::Raw("[1,").parse().unwrap_err().offset(), Some(2));
+    /// ```
+    #[cfg(feature = "raw_value")]
+    pub fn parse(&self) -> Result<Value<'ctx>, ParseError> {
+        match self {
+            Value::Raw(text) => crate::from_str(text),
+            other => Ok(other.clone()),
+        }
     }
 
-    /// Returns true if `Value` is Value::Array.
-    pub fn is_array(&self) -> bool {
-        matches!(self, Value::Array(_))
+    /// Returns `default` if the Value is `Null`, and `self` otherwise.
+    ///
+    /// Since [`get`](Value::get) returns `Value::Null` for missing keys, this supplies defaults
+    /// for missing and null values alike.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let default_timeout: Value = serde_json::from_str("30").unwrap();
+    /// let config: Value = serde_json::from_str(r#"{"retries": 3, "timeout": null}"#).unwrap();
+    ///
+    /// assert_eq!(config.get("retries").unwrap_or(&default_timeout), 3);
+    /// assert_eq!(config.get("timeout").unwrap_or(&default_timeout), 30);
+    /// assert_eq!(config.get("missing").unwrap_or(&default_timeout), 30);
+    /// ```
+    #[inline]
+    pub fn unwrap_or<'a>(&'a self, default: &'a Value<'ctx>) -> &'a Value<'ctx> {
+        if self.is_null() {
+            default
+        } else {
+            self
+        }
     }
 
-    /// Returns true if `Value` is Value::Object.
-    pub fn is_object(&self) -> bool {
-        matches!(self, Value::Object(_))
+    /// Returns the result of `f` if the Value is `Null`, and `self` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let defaults: Value = serde_json::from_str(r#"{"host": "localhost"}"#).unwrap();
+    /// let config: Value = serde_json::from_str(r#"{"port": 8080}"#).unwrap();
+    ///
+    /// assert_eq!(config.get("host").or_else(|| defaults.get("host")), "localhost");
+    /// assert_eq!(config.get("port").or_else(|| defaults.get("port")), 8080);
+    /// ```
+    #[inline]
+    pub fn or_else<'a, F>(&'a self, f: F) -> &'a Value<'ctx>
+    where F: FnOnce() -> &'a Value<'ctx> {
+        if self.is_null() {
+            f()
+        } else {
+            self
+        }
     }
 
-    /// Returns true if `Value` is Value::Bool.
-    pub fn is_bool(&self) -> bool {
-        matches!(self, Value::Bool(_))
+    /// Index into a `serde_json_borrow::Value` using the syntax `value.try_get(0)` or
+    /// `value.try_get("k")`, failing with a descriptive error instead of returning `Value::Null`.
+    ///
+    /// Like [`get`](Value::get), the first entry of a duplicate key is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::{GetError, Value};
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"ids": [1, 2], "name": "a"}"#).unwrap();
+    ///
+    /// assert_eq!(data.try_get("ids").and_then(|ids| ids.try_get(1)).unwrap(), 2);
+    /// assert_eq!(data.try_get("id"), Err(GetError::KeyNotFound("id".to_string())));
+    /// assert_eq!(
+    ///     data.get("ids").try_get(5).unwrap_err().to_string(),
+    ///     "index 5 out of bounds for array of length 2"
+    /// );
+    /// assert_eq!(data.get("name").try_get(0).unwrap_err().to_string(), "expected array, found string");
+    /// ```
+    #[inline]
+    pub fn try_get<I: Index>(&self, index: I) -> Result<&Value<'ctx>, GetError> {
+        index.try_index_into(self)
     }
 
-    /// Returns true if `Value` is Value::Number.
-    pub fn is_number(&self) -> bool {
-        matches!(self, Value::Number(_))
+    /// Index into a `serde_json_borrow::Value` along a path of object keys and array indices.
+    ///
+    /// Returns `Value::Null` if any segment of the path does not resolve, like
+    /// [`get`](Value::get). This is convenient when the path is built dynamically at runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::{PathSegment, Value};
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"users": [{"name": "a"}]}"#).unwrap();
+    ///
+    /// let path = [PathSegment::Key("users"), PathSegment::Index(0), "name".into()];
+    /// assert_eq!(data.get_path(path), &Value::Str("a".into()));
+    /// assert_eq!(data.get_path(["users", "name"]), &Value::Null);
+    /// ```
+    pub fn get_path<'a, 'p, P>(&'a self, path: P) -> &'a Value<'ctx>
+    where
+        P: IntoIterator,
+        P::Item: Into<PathSegment<'p>>,
+    {
+        let mut target = self;
+        for segment in path {
+            let next = match (segment.into(), target) {
+                (PathSegment::Key(key), Value::Object(obj)) => {
+                    obj.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+                }
+                (PathSegment::Index(index), Value::Array(arr)) => arr.get(index),
+                _ => None,
+            };
+            match next {
+                Some(next) => target = next,
+                None => return &NULL,
+            }
+        }
+        target
     }
 
-    /// Returns true if `Value` is Value::Str.
-    pub fn is_string(&self) -> bool {
-        matches!(self, Value::Str(_))
+    /// Mutably index into a `serde_json_borrow::Value` using the syntax `value.get_mut(0)` or
+    /// `value.get_mut("k")`.
+    ///
+    /// Returns `None` if the type of `self` does not match the type of the index, if the given
+    /// key does not exist in the map or if the given index is not within the bounds of the array.
+    /// If an object contains the key multiple times, the first matching value is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let json_obj = r#"{"x": {"y": ["z", "zz"]}}"#;
+    ///
+    /// let mut data: Value = serde_json::from_str(json_obj).unwrap();
+    ///
+    /// *data.get_mut("x").unwrap().get_mut("y").unwrap().get_mut(0).unwrap() = Value::Bool(true);
+    /// assert_eq!(data.get("x").get("y").get(0), &Value::Bool(true));
+    ///
+    /// assert_eq!(data.get_mut("a"), None);
+    /// assert_eq!(data.get_mut(0), None);
+    /// ```
+    #[inline]
+    pub fn get_mut<I: Index>(&mut self, index: I) -> Option<&mut Value<'ctx>> {
+        index.index_into_mut(self)
     }
 
-    /// Returns true if the Value is an integer between i64::MIN and i64::MAX.
-    /// For any Value on which is_i64 returns true, as_i64 is guaranteed to return the integer
-    /// value.
-    pub fn is_i64(&self) -> bool {
+    /// Takes the value out of the `Value`, leaving a `Value::Null` in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"x": ["a", "b"]}"#).unwrap();
+    ///
+    /// let x = data.get_mut("x").unwrap().take();
+    /// assert_eq!(x.get(1), &Value::Str("b".into()));
+    /// assert_eq!(data.get("x"), &Value::Null);
+    /// ```
+    pub fn take(&mut self) -> Value<'ctx> {
+        core::mem::replace(self, Value::Null)
+    }
+
+    /// Recursively sorts the entries of all objects by key, to enable lookups via
+    /// [`get_sorted`](Value::get_sorted).
+    ///
+    /// The sort is stable, so entries with duplicate keys keep their document order.
+    pub fn sort_keys(&mut self) {
         match self {
-            Value::Number(n) => n.is_i64(),
-            _ => false,
+            Value::Array(arr) => arr.iter_mut().for_each(Value::sort_keys),
+            Value::Object(obj) => {
+                obj.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+                obj.iter_mut().for_each(|(_, v)| v.sort_keys());
+            }
+            _ => {}
         }
     }
 
-    /// Returns true if the Value is an integer between zero and u64::MAX.
-    /// For any Value on which is_u64 returns true, as_u64 is guaranteed to return the integer
-    /// value.
-    pub fn is_u64(&self) -> bool {
+    /// Looks up a key in an object via binary search. The object entries need to be sorted by
+    /// key, e.g. via [`sort_keys`](Value::sort_keys), otherwise the result is unspecified.
+    ///
+    /// Returns `Value::Null` if `self` is not an object or the key does not exist. If the key
+    /// exists multiple times, the first value is returned, like with `get`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"c": 3, "a": {"y": 2, "x": 1}}"#).unwrap();
+    /// data.sort_keys();
+    ///
+    /// assert_eq!(data.get_sorted("a").get_sorted("x"), &Value::Number(1u64.into()));
+    /// assert_eq!(data.get_sorted("c"), &Value::Number(3u64.into()));
+    /// assert_eq!(data.get_sorted("b"), &Value::Null);
+    /// ```
+    pub fn get_sorted<'a>(&'a self, key: &str) -> &'a Value<'ctx> {
         match self {
-            Value::Number(n) => n.is_u64(),
-            _ => false,
+            Value::Object(obj) => {
+                let pos = obj.partition_point(|(k, _)| k.as_ref() < key);
+                match obj.get(pos) {
+                    Some((k, v)) if k == key => v,
+                    _ => &NULL,
+                }
+            }
+            _ => &NULL,
         }
     }
 
-    /// Returns true if the Value is a f64 number.
-    pub fn is_f64(&self) -> bool {
-        match self {
-            Value::Number(n) => n.is_f64(),
+    /// Returns the entries of an object sorted by key, without reordering the object itself.
+    ///
+    /// Keys are compared by their bytes. The sort is stable, so entries with duplicate keys keep
+    /// their document order. Only the top-level entries are sorted, nested objects are unchanged.
+    /// Returns None if `self` is not an object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"c": 3, "a": 1, "b": 2}"#).unwrap();
+    ///
+    /// let keys: Vec<&str> = data.sorted_entries().unwrap().into_iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, ["a", "b", "c"]);
+    /// assert_eq!(data.to_string(), r#"{"c":3,"a":1,"b":2}"#);
+    /// ```
+    pub fn sorted_entries(&self) -> Option<Vec<(&str, &Value<'ctx>)>> {
+        let mut entries: Vec<_> = self
+            .as_object()?
+            .iter()
+            .map(|(key, val)| (key.as_ref(), val))
+            .collect();
+        entries.sort_by_key(|(key, _)| *key);
+        Some(entries)
+    }
+
+    /// Compares two values, treating objects as unordered collections of entries.
+    ///
+    /// Unlike `==`, which respects the order of object entries, two objects are equal here if they
+    /// contain the same key/value pairs in any order. Duplicate keys count as separate entries, so
+    /// `{"a":1,"a":1}` is not equal to `{"a":1}`. Numbers are compared by their numeric value, so
+    /// `1.0` equals `1`. Nested values are compared the same way.
+    ///
+    /// Matching object entries is quadratic in the number of entries of each object, so prefer
+    /// [`sort_keys`](Value::sort_keys) and `==` when comparing large objects repeatedly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let a: Value = serde_json::from_str(r#"{"a": 1, "b": [{"x": 1, "y": 2}]}"#).unwrap();
+    /// let b: Value = serde_json::from_str(r#"{"b": [{"y": 2, "x": 1}], "a": 1}"#).unwrap();
+    ///
+    /// assert_ne!(a, b);
+    /// assert!(a.semantic_eq(&b));
+    ///
+    /// let float: Value = serde_json::from_str("1.0").unwrap();
+    /// let int: Value = serde_json::from_str("1").unwrap();
+    /// assert!(float.semantic_eq(&int));
+    /// ```
+    pub fn semantic_eq(&self, other: &Value<'_>) -> bool {
+        match (self, other) {
+            (Value::Null, Value::Null) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Number(a), Value::Number(b)) => a.cmp_value(b) == Some(Ordering::Equal),
+            (Value::Str(a), Value::Str(b)) => a == b,
+            (Value::Array(a), Value::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.semantic_eq(b))
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                if a.len() != b.len() {
+                    return false;
+                }
+                let mut matched = vec![false; b.len()];
+                a.iter().all(|(key, val)| {
+                    let pos = b
+                        .iter()
+                        .zip(&matched)
+                        .position(|((k, v), &used)| !used && k == key && val.semantic_eq(v));
+                    match pos {
+                        Some(pos) => {
+                            matched[pos] = true;
+                            true
+                        }
+                        None => false,
+                    }
+                })
+            }
             _ => false,
         }
     }
 
-    /// If the Value is an Array, returns an iterator over the elements in the array.
-    pub fn iter_array(&self) -> Option<impl Iterator<Item = &Value<'_>>> {
+    /// Converts the `Value` into a `Value<'static>`, which no longer borrows from the input.
+    ///
+    /// Every borrowed string and object key is copied into an owned `String`, so this allocates
+    /// for each of them. Strings and keys which are already owned are moved without copying.
+    ///
+    /// A `Value::Raw` is parsed into an owned value. If its text is not valid JSON, it becomes a
+    /// [`Value::Str`] holding the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let owned: Value<'static> = {
+    ///     let data = String::from(r#"{"key": ["value"]}"#);
+    ///     let value: Value = serde_json::from_str(&data).unwrap();
+    ///     value.into_owned()
+    /// };
+    /// assert_eq!(owned.get("key").get(0), &Value::Str("value".into()));
+    /// ```
+    pub fn into_owned(self) -> Value<'static> {
         match self {
-            Value::Array(arr) => Some(arr.iter()),
-            _ => None,
+            Value::Null => Value::Null,
+            Value::Bool(b) => Value::Bool(b),
+            Value::Number(n) => Value::Number(n),
+            Value::Str(s) => Value::Str(Cow::Owned(s.into_owned())),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => raw_to_owned(text),
+            Value::Array(arr) => Value::Array(arr.into_iter().map(Value::into_owned).collect()),
+            Value::Object(obj) => Value::Object(
+                obj.into_iter()
+                    .map(|(key, val)| (Cow::Owned(key.into_owned()), val.into_owned()))
+                    .collect(),
+            ),
         }
     }
 
-    /// If the Value is an Object, returns an iterator over the elements in the object.
-    pub fn iter_object(&self) -> Option<impl Iterator<Item = &(&str, Value<'_>)>> {
+    /// Returns true if no string or object key in the `Value` had to be copied out of the input.
+    ///
+    /// Strings and keys containing escape sequences are unescaped into an owned `String` during
+    /// parsing, while all others borrow from the input. This checks recursively that every string
+    /// and key is `Cow::Borrowed`, which helps to audit that parsing stays zero-copy. Null,
+    /// booleans and numbers never borrow, but don't allocate either, so they count as borrowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"{"a": ["b", 1], "c": "d\ne"}"#).unwrap();
+    /// assert!(value.get("a").is_borrowed());
+    /// assert!(!value.get("c").is_borrowed());
+    /// assert!(!value.is_borrowed());
+    /// ```
+    pub fn is_borrowed(&self) -> bool {
         match self {
-            Value::Object(arr) => Some(arr.iter()),
-            _ => None,
+            Value::Null | Value::Bool(_) | Value::Number(_) => true,
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => true,
+            Value::Str(s) => matches!(s, Cow::Borrowed(_)),
+            Value::Array(arr) => arr.iter().all(Value::is_borrowed),
+            Value::Object(obj) => obj
+                .iter()
+                .all(|(key, val)| matches!(key, Cow::Borrowed(_)) && val.is_borrowed()),
         }
     }
 
-    /// If the Value is a Boolean, returns the associated bool. Returns None otherwise.
-    pub fn as_bool(&self) -> Option<bool> {
+    /// Creates an owned copy of the `Value`, which no longer borrows from the input, without
+    /// consuming `self`.
+    ///
+    /// Unlike [`into_owned`](Value::into_owned), every string and object key is copied into a new
+    /// `String`, including those which are already owned, and every array and object is cloned.
+    /// The cost is therefore similar to a deep clone of the whole tree.
+    ///
+    /// Like with `into_owned`, a `Value::Raw` is parsed, and becomes a [`Value::Str`] holding its
+    /// text if it is not valid JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data = String::from(r#"{"key": ["value"]}"#);
+    /// let value: Value = serde_json::from_str(&data).unwrap();
+    ///
+    /// let detached: Value<'static> = value.to_owned_value();
+    /// drop(data);
+    /// assert_eq!(detached.get("key").get(0), "value");
+    /// ```
+    pub fn to_owned_value(&self) -> Value<'static> {
         match self {
-            Value::Bool(b) => Some(*b),
-            _ => None,
+            Value::Null => Value::Null,
+            Value::Bool(b) => Value::Bool(*b),
+            Value::Number(n) => Value::Number(n.clone()),
+            Value::Str(s) => Value::Str(Cow::Owned(s.to_string())),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => raw_to_owned(text),
+            Value::Array(arr) => Value::Array(arr.iter().map(Value::to_owned_value).collect()),
+            Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(key, val)| (Cow::Owned(key.to_string()), val.to_owned_value()))
+                    .collect(),
+            ),
         }
     }
 
-    /// If the Value is a String, returns the associated str. Returns None otherwise.
-    pub fn as_str(&self) -> Option<&str> {
-        match self {
-            Value::Str(text) => Some(text),
-            _ => None,
+    /// Replaces arrays and objects nested deeper than `max_depth` with the placeholder string
+    /// `"…"`, e.g. to log large documents in a bounded and readable way.
+    ///
+    /// The depth is counted like for [`from_str_with_limit`](crate::from_str_with_limit): the
+    /// root is at depth 0 and the elements of an array or object are one level deeper than the
+    /// container. Containers at depth `max_depth` or deeper are replaced, including empty ones,
+    /// so the nesting depth of the result is at most `max_depth`. Scalars are kept at any depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value =
+    ///     serde_json::from_str(r#"{"id": 1, "user": {"name": "a", "tags": ["x"]}, "list": []}"#)
+    ///         .unwrap();
+    ///
+    /// data.truncate_depth(2);
+    /// assert_eq!(data.to_string(), r#"{"id":1,"user":{"name":"a","tags":"…"},"list":[]}"#);
+    ///
+    /// data.truncate_depth(1);
+    /// assert_eq!(data.to_string(), r#"{"id":1,"user":"…","list":"…"}"#);
+    /// ```
+    pub fn truncate_depth(&mut self, max_depth: usize) {
+        match self {
+            Value::Array(_) | Value::Object(_) if max_depth == 0 => {
+                *self = Value::Str(Cow::Borrowed("…"));
+            }
+            Value::Array(arr) => {
+                for val in arr {
+                    val.truncate_depth(max_depth - 1);
+                }
+            }
+            Value::Object(obj) => {
+                for (_, val) in obj {
+                    val.truncate_depth(max_depth - 1);
+                }
+            }
+            _ => {}
         }
     }
 
-    /// If the Value is an integer, represent it as i64 if possible. Returns None otherwise.
-    pub fn as_i64(&self) -> Option<i64> {
+    /// Converts every float without a fractional part, like `1.0` or `-0.0`, into an integer, so
+    /// numbers have a canonical representation regardless of how the input formatted them.
+    ///
+    /// Afterwards `1` and `1.0` from different sources compare and hash equal. Floats outside of
+    /// the 128-bit integer range are kept unchanged. With the `arbitrary_precision` feature,
+    /// numbers kept verbatim are unchanged, since they can't be represented exactly as integers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut a: Value = serde_json::from_str(r#"{"n": [1.0, -2.0, 2.5, 3]}"#).unwrap();
+    /// let b: Value = serde_json::from_str(r#"{"n": [1, -2, 2.5, 3]}"#).unwrap();
+    /// assert_ne!(a, b);
+    ///
+    /// a.normalize_numbers();
+    /// assert_eq!(a, b);
+    /// assert_eq!(a.to_string(), r#"{"n":[1,-2,2.5,3]}"#);
+    /// ```
+    pub fn normalize_numbers(&mut self) {
+        self.for_each_number_mut(&mut Number::normalize);
+    }
+
+    /// Converts every integer into a float, the inverse of
+    /// [`normalize_numbers`](Value::normalize_numbers).
+    ///
+    /// Integers beyond 2^53 in magnitude may lose precision. With the `arbitrary_precision`
+    /// feature, numbers kept verbatim are converted as well, unless they are out of the float
+    /// range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"n": [1, -2, 2.5]}"#).unwrap();
+    ///
+    /// data.floatify_numbers();
+    /// assert_eq!(data.to_string(), r#"{"n":[1.0,-2.0,2.5]}"#);
+    /// assert!(data.get("n").get(0).is_f64());
+    /// ```
+    pub fn floatify_numbers(&mut self) {
+        self.for_each_number_mut(&mut Number::floatify);
+    }
+
+    fn for_each_number_mut(&mut self, f: &mut impl FnMut(&mut Number)) {
         match self {
-            Value::Number(n) => n.as_i64(),
-            _ => None,
+            Value::Number(n) => f(n),
+            Value::Array(arr) => {
+                for val in arr {
+                    val.for_each_number_mut(f);
+                }
+            }
+            Value::Object(obj) => {
+                for (_, val) in obj {
+                    val.for_each_number_mut(f);
+                }
+            }
+            _ => {}
         }
     }
 
-    /// If the Value is an integer, represent it as u64 if possible. Returns None otherwise.
-    pub fn as_u64(&self) -> Option<u64> {
-        match self {
-            Value::Number(n) => n.as_u64(),
-            _ => None,
+    /// Returns the number of values in the document, counting the root, every container and every
+    /// scalar. Object keys are not counted separately.
+    ///
+    /// The document is traversed iteratively, so deeply nested values don't overflow the stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"a": [1, 2], "b": {}}"#).unwrap();
+    /// assert_eq!(data.count_nodes(), 5);
+    /// ```
+    pub fn count_nodes(&self) -> usize {
+        let mut count = 0;
+        let mut stack = vec![self];
+        while let Some(value) = stack.pop() {
+            count += 1;
+            match value {
+                Value::Array(arr) => stack.extend(arr),
+                Value::Object(obj) => stack.extend(obj.iter().map(|(_, val)| val)),
+                _ => {}
+            }
         }
+        count
     }
 
-    /// If the Value is a number, represent it as f64 if possible. Returns None otherwise.
-    pub fn as_f64(&self) -> Option<f64> {
-        match self {
-            Value::Number(n) => n.as_f64(),
-            _ => None,
+    /// Returns the nesting depth of arrays and objects in the document.
+    ///
+    /// The depth is counted like for [`from_str_with_limit`](crate::from_str_with_limit): a scalar
+    /// has a depth of 0, `[]` and `{}` have a depth of 1, `[[]]` a depth of 2 and so on. The
+    /// document is traversed iteratively, so deeply nested values don't overflow the stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"a": [1, {"b": null}], "c": 2}"#).unwrap();
+    /// assert_eq!(data.max_depth(), 3);
+    /// assert_eq!(data.get("c").max_depth(), 0);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        let mut max_depth = 0;
+        // Every value with the depth of the containers enclosing it.
+        let mut stack = vec![(self, 0)];
+        while let Some((value, depth)) = stack.pop() {
+            match value {
+                Value::Array(arr) => {
+                    max_depth = max_depth.max(depth + 1);
+                    stack.extend(arr.iter().map(|val| (val, depth + 1)));
+                }
+                Value::Object(obj) => {
+                    max_depth = max_depth.max(depth + 1);
+                    stack.extend(obj.iter().map(|(_, val)| (val, depth + 1)));
+                }
+                _ => {}
+            }
         }
+        max_depth
     }
-}
 
-impl<'ctx> std::fmt::Debug for Value<'ctx> {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+    /// Returns an estimate of the heap memory in bytes held by the `Value`.
+    ///
+    /// This sums up the allocated capacity of all arrays and objects and of all owned strings and
+    /// keys, recursively. Borrowed strings and keys count as zero, since their bytes belong to the
+    /// input. The result is an estimate: it doesn't include allocator overhead or the size of
+    /// `self`, and it is only as accurate as the capacities reported by `Vec` and `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"["borrowed", "owned\n"]"#).unwrap();
+    /// let elements = value.as_array().unwrap().capacity() * std::mem::size_of::<Value>();
+    /// assert!(value.heap_size() >= elements + "owned\n".len());
+    /// ```
+    pub fn heap_size(&self) -> usize {
+        let cow_heap_size = |s: &Cow<str>| match s {
+            Cow::Borrowed(_) => 0,
+            Cow::Owned(s) => s.capacity(),
+        };
         match self {
-            Value::Null => formatter.write_str("Null"),
-            Value::Bool(boolean) => write!(formatter, "Bool({})", boolean),
-            Value::Number(number) => match number.n {
-                N::PosInt(n) => write!(formatter, "Number({:?})", n),
-                N::NegInt(n) => write!(formatter, "Number({:?})", n),
-                N::Float(n) => write!(formatter, "Number({:?})", n),
-            },
-            Value::Str(string) => write!(formatter, "Str({:?})", string),
-            Value::Array(vec) => {
-                formatter.write_str("Array ")?;
-                Debug::fmt(vec, formatter)
+            Value::Null | Value::Bool(_) => 0,
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => 0,
+            Value::Number(n) => n.as_raw_str().map_or(0, str::len),
+            Value::Str(s) => cow_heap_size(s),
+            Value::Array(arr) => {
+                arr.capacity() * core::mem::size_of::<Value>()
+                    + arr.iter().map(Value::heap_size).sum::<usize>()
             }
-            Value::Object(map) => {
-                formatter.write_str("Object ")?;
-                Debug::fmt(map, formatter)
+            Value::Object(obj) => {
+                obj.capacity() * core::mem::size_of::<(Cow<str>, Value)>()
+                    + obj
+                        .iter()
+                        .map(|(key, val)| cow_heap_size(key) + val.heap_size())
+                        .sum::<usize>()
             }
         }
     }
-}
 
-/// Represents a JSON number, whether integer or floating point.
-#[derive(Clone, PartialEq, Eq, Hash)]
-pub struct Number {
-    n: N,
-}
+    /// Returns the name of the type of the value, for error messages and logging: `"null"`,
+    /// `"bool"`, `"number"`, `"string"`, `"array"` or `"object"`. With the `raw_value` feature,
+    /// it is `"raw"` for `Value::Raw`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let value: Value = serde_json::from_str(r#"{"a": [true]}"#).unwrap();
+    /// assert_eq!(value.type_name(), "object");
+    /// assert_eq!(value.get("a").get(0).type_name(), "bool");
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Bool(_) => "bool",
+            Value::Number(_) => "number",
+            Value::Str(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => "raw",
+        }
+    }
 
-#[derive(Copy, Clone)]
-enum N {
-    PosInt(u64),
-    /// Always less than zero.
-    NegInt(i64),
-    /// Always finite.
-    Float(f64),
-}
+    /// Returns true if `Value` is Value::Null.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
 
-impl Number {
-    /// If the `Number` is an integer, represent it as i64 if possible. Returns
-    /// None otherwise.
-    pub fn as_u64(&self) -> Option<u64> {
-        match self.n {
-            N::PosInt(v) => Some(v),
-            _ => None,
-        }
+    /// Returns true if `Value` is Value::Array.
+    pub fn is_array(&self) -> bool {
+        matches!(self, Value::Array(_))
     }
-    /// If the `Number` is an integer, represent it as u64 if possible. Returns
-    /// None otherwise.
-    pub fn as_i64(&self) -> Option<i64> {
-        match self.n {
-            N::PosInt(n) => {
-                if n <= i64::max_value() as u64 {
-                    Some(n as i64)
-                } else {
-                    None
-                }
-            }
-            N::NegInt(v) => Some(v),
-            _ => None,
-        }
+
+    /// Returns true if `Value` is Value::Object.
+    pub fn is_object(&self) -> bool {
+        matches!(self, Value::Object(_))
     }
 
-    /// Represents the number as f64 if possible. Returns None otherwise.
-    pub fn as_f64(&self) -> Option<f64> {
-        match self.n {
-            N::PosInt(n) => Some(n as f64),
-            N::NegInt(n) => Some(n as f64),
-            N::Float(n) => Some(n),
-        }
+    /// Returns true if `Value` is Value::Bool.
+    pub fn is_bool(&self) -> bool {
+        matches!(self, Value::Bool(_))
     }
 
-    /// Returns true if the `Number` is a f64.
-    pub fn is_f64(&self) -> bool {
-        matches!(self.n, N::Float(_))
+    /// Returns true if `Value` is Value::Number.
+    pub fn is_number(&self) -> bool {
+        matches!(self, Value::Number(_))
     }
 
-    /// Returns true if the `Number` is a u64.
-    pub fn is_u64(&self) -> bool {
-        matches!(self.n, N::PosInt(_))
+    /// Returns true if `Value` is Value::Str.
+    pub fn is_string(&self) -> bool {
+        matches!(self, Value::Str(_))
     }
 
-    /// Returns true if the `Number` is an integer between `i64::MIN` and
-    /// `i64::MAX`.
+    /// Returns true if the Value is an integer between i64::MIN and i64::MAX.
+    /// For any Value on which is_i64 returns true, as_i64 is guaranteed to return the integer
+    /// value.
     pub fn is_i64(&self) -> bool {
-        match self.n {
-            N::PosInt(v) => v <= i64::max_value() as u64,
-            N::NegInt(_) => true,
-            N::Float(_) => false,
+        match self {
+            Value::Number(n) => n.is_i64(),
+            _ => false,
         }
     }
-}
 
-impl PartialEq for N {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (N::PosInt(a), N::PosInt(b)) => a == b,
-            (N::NegInt(a), N::NegInt(b)) => a == b,
-            (N::Float(a), N::Float(b)) => a == b,
+    /// Returns true if the Value is an integer between zero and u64::MAX.
+    /// For any Value on which is_u64 returns true, as_u64 is guaranteed to return the integer
+    /// value.
+    pub fn is_u64(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_u64(),
             _ => false,
         }
     }
-}
 
-// Implementing Eq is fine since any float values are always finite.
-impl Eq for N {}
-
-impl Hash for N {
-    fn hash<H: Hasher>(&self, h: &mut H) {
-        match *self {
-            N::PosInt(i) => i.hash(h),
-            N::NegInt(i) => i.hash(h),
-            N::Float(f) => {
-                if f == 0.0f64 {
-                    // There are 2 zero representations, +0 and -0, which
-                    // compare equal but have different bits. We use the +0 hash
-                    // for both so that hash(+0) == hash(-0).
-                    0.0f64.to_bits().hash(h);
-                } else {
-                    f.to_bits().hash(h);
-                }
-            }
+    /// Returns true if the Value is an integer between i128::MIN and i128::MAX.
+    /// For any Value on which is_i128 returns true, as_i128 is guaranteed to return the integer
+    /// value.
+    pub fn is_i128(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_i128(),
+            _ => false,
         }
     }
-}
 
-impl From<u64> for Number {
-    fn from(val: u64) -> Self {
-        Self { n: N::PosInt(val) }
+    /// Returns true if the Value is an integer between zero and u128::MAX.
+    /// For any Value on which is_u128 returns true, as_u128 is guaranteed to return the integer
+    /// value.
+    pub fn is_u128(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_u128(),
+            _ => false,
+        }
     }
-}
 
-impl From<i64> for Number {
-    fn from(val: i64) -> Self {
-        Self { n: N::NegInt(val) }
+    /// Returns true if the Value is a f64 number.
+    pub fn is_f64(&self) -> bool {
+        match self {
+            Value::Number(n) => n.is_f64(),
+            _ => false,
+        }
     }
-}
 
-impl From<f64> for Number {
-    fn from(val: f64) -> Self {
-        Self { n: N::Float(val) }
+    /// If the Value is an Array, returns an iterator over the elements in the array.
+    pub fn iter_array(&self) -> Option<impl Iterator<Item = &Value<'ctx>>> {
+        match self {
+            Value::Array(arr) => Some(arr.iter()),
+            _ => None,
+        }
     }
-}
 
-impl From<Number> for serde_json::value::Number {
-    fn from(num: Number) -> Self {
-        match num.n {
-            N::PosInt(n) => n.into(),
-            N::NegInt(n) => n.into(),
-            N::Float(n) => serde_json::value::Number::from_f64(n).unwrap(),
+    /// If the Value is an Object, returns an iterator over the elements in the object.
+    pub fn iter_object(&self) -> Option<impl Iterator<Item = &(Cow<'ctx, str>, Value<'ctx>)>> {
+        match self {
+            Value::Object(arr) => Some(arr.iter()),
+            _ => None,
         }
     }
-}
 
-impl<'ctx> From<Value<'ctx>> for serde_json::Value {
-    fn from(val: Value) -> Self {
-        match val {
-            Value::Null => serde_json::Value::Null,
-            Value::Bool(val) => serde_json::Value::Bool(val),
-            Value::Number(val) => serde_json::Value::Number(val.into()),
-            Value::Str(val) => serde_json::Value::String(val.to_string()),
-            Value::Array(vals) => {
-                serde_json::Value::Array(vals.into_iter().map(|val| val.into()).collect())
-            }
-            Value::Object(vals) => serde_json::Value::Object(
-                vals.into_iter()
-                    .map(|(key, val)| (key.to_owned(), val.into()))
-                    .collect(),
-            ),
+    /// If the Value is an Array, returns an iterator over mutable references to the elements in
+    /// the array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"[1, null, 3]"#).unwrap();
+    ///
+    /// for value in data.iter_array_mut().unwrap().filter(|v| v.is_null()) {
+    ///     *value = Value::Number(0u64.into());
+    /// }
+    /// assert_eq!(data.to_string(), "[1,0,3]");
+    /// ```
+    pub fn iter_array_mut(&mut self) -> Option<impl Iterator<Item = &mut Value<'ctx>>> {
+        match self {
+            Value::Array(arr) => Some(arr.iter_mut()),
+            _ => None,
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use std::io;
 
-    use super::*;
+    /// If the Value is an Object, returns an iterator over the keys and mutable references to the
+    /// values in the object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"password": "secret", "user": "a"}"#).unwrap();
+    ///
+    /// for (key, value) in data.iter_object_mut().unwrap() {
+    ///     if key == "password" {
+    ///         *value = Value::Str("***".into());
+    ///     }
+    /// }
+    /// assert_eq!(data.to_string(), r#"{"password":"***","user":"a"}"#);
+    /// ```
+    pub fn iter_object_mut(&mut self) -> Option<impl Iterator<Item = (&str, &mut Value<'ctx>)>> {
+        match self {
+            Value::Object(obj) => Some(obj.iter_mut().map(|(key, val)| {
+                let key: &Cow<str> = key;
+                (key.as_ref(), val)
+            })),
+            _ => None,
+        }
+    }
 
-    #[test]
-    fn number_test() -> io::Result<()> {
-        let data = r#"{"val1": 123.5, "val2": 123, "val3": -123}"#;
-        let value: Value = serde_json::from_str(data)?;
-        assert!(value.get("val1").is_f64());
-        assert!(!value.get("val1").is_u64());
-        assert!(!value.get("val1").is_i64());
+    /// Returns the number of elements of an Array or the number of entries of an Object.
+    /// Returns None for all other variants, to distinguish them from empty containers.
+    pub fn len(&self) -> Option<usize> {
+        match self {
+            Value::Array(arr) => Some(arr.len()),
+            Value::Object(obj) => Some(obj.len()),
+            _ => None,
+        }
+    }
 
-        assert!(!value.get("val2").is_f64());
-        assert!(value.get("val2").is_u64());
-        assert!(value.get("val2").is_i64());
+    /// Returns whether an Array or Object is empty. Returns None for all other variants.
+    pub fn is_empty(&self) -> Option<bool> {
+        self.len().map(|len| len == 0)
+    }
 
-        assert!(!value.get("val3").is_f64());
-        assert!(!value.get("val3").is_u64());
-        assert!(value.get("val3").is_i64());
+    /// Returns true if the Value is an Object and contains the key.
+    pub fn contains_key(&self, key: &str) -> bool {
+        match self {
+            Value::Object(obj) => obj.iter().any(|(k, _)| k == key),
+            _ => false,
+        }
+    }
 
-        assert!(value.get("val1").as_f64().is_some());
-        assert!(value.get("val2").as_f64().is_some());
-        assert!(value.get("val3").as_f64().is_some());
+    /// If the Value is an Object, returns an iterator over the keys in the object.
+    /// Duplicate keys are yielded in document order. Returns None otherwise.
+    pub fn keys(&self) -> Option<impl Iterator<Item = &str>> {
+        match self {
+            Value::Object(obj) => Some(obj.iter().map(|(k, _)| k.as_ref())),
+            _ => None,
+        }
+    }
 
-        assert!(value.get("val1").as_u64().is_none());
-        assert!(value.get("val2").as_u64().is_some());
-        assert!(value.get("val3").as_u64().is_none());
+    /// If the Value is an Object, returns an iterator over the values in the object.
+    /// Values of duplicate keys are yielded in document order. Returns None otherwise.
+    pub fn values(&self) -> Option<impl Iterator<Item = &Value<'ctx>>> {
+        match self {
+            Value::Object(obj) => Some(obj.iter().map(|(_, v)| v)),
+            _ => None,
+        }
+    }
 
-        assert!(value.get("val1").as_i64().is_none());
-        assert!(value.get("val2").as_i64().is_some());
-        assert!(value.get("val3").as_i64().is_some());
+    /// If the Value is an Array, returns the associated Vec. Returns None otherwise.
+    pub fn as_array(&self) -> Option<&Vec<Value<'ctx>>> {
+        match self {
+            Value::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an Object, returns the associated Vec of (key, value) pairs.
+    /// Returns None otherwise.
+    pub fn as_object(&self) -> Option<&Vec<(Cow<'ctx, str>, Value<'ctx>)>> {
+        match self {
+            Value::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an Array, returns the associated mutable Vec. Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str("[3, 1, 2]").unwrap();
+    ///
+    /// data.as_array_mut().unwrap().sort_by_key(|v| v.as_u64());
+    /// assert_eq!(data.to_string(), "[1,2,3]");
+    /// ```
+    pub fn as_array_mut(&mut self) -> Option<&mut Vec<Value<'ctx>>> {
+        match self {
+            Value::Array(arr) => Some(arr),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an Object, returns the associated mutable Vec of (key, value) pairs.
+    /// Returns None otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"b": 1, "a": 2}"#).unwrap();
+    ///
+    /// data.as_object_mut().unwrap().sort_by(|(a, _), (b, _)| a.cmp(b));
+    /// assert_eq!(data.to_string(), r#"{"a":2,"b":1}"#);
+    /// ```
+    pub fn as_object_mut(&mut self) -> Option<&mut Vec<(Cow<'ctx, str>, Value<'ctx>)>> {
+        match self {
+            Value::Object(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an Array, moves out the associated Vec. Returns the Value unchanged
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"[1, "a"]"#).unwrap();
+    /// let arr = data.into_array().unwrap();
+    /// assert_eq!(arr.len(), 2);
+    ///
+    /// let data: Value = serde_json::from_str("true").unwrap();
+    /// assert_eq!(data.into_array(), Err(Value::Bool(true)));
+    /// ```
+    pub fn into_array(self) -> Result<Vec<Value<'ctx>>, Value<'ctx>> {
+        match self {
+            Value::Array(arr) => Ok(arr),
+            other => Err(other),
+        }
+    }
+
+    /// If the Value is an Object, moves out the associated Vec of (key, value) pairs. Returns the
+    /// Value unchanged otherwise.
+    ///
+    /// Keys are handed out as they are stored, borrowed from the input where possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"a": 1, "b": 2}"#).unwrap();
+    /// let keys: Vec<_> = data.into_object().unwrap().into_iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, ["a", "b"]);
+    /// ```
+    pub fn into_object(self) -> Result<Vec<(Cow<'ctx, str>, Value<'ctx>)>, Value<'ctx>> {
+        match self {
+            Value::Object(obj) => Ok(obj),
+            other => Err(other),
+        }
+    }
+
+    /// If the Value is a String, moves out the associated Cow. Returns the Value unchanged
+    /// otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#""text""#).unwrap();
+    /// assert!(matches!(data.into_string(), Ok(Cow::Borrowed("text"))));
+    /// ```
+    pub fn into_string(self) -> Result<Cow<'ctx, str>, Value<'ctx>> {
+        match self {
+            Value::Str(text) => Ok(text),
+            other => Err(other),
+        }
+    }
+
+    /// If the Value is Null, returns (). Returns None otherwise.
+    pub fn as_null(&self) -> Option<()> {
+        match self {
+            Value::Null => Some(()),
+            _ => None,
+        }
+    }
+
+    /// If the Value is a Boolean, returns the associated bool. Returns None otherwise.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// If the Value is a String, returns the associated str. Returns None otherwise.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as i64 if possible. Returns None otherwise.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as u64 if possible. Returns None otherwise.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            Value::Number(n) => n.as_u64(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as i128 if possible. Returns None otherwise.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Number(n) => n.as_i128(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as u128 if possible. Returns None otherwise.
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::Number(n) => n.as_u128(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is a number, represent it as f64 if possible. Returns None otherwise.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(n) => n.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is a number, represent it as f32 if possible. Returns None otherwise.
+    ///
+    /// The conversion may lose precision, see [`Number::as_f32`].
+    pub fn as_f32(&self) -> Option<f32> {
+        match self {
+            Value::Number(n) => n.as_f32(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as i32 if it fits. Returns None otherwise.
+    pub fn as_i32(&self) -> Option<i32> {
+        match self {
+            Value::Number(n) => n.as_i32(),
+            _ => None,
+        }
+    }
+
+    /// If the Value is an integer, represent it as u32 if it fits. Returns None otherwise.
+    pub fn as_u32(&self) -> Option<u32> {
+        match self {
+            Value::Number(n) => n.as_u32(),
+            _ => None,
+        }
+    }
+
+    /// Like [`as_i64`](Value::as_i64), but also accepts a string containing an integer, e.g.
+    /// `"42"` or `"-7"`.
+    ///
+    /// The string must be a valid JSON number without surrounding whitespace, so `"+1"`, `"0x10"`
+    /// and `" 1"` are rejected. Like for numbers, a fraction or exponent such as `"1.0"` or
+    /// `"1e3"` is not an integer and returns None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"[42, "42", "4.2", "abc"]"#).unwrap();
+    /// assert_eq!(data.get(0).as_i64_lenient(), Some(42));
+    /// assert_eq!(data.get(1).as_i64_lenient(), Some(42));
+    /// assert_eq!(data.get(2).as_i64_lenient(), None);
+    /// assert_eq!(data.get(3).as_i64_lenient(), None);
+    /// ```
+    pub fn as_i64_lenient(&self) -> Option<i64> {
+        match self {
+            Value::Str(text) => text.parse::<Number>().ok()?.as_i64(),
+            _ => self.as_i64(),
+        }
+    }
+
+    /// Like [`as_f64`](Value::as_f64), but also accepts a string containing a number, e.g.
+    /// `"4.2"`, `"-1"` or `"1e3"`.
+    ///
+    /// The string must be a valid JSON number without surrounding whitespace, so `"NaN"`, `"inf"`,
+    /// `".5"` and `" 1"` are rejected, as are numbers which overflow `f64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"[4.2, "4.2", "NaN"]"#).unwrap();
+    /// assert_eq!(data.get(0).as_f64_lenient(), Some(4.2));
+    /// assert_eq!(data.get(1).as_f64_lenient(), Some(4.2));
+    /// assert_eq!(data.get(2).as_f64_lenient(), None);
+    /// ```
+    pub fn as_f64_lenient(&self) -> Option<f64> {
+        match self {
+            Value::Str(text) => text
+                .parse::<Number>()
+                .ok()?
+                .as_f64()
+                .filter(|f| f.is_finite()),
+            _ => self.as_f64(),
+        }
+    }
+
+    /// Like [`as_bool`](Value::as_bool), but also accepts the strings `"true"` and `"false"`.
+    ///
+    /// The comparison is case-sensitive and no other forms like `"1"` or `"yes"` are accepted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"[true, "false", "True"]"#).unwrap();
+    /// assert_eq!(data.get(0).as_bool_lenient(), Some(true));
+    /// assert_eq!(data.get(1).as_bool_lenient(), Some(false));
+    /// assert_eq!(data.get(2).as_bool_lenient(), None);
+    /// ```
+    pub fn as_bool_lenient(&self) -> Option<bool> {
+        match self {
+            Value::Str(text) => match text.as_ref() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            _ => self.as_bool(),
+        }
+    }
+
+    /// Returns whether the value is truthy by the rules of JavaScript, e.g. to port conditions
+    /// like `if (value)` from JavaScript code.
+    ///
+    /// This is a JavaScript-style coercion, not part of JSON: `null`, `false`, `0`, `-0`, NaN and
+    /// the empty string are falsy, everything else is truthy, including the string `"0"` and empty
+    /// arrays and objects. Use [`as_bool`](Value::as_bool) to check for an actual boolean.
+    ///
+    /// A `Value::Raw` is judged by its parsed value, or as a non-empty string if its text is not
+    /// valid JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"[null, false, 0, -0.0, "", "0", [], {}, 1]"#).unwrap();
+    /// let truthy: Vec<bool> = data.iter_array().unwrap().map(Value::is_truthy).collect();
+    /// assert_eq!(truthy, [false, false, false, false, false, true, true, true, true]);
+    /// ```
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Bool(b) => *b,
+            Value::Number(n) => !n.as_f64().is_some_and(|f| f == 0.0 || f.is_nan()),
+            Value::Str(s) => !s.is_empty(),
+            Value::Array(_) | Value::Object(_) => true,
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => parse_raw_lossy(text).is_truthy(),
+        }
+    }
+}
+
+/// Values are ordered by their type first, in the order
+/// `Null < Bool < Number < Str < Array < Object`, then by their contents:
+///
+/// - `false < true`.
+/// - Numbers are ordered numerically, see [`Number`].
+/// - Strings are ordered lexicographically by their bytes.
+/// - Arrays are ordered lexicographically by their elements.
+/// - Objects are ordered lexicographically by their `(key, value)` entries in document order,
+///   consistent with equality, which also depends on the order of the entries.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let mut values: Vec<Value> = serde_json::from_str(r#"[{}, [], "a", 2, 1.5, true, null]"#).unwrap();
+/// values.sort();
+/// assert_eq!(Value::Array(values).to_string(), r#"[null,true,1.5,2,"a",[],{}]"#);
+/// ```
+impl<'ctx> Ord for Value<'ctx> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        fn rank(value: &Value) -> u8 {
+            match value {
+                Value::Null => 0,
+                Value::Bool(_) => 1,
+                Value::Number(_) => 2,
+                Value::Str(_) => 3,
+                Value::Array(_) => 4,
+                Value::Object(_) => 5,
+                #[cfg(feature = "raw_value")]
+                Value::Raw(_) => 6,
+            }
+        }
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Number(a), Value::Number(b)) => a.cmp(b),
+            (Value::Str(a), Value::Str(b)) => a.cmp(b),
+            (Value::Array(a), Value::Array(b)) => a.cmp(b),
+            (Value::Object(a), Value::Object(b)) => a.cmp(b),
+            #[cfg(feature = "raw_value")]
+            (Value::Raw(a), Value::Raw(b)) => a.cmp(b),
+            _ => rank(self).cmp(&rank(other)),
+        }
+    }
+}
+
+impl<'ctx> PartialOrd for Value<'ctx> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The default value is `Value::Null`.
+impl<'ctx> Default for Value<'ctx> {
+    fn default() -> Self {
+        Value::Null
+    }
+}
+
+/// Collects values into a `Value::Array`.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let value: Value = ["a", "b"].into_iter().map(|s| Value::Str(s.into())).collect();
+/// assert_eq!(value.to_string(), r#"["a","b"]"#);
+/// ```
+impl<'ctx> FromIterator<Value<'ctx>> for Value<'ctx> {
+    fn from_iter<I: IntoIterator<Item = Value<'ctx>>>(iter: I) -> Self {
+        Value::Array(iter.into_iter().collect())
+    }
+}
+
+/// Collects key/value pairs into a `Value::Object`, keeping their order and duplicate keys.
+///
+/// Keys can be anything which converts into a `Cow<str>`, e.g. `&str` or `String`.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let value: Value = [("a", Value::Null), ("b", Value::Bool(true))].into_iter().collect();
+/// assert_eq!(value.to_string(), r#"{"a":null,"b":true}"#);
+/// ```
+impl<'ctx, K: Into<Cow<'ctx, str>>> FromIterator<(K, Value<'ctx>)> for Value<'ctx> {
+    fn from_iter<I: IntoIterator<Item = (K, Value<'ctx>)>>(iter: I) -> Self {
+        Value::Object(
+            iter.into_iter()
+                .map(|(key, val)| (key.into(), val))
+                .collect(),
+        )
+    }
+}
+
+/// Appends values to a `Value::Array`. A `Value::Null` is turned into an array first.
+///
+/// # Panics
+///
+/// Panics if the Value is neither an array nor null.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let mut value = Value::Null;
+/// value.extend([Value::Bool(true), Value::Null]);
+/// value.extend([Value::Str("x".into())]);
+/// assert_eq!(value.to_string(), r#"[true,null,"x"]"#);
+/// ```
+impl<'ctx> Extend<Value<'ctx>> for Value<'ctx> {
+    fn extend<I: IntoIterator<Item = Value<'ctx>>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = Value::Array(Vec::new());
+        }
+        match self {
+            Value::Array(arr) => arr.extend(iter),
+            _ => panic!("extend called on a Value which is neither an array nor null"),
+        }
+    }
+}
+
+/// Appends key/value pairs to a `Value::Object`, keeping existing entries and duplicate keys. A
+/// `Value::Null` is turned into an object first.
+///
+/// # Panics
+///
+/// Panics if the Value is neither an object nor null.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let mut value = Value::Null;
+/// value.extend([("a", Value::Null)]);
+/// value.extend([(String::from("b"), Value::Bool(false))]);
+/// assert_eq!(value.to_string(), r#"{"a":null,"b":false}"#);
+/// ```
+impl<'ctx, K: Into<Cow<'ctx, str>>> Extend<(K, Value<'ctx>)> for Value<'ctx> {
+    fn extend<I: IntoIterator<Item = (K, Value<'ctx>)>>(&mut self, iter: I) {
+        if self.is_null() {
+            *self = Value::Object(Vec::new());
+        }
+        match self {
+            Value::Object(obj) => obj.extend(iter.into_iter().map(|(key, val)| (key.into(), val))),
+            _ => panic!("extend called on a Value which is neither an object nor null"),
+        }
+    }
+}
+
+/// Formats the `Value` like a derived `Debug` implementation would, including the pretty-printed
+/// form with `{:#?}`.
+///
+/// Nested arrays and objects are formatted iteratively, so deeply nested values don't overflow the
+/// stack.
+impl<'ctx> fmt::Debug for Value<'ctx> {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        /// Pieces of output, processed from a stack instead of recursing into nested values.
+        enum Task<'a, 'ctx> {
+            Value(&'a Value<'ctx>, usize),
+            Key(&'a str),
+            Text(&'static str),
+            Indent(usize),
+        }
+
+        let pretty = formatter.alternate();
+        let mut stack = vec![Task::Value(self, 0)];
+        let mut tasks = Vec::new();
+        while let Some(task) = stack.pop() {
+            match task {
+                Task::Text(text) => formatter.write_str(text)?,
+                Task::Key(key) => write!(formatter, "{:?}", key)?,
+                Task::Indent(level) => {
+                    for _ in 0..level {
+                        formatter.write_str("    ")?;
+                    }
+                }
+                Task::Value(value, level) => match value {
+                    Value::Null => formatter.write_str("Null")?,
+                    Value::Bool(boolean) => write!(formatter, "Bool({})", boolean)?,
+                    Value::Number(number) => Debug::fmt(number, formatter)?,
+                    Value::Str(string) => write!(formatter, "Str({:?})", string)?,
+                    #[cfg(feature = "raw_value")]
+                    Value::Raw(text) => write!(formatter, "Raw({:?})", text)?,
+                    Value::Array(vec) => {
+                        formatter.write_str("Array [")?;
+                        for (i, val) in vec.iter().enumerate() {
+                            if pretty {
+                                tasks.push(Task::Text(if i == 0 { "\n" } else { ",\n" }));
+                                tasks.push(Task::Indent(level + 1));
+                            } else if i > 0 {
+                                tasks.push(Task::Text(", "));
+                            }
+                            tasks.push(Task::Value(val, level + 1));
+                        }
+                        if pretty && !vec.is_empty() {
+                            tasks.push(Task::Text(",\n"));
+                            tasks.push(Task::Indent(level));
+                        }
+                        tasks.push(Task::Text("]"));
+                        stack.extend(tasks.drain(..).rev());
+                    }
+                    Value::Object(map) => {
+                        formatter.write_str("Object [")?;
+                        for (i, (key, val)) in map.iter().enumerate() {
+                            if pretty {
+                                tasks.push(Task::Text(if i == 0 { "\n" } else { ",\n" }));
+                                tasks.push(Task::Indent(level + 1));
+                                tasks.push(Task::Text("(\n"));
+                                tasks.push(Task::Indent(level + 2));
+                                tasks.push(Task::Key(key));
+                                tasks.push(Task::Text(",\n"));
+                                tasks.push(Task::Indent(level + 2));
+                                tasks.push(Task::Value(val, level + 2));
+                                tasks.push(Task::Text(",\n"));
+                                tasks.push(Task::Indent(level + 1));
+                                tasks.push(Task::Text(")"));
+                            } else {
+                                if i > 0 {
+                                    tasks.push(Task::Text(", "));
+                                }
+                                tasks.push(Task::Text("("));
+                                tasks.push(Task::Key(key));
+                                tasks.push(Task::Text(", "));
+                                tasks.push(Task::Value(val, level));
+                                tasks.push(Task::Text(")"));
+                            }
+                        }
+                        if pretty && !map.is_empty() {
+                            tasks.push(Task::Text(",\n"));
+                            tasks.push(Task::Indent(level));
+                        }
+                        tasks.push(Task::Text("]"));
+                        stack.extend(tasks.drain(..).rev());
+                    }
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Converts into a `serde_json::Value`, copying all strings.
+///
+/// The conversion is iterative, so deeply nested values don't overflow the stack. Since a
+/// `serde_json::Map` can't contain duplicate keys, the last entry of a duplicate key wins. A
+/// `Value::Raw` whose text is not valid JSON becomes a `serde_json::Value::String` holding the
+/// text.
+impl<'ctx> From<Value<'ctx>> for serde_json::Value {
+    fn from(val: Value) -> Self {
+        /// An array or object whose elements are being converted.
+        enum Frame<'ctx> {
+            Array(Vec<serde_json::Value>, alloc::vec::IntoIter<Value<'ctx>>),
+            Object(
+                serde_json::Map<String, serde_json::Value>,
+                String,
+                alloc::vec::IntoIter<(Cow<'ctx, str>, Value<'ctx>)>,
+            ),
+        }
+
+        let mut stack: Vec<Frame> = Vec::new();
+        let mut current = val;
+        loop {
+            let mut converted = match current {
+                Value::Null => Some(serde_json::Value::Null),
+                Value::Bool(val) => Some(serde_json::Value::Bool(val)),
+                Value::Number(val) => Some(serde_json::Value::Number(val.into())),
+                Value::Str(val) => Some(serde_json::Value::String(val.into_owned())),
+                #[cfg(feature = "raw_value")]
+                Value::Raw(text) => Some(parse_raw_lossy(text).into()),
+                Value::Array(vals) => {
+                    stack.push(Frame::Array(
+                        Vec::with_capacity(vals.len()),
+                        vals.into_iter(),
+                    ));
+                    None
+                }
+                Value::Object(vals) => {
+                    let map = serde_json::Map::new();
+                    stack.push(Frame::Object(map, String::new(), vals.into_iter()));
+                    None
+                }
+            };
+            // Hand the converted value to its parent and find the next value to convert.
+            current = loop {
+                let Some(frame) = stack.last_mut() else {
+                    return converted.expect("the root value was converted");
+                };
+                let next = match frame {
+                    Frame::Array(converted_vals, vals) => {
+                        converted_vals.extend(converted.take());
+                        vals.next()
+                    }
+                    Frame::Object(map, key, vals) => {
+                        if let Some(val) = converted.take() {
+                            map.insert(core::mem::take(key), val);
+                        }
+                        vals.next().map(|(next_key, next_val)| {
+                            *key = next_key.into_owned();
+                            next_val
+                        })
+                    }
+                };
+                match next {
+                    Some(next) => break next,
+                    None => {
+                        converted = Some(match stack.pop() {
+                            Some(Frame::Array(vals, _)) => serde_json::Value::Array(vals),
+                            Some(Frame::Object(map, _, _)) => serde_json::Value::Object(map),
+                            None => unreachable!("the frame was on the stack"),
+                        });
+                    }
+                }
+            };
+        }
+    }
+}
+
+/// Builds a `Value` which borrows all strings and object keys from the `serde_json::Value`.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let owned: serde_json::Value = serde_json::from_str(r#"{"k": ["v", 1]}"#).unwrap();
+/// let value = Value::from(&owned);
+/// assert_eq!(value.get("k").get(0), &Value::Str("v".into()));
+/// ```
+impl<'ctx> From<&'ctx serde_json::Value> for Value<'ctx> {
+    fn from(val: &'ctx serde_json::Value) -> Self {
+        match val {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(val) => Value::Bool(*val),
+            serde_json::Value::Number(val) => Value::Number(val.into()),
+            serde_json::Value::String(val) => Value::Str(Cow::Borrowed(val)),
+            serde_json::Value::Array(vals) => Value::Array(vals.iter().map(Value::from).collect()),
+            serde_json::Value::Object(vals) => Value::Object(
+                vals.iter()
+                    .map(|(key, val)| (Cow::Borrowed(key.as_str()), val.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Parses a `Value` from a `&str`, borrowing strings and object keys from it, like
+/// [`from_str`](crate::from_str).
+///
+/// # Examples
+///
+/// ```
+/// # use serde_json_borrow::Value;
+/// #
+/// let value = Value::try_from(r#"{"k": ["v", 1]}"#).unwrap();
+/// assert_eq!(value.get("k").get(0), &Value::Str("v".into()));
+///
+/// let result: Result<Value, _> = "[1,".try_into();
+/// assert!(result.is_err());
+/// ```
+impl<'ctx> TryFrom<&'ctx str> for Value<'ctx> {
+    type Error = ParseError;
+
+    fn try_from(input: &'ctx str) -> Result<Self, ParseError> {
+        crate::from_str(input)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::FromEnd;
+
+    #[test]
+    fn number_test() -> io::Result<()> {
+        let data = r#"{"val1": 123.5, "val2": 123, "val3": -123}"#;
+        let value: Value = serde_json::from_str(data)?;
+        assert!(value.get("val1").is_f64());
+        assert!(!value.get("val1").is_u64());
+        assert!(!value.get("val1").is_i64());
+
+        assert!(!value.get("val2").is_f64());
+        assert!(value.get("val2").is_u64());
+        assert!(value.get("val2").is_i64());
+
+        assert!(!value.get("val3").is_f64());
+        assert!(!value.get("val3").is_u64());
+        assert!(value.get("val3").is_i64());
+
+        assert!(value.get("val1").as_f64().is_some());
+        assert!(value.get("val2").as_f64().is_some());
+        assert!(value.get("val3").as_f64().is_some());
+
+        assert!(value.get("val1").as_u64().is_none());
+        assert!(value.get("val2").as_u64().is_some());
+        assert!(value.get("val3").as_u64().is_none());
+
+        assert!(value.get("val1").as_i64().is_none());
+        assert!(value.get("val2").as_i64().is_some());
+        assert!(value.get("val3").as_i64().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn as_array_as_object_test() -> io::Result<()> {
+        let data = r#"{"arr": [1, "a"], "obj": {"k": true}, "str": "s"}"#;
+        let value: Value = serde_json::from_str(data)?;
+
+        let arr = value.get("arr").as_array().unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr[1], Value::Str("a".into()));
+        assert!(value.get("obj").as_array().is_none());
+        assert!(value.get("str").as_array().is_none());
+
+        let obj = value.get("obj").as_object().unwrap();
+        assert_eq!(obj, &vec![("k".into(), Value::Bool(true))]);
+        assert!(value.get("arr").as_object().is_none());
+        assert!(value.get("missing").as_object().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_borrows_self_test() -> io::Result<()> {
+        fn lookup<'a, 'ctx>(value: &'a Value<'ctx>, key: &str) -> &'a Value<'ctx> {
+            value.get(key).get(0)
+        }
+
+        let input = String::from(r#"{"a": ["x"]}"#);
+        let found: Value<'_> = {
+            let value: Value = serde_json::from_str(&input)?;
+            lookup(&value, "a").clone()
+        };
+        assert_eq!(found, "x");
+        assert!(found.is_borrowed());
+        Ok(())
+    }
+
+    #[test]
+    fn try_get_test() -> io::Result<()> {
+        let value: Value = serde_json::from_str(r#"{"a": [null, {"b": true}], "a": 1}"#)?;
+        let found = value.try_get("a").and_then(|a| a.try_get(1)?.try_get("b"));
+        assert_eq!(found, Ok(&Value::Bool(true)));
+        assert_eq!(value.get("a").try_get(0), Ok(&Value::Null));
+        assert_eq!(
+            value.try_get("b"),
+            Err(GetError::KeyNotFound("b".to_string()))
+        );
+        assert_eq!(
+            value.get("a").try_get(2),
+            Err(GetError::IndexOutOfBounds { index: 2, len: 2 })
+        );
+        assert_eq!(
+            value.try_get(0),
+            Err(GetError::WrongType {
+                expected: "array",
+                found: "object"
+            })
+        );
+        assert_eq!(
+            Value::Null.try_get("a").unwrap_err().to_string(),
+            "expected object, found null"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn from_end_index_test() -> io::Result<()> {
+        let mut value: Value = serde_json::from_str(r#"[1, 2, [3, 4]]"#)?;
+        assert_eq!(value.get(FromEnd(1)).get(FromEnd(2)), 3);
+        assert_eq!(value.get(FromEnd(3)), 1);
+        assert_eq!(value.get(FromEnd(4)), &Value::Null);
+        assert_eq!(value.get(FromEnd(0)), &Value::Null);
+        assert_eq!(value.get(FromEnd(usize::MAX)), &Value::Null);
+        assert_eq!(Value::Null.get(FromEnd(1)), &Value::Null);
+        // Plain integer literals still index with `usize`.
+        assert_eq!(value.get(3_000_000_000), &Value::Null);
+
+        *value
+            .get_mut(FromEnd(1))
+            .unwrap()
+            .get_mut(FromEnd(1))
+            .unwrap() = Value::Null;
+        assert_eq!(value.to_string(), "[1,2,[3,null]]");
+        assert!(value.get_mut(FromEnd(4)).is_none());
+
+        assert_eq!(value.try_get(FromEnd(2)), Ok(&Value::Number(2u64.into())));
+        let err = value.try_get(FromEnd(4)).unwrap_err();
+        assert_eq!(err, GetError::IndexFromEndOutOfBounds { index: 4, len: 3 });
+        assert_eq!(
+            err.to_string(),
+            "index 4 from the end out of bounds for array of length 3"
+        );
+        assert_eq!(
+            Value::Null.try_get(FromEnd(1)).unwrap_err().to_string(),
+            "expected array, found null"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unwrap_or_test() -> io::Result<()> {
+        let value: Value = serde_json::from_str(r#"{"a": null, "b": false, "c": 0}"#)?;
+        let default = Value::Str("d".into());
+        assert_eq!(value.get("a").unwrap_or(&default), "d");
+        assert_eq!(value.get("b").unwrap_or(&default), &Value::Bool(false));
+        assert_eq!(value.get("c").unwrap_or(&default), 0);
+        assert_eq!(value.get("x").unwrap_or(&default), "d");
+
+        let mut called = false;
+        let found = value.get("b").or_else(|| {
+            called = true;
+            &default
+        });
+        assert_eq!(found, &Value::Bool(false));
+        assert!(!called);
+        assert_eq!(value.get("x").or_else(|| value.get("c")), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn get_mut_test() -> io::Result<()> {
+        let data = r#"{"arr": [1, 2], "obj": {"k": "v"}, "k": 1, "k": 2}"#;
+        let mut value: Value = serde_json::from_str(data)?;
+
+        if let Some(Value::Array(arr)) = value.get_mut("arr") {
+            arr.push(Value::Number(3u64.into()));
+        }
+        assert_eq!(value.get("arr").get(2), &Value::Number(3u64.into()));
+
+        *value.get_mut("obj").unwrap().get_mut("k").unwrap() = Value::Null;
+        assert_eq!(value.get("obj").get("k"), &Value::Null);
+
+        // The first matching key is returned.
+        *value.get_mut("k").unwrap() = Value::Bool(true);
+        assert_eq!(
+            value.iter_object().unwrap().nth(3).unwrap().1,
+            Value::Number(2u64.into())
+        );
+        assert_eq!(value.get("k"), &Value::Bool(true));
+
+        assert!(value.get_mut("missing").is_none());
+        assert!(value.get_mut("arr").unwrap().get_mut(5).is_none());
+        assert!(value.get_mut("arr").unwrap().get_mut("k").is_none());
+        assert!(value.get_mut("obj").unwrap().get_mut(0).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn into_owned_test() -> io::Result<()> {
+        let data = String::from(r#"{"k\"ey": ["a", "b\"c", {"x": 1.5}], "n": null}"#);
+        let value: Value = serde_json::from_str(&data)?;
+        let owned: Value<'static> = value.into_owned();
+        drop(data);
+
+        let expected = Value::Object(vec![
+            (
+                "k\"ey".into(),
+                Value::Array(vec![
+                    Value::Str("a".into()),
+                    Value::Str("b\"c".into()),
+                    Value::Object(vec![("x".into(), Value::Number(1.5.into()))]),
+                ]),
+            ),
+            ("n".into(), Value::Null),
+        ]);
+        assert_eq!(owned, expected);
+        assert!(matches!(
+            owned.get("k\"ey").get(0),
+            Value::Str(Cow::Owned(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort_keys_test() -> io::Result<()> {
+        let data = r#"{"b": 1, "a": [{"z": 1, "y": 2}], "c": 0, "a": 2, "0": null}"#;
+        let mut value: Value = serde_json::from_str(data)?;
+        value.sort_keys();
+
+        let keys: Vec<&str> = value
+            .iter_object()
+            .unwrap()
+            .map(|(k, _)| k.as_ref())
+            .collect();
+        assert_eq!(keys, vec!["0", "a", "a", "b", "c"]);
+        // Duplicate keys keep their order, the first one is found.
+        assert!(value.get_sorted("a").is_array());
+        assert_eq!(
+            value.get_sorted("a").get(0).get_sorted("y"),
+            &Value::Number(2u64.into())
+        );
+        assert_eq!(value.get_sorted("c"), &Value::Number(0u64.into()));
+        assert_eq!(value.get_sorted("0"), &Value::Null);
+        assert_eq!(value.get_sorted("d"), &Value::Null);
+        assert_eq!(value.get_sorted("a").get_sorted("a"), &Value::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sorted_entries_test() -> io::Result<()> {
+        let data = r#"{"b": 1, "a": {"z": 1, "y": 2}, "B": 0, "a": 2, "": null}"#;
+        let value: Value = serde_json::from_str(data)?;
+        let entries = value.sorted_entries().unwrap();
+        let keys: Vec<&str> = entries.iter().map(|(k, _)| *k).collect();
+        assert_eq!(keys, ["", "B", "a", "a", "b"]);
+        // Duplicate keys keep their order, nested objects are unchanged.
+        assert_eq!(entries[2].1.to_string(), r#"{"z":1,"y":2}"#);
+        assert_eq!(entries[3].1, &Value::Number(2u64.into()));
+        assert_eq!(value, serde_json::from_str::<Value>(data)?);
+
+        assert_eq!(Value::Object(Vec::new()).sorted_entries(), Some(Vec::new()));
+        assert_eq!(Value::Array(Vec::new()).sorted_entries(), None);
+        assert_eq!(Value::Null.sorted_entries(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn keys_values_test() -> io::Result<()> {
+        let data = r#"{"a": 1, "b": [true], "a": 2}"#;
+        let value: Value = serde_json::from_str(data)?;
+
+        assert!(value.contains_key("a"));
+        assert!(value.contains_key("b"));
+        assert!(!value.contains_key("c"));
+        assert!(!value.get("b").contains_key("a"));
+
+        assert_eq!(
+            value.keys().unwrap().collect::<Vec<_>>(),
+            vec!["a", "b", "a"]
+        );
+        let values: Vec<&Value> = value.values().unwrap().collect();
+        assert_eq!(values[0], &Value::Number(1u64.into()));
+        assert!(values[1].is_array());
+        assert_eq!(values[2], &Value::Number(2u64.into()));
+
+        assert!(value.get("b").keys().is_none());
+        assert!(value.get("b").values().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn len_is_empty_test() -> io::Result<()> {
+        let data = r#"{"arr": [1, 2], "empty_arr": [], "obj": {"a": 1, "a": 2}, "empty_obj": {}, "s": ""}"#;
+        let value: Value = serde_json::from_str(data)?;
+
+        assert_eq!(value.len(), Some(5));
+        assert_eq!(value.get("arr").len(), Some(2));
+        assert_eq!(value.get("arr").is_empty(), Some(false));
+        assert_eq!(value.get("empty_arr").len(), Some(0));
+        assert_eq!(value.get("empty_arr").is_empty(), Some(true));
+        assert_eq!(value.get("obj").len(), Some(2));
+        assert_eq!(value.get("empty_obj").is_empty(), Some(true));
+        assert_eq!(value.get("s").len(), None);
+        assert_eq!(value.get("missing").is_empty(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_serde_json_value_test() -> io::Result<()> {
+        let data = r#"{"a": [1, -2, 3.5, true, null], "b": {"c": "d"}}"#;
+        let owned: serde_json::Value = serde_json::from_str(data)?;
+        let value = Value::from(&owned);
+
+        let expected: Value = serde_json::from_str(data)?;
+        assert_eq!(value, expected);
+        assert!(value.get("a").get(0).is_u64());
+        assert!(value.get("a").get(1).is_i64());
+        assert!(value.get("a").get(2).is_f64());
+        assert!(matches!(
+            value.get("b").get("c"),
+            Value::Str(Cow::Borrowed("d"))
+        ));
+        assert_eq!(serde_json::Value::from(value), owned);
+
+        Ok(())
+    }
+
+    #[test]
+    fn take_test() -> io::Result<()> {
+        let data = r#"{"a": {"b": [1, 2]}, "c": "d"}"#;
+        let mut value: Value = serde_json::from_str(data)?;
+
+        let b = value.pointer_mut("/a/b").unwrap().take();
+        assert_eq!(b.get(1), 2);
+        assert_eq!(value.get("a").get("b"), &Value::Null);
+        assert_eq!(value.get_mut("c").unwrap().take(), "d");
+
+        let whole = value.take();
+        assert!(whole.is_object());
+        assert!(value.is_null());
+        assert!(value.take().is_null());
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_path_test() -> io::Result<()> {
+        let data = r#"{"users": [{"name": "a", "tags": ["x", "y"]}, {"name": "b"}]}"#;
+        let value: Value = serde_json::from_str(data)?;
+
+        let path: Vec<PathSegment> = vec!["users".into(), 0.into(), "tags".into(), 1.into()];
+        assert_eq!(value.get_path(path), "y");
+        assert_eq!(
+            value
+                .get_path(vec![PathSegment::Key("users"), PathSegment::Index(1)])
+                .get("name"),
+            "b"
+        );
+        let key = String::from("users");
+        assert_eq!(value.get_path([&key]).len(), Some(2));
+        assert_eq!(value.get_path(Vec::<PathSegment>::new()), &value);
+
+        assert_eq!(value.get_path(["missing"]), &Value::Null);
+        assert_eq!(value.get_path([PathSegment::Index(0)]), &Value::Null);
+        assert_eq!(
+            value.get_path(vec!["users".into(), PathSegment::Index(2)]),
+            &Value::Null
+        );
+        assert_eq!(value.get_path(["users", "name"]), &Value::Null);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ord_test() -> io::Result<()> {
+        let data = r#"[
+            {"b": 1}, {"a": 2}, {"a": 1, "b": 1}, {"a": 1},
+            [1, 2], [1], [], [0, 5],
+            "b", "ab", "a", "",
+            2, -1, 1.5, 1, 1.0,
+            true, false, null
+        ]"#;
+        let mut values: Vec<Value> = serde_json::from_str(data)?;
+        values.sort();
+        assert_eq!(
+            Value::Array(values).to_string(),
+            r#"[null,false,true,-1,1,1.0,1.5,2,"","a","ab","b",[],[0,5],[1],[1,2],{"a":1},{"a":1,"b":1},{"a":2},{"b":1}]"#
+        );
+
+        let a: Value = serde_json::from_str(r#"{"x": [1, {"y": null}]}"#)?;
+        let b: Value = serde_json::from_str(r#"{"x": [1, {"y": null}]}"#)?;
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+
+        Ok(())
+    }
+
+    #[test]
+    fn hash_test() -> io::Result<()> {
+        use std::collections::HashSet;
+
+        let values: Vec<Value> = serde_json::from_str(
+            r#"[null, true, 1, 1, 1.0, -0.0, 0.0, "a", "a", [1, "a"], [1, "a"], {"a": 1, "b": 2}, {"a": 1, "b": 2}, {"b": 2, "a": 1}]"#,
+        )?;
+        let set: HashSet<&Value> = values.iter().collect();
+        assert_eq!(set.len(), 9);
+        assert!(set.contains(&Value::Number(1u64.into())));
+        assert!(set.contains(&Value::Number((-0.0).into())));
+        assert!(set.contains(&Value::Str("a".into())));
+
+        Ok(())
+    }
+
+    #[test]
+    fn semantic_eq_test() -> io::Result<()> {
+        let a: Value = serde_json::from_str(r#"{"a": 1, "b": {"c": [1, 2], "d": null}}"#)?;
+        let b: Value = serde_json::from_str(r#"{"b": {"d": null, "c": [1, 2]}, "a": 1}"#)?;
+        assert!(a.semantic_eq(&b));
+        assert!(b.semantic_eq(&a));
+        assert_ne!(a, b);
+
+        // Array order still matters.
+        let c: Value = serde_json::from_str(r#"{"a": 1, "b": {"c": [2, 1], "d": null}}"#)?;
+        assert!(!a.semantic_eq(&c));
+
+        // Duplicate keys are compared as a multiset.
+        let dup1: Value = serde_json::from_str(r#"{"a": 1, "a": 2, "b": 3}"#)?;
+        let dup2: Value = serde_json::from_str(r#"{"a": 2, "b": 3, "a": 1}"#)?;
+        let dup3: Value = serde_json::from_str(r#"{"a": 1, "a": 1, "b": 3}"#)?;
+        let single: Value = serde_json::from_str(r#"{"a": 1}"#)?;
+        let single_dup: Value = serde_json::from_str(r#"{"a": 1, "a": 1}"#)?;
+        assert!(dup1.semantic_eq(&dup2));
+        assert!(!dup1.semantic_eq(&dup3));
+        assert!(!dup3.semantic_eq(&dup1));
+        assert!(!single.semantic_eq(&single_dup));
+
+        // Numbers are compared by value.
+        let float: Value = serde_json::from_str(r#"{"n": [1.0, -2.0, 0.5]}"#)?;
+        let int: Value = serde_json::from_str(r#"{"n": [1, -2, 0.5]}"#)?;
+        let other: Value = serde_json::from_str(r#"{"n": [1, -2, 1]}"#)?;
+        assert!(float.semantic_eq(&int));
+        assert!(!float.semantic_eq(&other));
+
+        assert!(Value::Null.semantic_eq(&Value::Null));
+        assert!(!Value::Null.semantic_eq(&Value::Bool(false)));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "raw_value")]
+    #[test]
+    fn raw_lossy_conversions_test() {
+        let valid = Value::Array(vec![Value::Raw(r#"{"b": 1.0, "a": 0}"#)]);
+        assert_eq!(valid.to_string(), r#"[{"b": 1.0, "a": 0}]"#);
+        assert_eq!(valid.clone().into_owned().get(0).get("b"), 1.0);
+        assert_eq!(valid.to_canonical_string(), r#"[{"a":0,"b":1}]"#);
+        assert!(!Value::Raw("0").is_truthy());
+
+        // Invalid raw text is treated as a string by everything but `parse`.
+        let invalid = Value::Array(vec![Value::Raw("{oops")]);
+        let as_str = Value::Array(vec![Value::Str("{oops".into())]);
+        assert_eq!(invalid.clone().into_owned(), as_str);
+        assert_eq!(invalid.to_owned_value(), as_str);
+        assert!(invalid.get(0).is_truthy());
+        assert_eq!(
+            serde_json::Value::from(invalid.clone()),
+            serde_json::json!(["{oops"])
+        );
+        let mut out = String::new();
+        invalid
+            .write_to(&mut out, &crate::WriteConfig::default())
+            .unwrap();
+        assert_eq!(out, r#"["{oops"]"#);
+        assert_eq!(invalid.to_canonical_string(), r#"["{oops"]"#);
+        assert_eq!(serde_json::to_string(&invalid).unwrap(), r#"["{oops"]"#);
+        assert_eq!(invalid.to_string(), r#"["{oops"]"#);
+        assert_eq!(invalid.to_string_pretty(), "[\n  \"{oops\"\n]");
+        // Only parsing fails.
+        assert!(invalid.get(0).parse().is_err());
+    }
+
+    #[test]
+    fn is_borrowed_test() -> io::Result<()> {
+        let value: Value = serde_json::from_str(r#"{"a": {"b": [null, true, 1.5, "c"]}}"#)?;
+        assert!(value.is_borrowed());
+
+        let value: Value = serde_json::from_str(r#"{"a": {"b\"c": 1}, "d": "e"}"#)?;
+        assert!(!value.is_borrowed());
+        assert!(!value.get("a").is_borrowed());
+        assert!(value.get("d").is_borrowed());
+
+        assert!(!Value::Str(Cow::Owned("owned".to_string())).is_borrowed());
+        assert!(!value.clone().into_owned().get("d").is_borrowed());
+
+        Ok(())
+    }
+
+    #[test]
+    fn default_test() -> io::Result<()> {
+        #[derive(Default)]
+        struct Holder<'a> {
+            value: Value<'a>,
+        }
+        assert_eq!(Holder::default().value, Value::Null);
+
+        let mut data: Value = serde_json::from_str(r#"{"a": [1]}"#)?;
+        let a = std::mem::take(data.get_mut("a").unwrap());
+        assert_eq!(a.to_string(), "[1]");
+        assert_eq!(data.to_string(), r#"{"a":null}"#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn heap_size_test() -> io::Result<()> {
+        assert_eq!(Value::Null.heap_size(), 0);
+        assert_eq!(Value::Str("borrowed".into()).heap_size(), 0);
+        let owned = String::with_capacity(32);
+        assert_eq!(Value::Str(Cow::Owned(owned)).heap_size(), 32);
+
+        let value: Value = serde_json::from_str(r#"{"a": [1, "b"], "c": "d"}"#)?;
+        let object =
+            value.as_object().unwrap().capacity() * std::mem::size_of::<(Cow<str>, Value)>();
+        let array = value.get("a").as_array().unwrap().capacity() * std::mem::size_of::<Value>();
+        assert_eq!(value.heap_size(), object + array);
+
+        let escaped: Value = serde_json::from_str(r#"{"k\"ey": "v\"al"}"#)?;
+        let object =
+            escaped.as_object().unwrap().capacity() * std::mem::size_of::<(Cow<str>, Value)>();
+        assert!(escaped.heap_size() >= object + "k\"ey".len() + "v\"al".len());
+
+        Ok(())
+    }
+
+    /// Drops a deeply nested value without recursion, which dropping a `Value` itself would use.
+    fn drop_iteratively(val: Value) {
+        let mut stack = vec![val];
+        while let Some(val) = stack.pop() {
+            match val {
+                Value::Array(vals) => stack.extend(vals),
+                Value::Object(vals) => stack.extend(vals.into_iter().map(|(_, val)| val)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Drops a deeply nested `serde_json::Value` without recursion, see [`drop_iteratively`].
+    fn drop_serde_json_iteratively(val: serde_json::Value) {
+        let mut stack = vec![val];
+        while let Some(val) = stack.pop() {
+            match val {
+                serde_json::Value::Array(vals) => stack.extend(vals),
+                serde_json::Value::Object(vals) => {
+                    stack.extend(vals.into_iter().map(|(_, val)| val))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Nests arrays and objects alternately, e.g. `[{"k": [{"k": 1}]}]`.
+    fn deeply_nested(depth: usize) -> Value<'static> {
+        let mut val = Value::Number(1u64.into());
+        for i in 0..depth {
+            val = if i % 2 == 0 {
+                Value::Object(vec![("k".into(), val)])
+            } else {
+                Value::Array(vec![val])
+            };
+        }
+        val
+    }
+
+    #[test]
+    fn deeply_nested_conversion_test() {
+        const DEPTH: usize = 100_000;
+
+        let converted = serde_json::Value::from(deeply_nested(DEPTH));
+        let mut depth = 0;
+        let mut cur = &converted;
+        loop {
+            cur = match cur {
+                serde_json::Value::Array(vals) => &vals[0],
+                serde_json::Value::Object(map) => &map["k"],
+                _ => break,
+            };
+            depth += 1;
+        }
+        assert_eq!(depth, DEPTH);
+        assert_eq!(cur, &serde_json::Value::from(1u64));
+        drop_serde_json_iteratively(converted);
+
+        let value = deeply_nested(DEPTH);
+        let debug = format!("{:?}", value);
+        assert!(debug.starts_with("Array [Object [(\"k\", Array ["));
+        assert!(debug.contains("Array [Object [(\"k\", Number(1))]]"));
+        assert!(debug.ends_with("])]])]]"));
+        let object_len = r#"Object [("k", )]"#.len();
+        let array_len = "Array []".len();
+        assert_eq!(
+            debug.len(),
+            DEPTH / 2 * (object_len + array_len) + "Number(1)".len()
+        );
+
+        let json = value.to_string();
+        assert!(json.starts_with(r#"[{"k":[{"k":"#));
+        assert!(json.ends_with("}]}]"));
+        assert_eq!(json.len(), DEPTH / 2 * (r#"{"k":}"#.len() + "[]".len()) + 1);
+        let mut out = String::new();
+        value
+            .write_to(&mut out, &crate::WriteConfig::default())
+            .unwrap();
+        assert_eq!(out, json);
+        assert_eq!(value.to_canonical_string(), json);
+        drop_iteratively(value);
+    }
+
+    #[test]
+    fn conversion_test() -> io::Result<()> {
+        let data = r#"{"a": [1, -2, 1.5, "s", null, true, {}], "b": {"c": []}, "a": "last"}"#;
+        let value: Value = serde_json::from_str(data)?;
+        let expected: serde_json::Value = serde_json::from_str(data)?;
+        assert_eq!(serde_json::Value::from(value), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn try_from_str_test() -> io::Result<()> {
+        let input = String::from(r#"{"a": ["b", 1.5, null]}"#);
+        let value = Value::try_from(input.as_str()).unwrap();
+        assert_eq!(value, serde_json::from_str::<Value>(&input)?);
+        assert!(value.is_borrowed());
+
+        let err = Value::try_from(r#"{"a" 1}"#).unwrap_err();
+        assert_eq!(err.offset(), Some(5));
+        assert!(Value::try_from("").is_err());
+        assert!(Value::try_from("1 2").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn debug_test() -> io::Result<()> {
+        let value: Value = serde_json::from_str(r#"{"a": [1, "b", null], "c": {}, "d": [true]}"#)?;
+        assert_eq!(
+            format!("{:?}", value),
+            r#"Object [("a", Array [Number(1), Str("b"), Null]), ("c", Object []), ("d", Array [Bool(true)])]"#
+        );
+        let pretty = r#"Object [
+    (
+        "a",
+        Array [
+            Number(1),
+            Str("b"),
+            Null,
+        ],
+    ),
+    (
+        "c",
+        Object [],
+    ),
+    (
+        "d",
+        Array [
+            Bool(true),
+        ],
+    ),
+]"#;
+        assert_eq!(format!("{:#?}", value), pretty);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_owned_value_test() -> io::Result<()> {
+        let data = String::from(r#"{"a": ["b", {"c\"d": 1.5}], "e": null}"#);
+        let value: Value = serde_json::from_str(&data)?;
+
+        let owned = value.to_owned_value();
+        assert_eq!(owned, value);
+        assert!(value.get("a").get(0).is_borrowed());
+        assert!(!owned.get("a").get(0).is_borrowed());
+        assert!(owned
+            .iter_object()
+            .unwrap()
+            .all(|(k, _)| matches!(k, Cow::Owned(_))));
+
+        drop(value);
+        drop(data);
+        assert_eq!(owned.to_string(), r#"{"a":["b",{"c\"d":1.5}],"e":null}"#);
+
+        Ok(())
+    }
+
+    #[test]
+    fn as_mut_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(r#"{"a": [1, 2], "b": {"c": true}}"#)?;
+        assert!(data.as_array_mut().is_none());
+        let entries = data.as_object_mut().unwrap();
+        entries.retain(|(key, _)| key != "b");
+        entries.push(("d".into(), Value::Null));
+
+        let arr = data
+            .get_or_insert_with("a", || Value::Null)
+            .as_array_mut()
+            .unwrap();
+        arr.drain(..1);
+        assert!(Value::Null.as_object_mut().is_none());
+        assert_eq!(data.to_string(), r#"{"a":[2],"d":null}"#);
+        Ok(())
+    }
+
+    #[test]
+    fn into_container_test() -> io::Result<()> {
+        let json = r#"{"a": [1, 2], "b": "text", "c": null}"#;
+        let data: Value = serde_json::from_str(json)?;
+        let mut entries = data.into_object().unwrap();
+        let (key, c) = entries.pop().unwrap();
+        assert_eq!(key, "c");
+        assert_eq!(c.into_string(), Err(Value::Null));
+        let (_, b) = entries.pop().unwrap();
+        let text = b.into_string().unwrap();
+        assert!(matches!(text, Cow::Borrowed("text")));
+        let (_, a) = entries.pop().unwrap();
+        assert_eq!(a.clone().into_object(), Err(a.clone()));
+        let arr = a.into_array().unwrap();
+        assert_eq!(
+            arr,
+            [Value::Number(1u64.into()), Value::Number(2u64.into())]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn lenient_accessors_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(
+            r#"[-7, "-7", "9223372036854775808", "1e3", " 1", "+1", "", true, "true", null]"#,
+        )?;
+        let ints: Vec<_> = data
+            .iter_array()
+            .unwrap()
+            .map(Value::as_i64_lenient)
+            .collect();
+        assert_eq!(
+            ints,
+            [
+                Some(-7),
+                Some(-7),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None
+            ]
+        );
+        let floats: Vec<_> = data
+            .iter_array()
+            .unwrap()
+            .map(Value::as_f64_lenient)
+            .collect();
+        assert_eq!(
+            floats,
+            [
+                Some(-7.0),
+                Some(-7.0),
+                Some(9223372036854775808.0),
+                Some(1000.0),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None
+            ]
+        );
+        let bools: Vec<_> = data
+            .iter_array()
+            .unwrap()
+            .map(Value::as_bool_lenient)
+            .collect();
+        assert_eq!(&bools[6..], [None, Some(true), Some(true), None]);
+        assert_eq!(Value::Str("1e400".into()).as_f64_lenient(), None);
+        // The strict accessors are unchanged.
+        assert_eq!(data.get(1).as_i64(), None);
+        assert_eq!(data.get(8).as_bool(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn is_truthy_test() -> io::Result<()> {
+        let falsy: Value = serde_json::from_str(r#"[null, false, 0, -0, 0.0, -0.0, 0e5, ""]"#)?;
+        for value in falsy.iter_array().unwrap() {
+            assert!(!value.is_truthy(), "{}", value);
+        }
+        let truthy: Value =
+            serde_json::from_str(r#"[true, 1, -1, 0.5, 1e-300, "0", "false", " ", [], {}, [0]]"#)?;
+        for value in truthy.iter_array().unwrap() {
+            assert!(value.is_truthy(), "{}", value);
+        }
+        assert!(!Value::Number(f64::NAN.into()).is_truthy());
+        assert!(Value::Number(u128::MAX.into()).is_truthy());
+        assert!(Value::Number(i128::MIN.into()).is_truthy());
+        Ok(())
+    }
+
+    #[test]
+    fn normalize_numbers_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(
+            r#"[1.0, -0.0, -3.0, 0.5, 1e19, -1e19, 1e300, 18446744073709551615, "1.0"]"#,
+        )?;
+        data.normalize_numbers();
+        assert_eq!(
+            data.to_string(),
+            r#"[1,0,-3,0.5,10000000000000000000,-10000000000000000000,1e+300,18446744073709551615,"1.0"]"#
+        );
+        assert!(data.get(4).is_u64());
+        assert!(data.get(5).is_i128());
+        assert!(data.get(6).is_f64());
+
+        let mut floats = data.clone();
+        floats.floatify_numbers();
+        assert!(floats.iter_array().unwrap().take(8).all(Value::is_f64));
+        assert_eq!(
+            floats.to_string(),
+            r#"[1.0,0.0,-3.0,0.5,1e+19,-1e+19,1e+300,1.8446744073709552e+19,"1.0"]"#
+        );
+        floats.normalize_numbers();
+        assert_eq!(floats.get(1), 0);
+        // u64::MAX is rounded to 2^64 as a float.
+        assert_eq!(floats.get(7).as_u128(), Some(1 << 64));
+        Ok(())
+    }
+
+    #[test]
+    fn truncate_depth_test() -> io::Result<()> {
+        let input = r#"[1, [2, [3, [4]]], {"a": {"b": {}}}, "s"]"#;
+        let truncated = |max_depth| -> io::Result<String> {
+            let mut value: Value = serde_json::from_str(input)?;
+            value.truncate_depth(max_depth);
+            Ok(value.to_string())
+        };
+        assert_eq!(truncated(0)?, r#""…""#);
+        assert_eq!(truncated(1)?, r#"[1,"…","…","s"]"#);
+        assert_eq!(truncated(2)?, r#"[1,[2,"…"],{"a":"…"},"s"]"#);
+        assert_eq!(truncated(3)?, r#"[1,[2,[3,"…"]],{"a":{"b":"…"}},"s"]"#);
+        assert_eq!(truncated(4)?, r#"[1,[2,[3,[4]]],{"a":{"b":{}}},"s"]"#);
+
+        let mut value = Value::Bool(true);
+        value.truncate_depth(0);
+        assert_eq!(value, Value::Bool(true));
+        Ok(())
+    }
+
+    #[test]
+    fn structural_metrics_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"[1, [2, [3]], {"a": {"b": {}}, "c": "s"}, []]"#)?;
+        assert_eq!(data.count_nodes(), 11);
+        assert_eq!(data.max_depth(), 4);
+        assert_eq!(Value::Null.count_nodes(), 1);
+        assert_eq!(Value::Null.max_depth(), 0);
+        assert_eq!(Value::Array(Vec::new()).max_depth(), 1);
+
+        let depth = 100_000;
+        let deep = deeply_nested(depth);
+        assert_eq!(deep.count_nodes(), depth + 1);
+        assert_eq!(deep.max_depth(), depth);
+        drop_iteratively(deep);
+        Ok(())
+    }
+
+    #[test]
+    fn as_null_test() -> io::Result<()> {
+        let value: Value = serde_json::from_str(r#"{"a": null, "b": false}"#)?;
+        assert_eq!(value.get("a").as_null(), Some(()));
+        assert_eq!(value.get("b").as_null(), None);
+        assert_eq!(value.get("missing").as_null(), Some(()));
+        assert_eq!(value.as_null(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn iter_mut_test() -> io::Result<()> {
+        let mut value: Value = serde_json::from_str(r#"{"a": [1, 2], "b": "c"}"#)?;
+
+        for (key, val) in value.iter_object_mut().unwrap() {
+            if key == "a" {
+                for elem in val.iter_array_mut().unwrap() {
+                    *elem = Value::Number((elem.as_u64().unwrap() * 10).into());
+                }
+            }
+        }
+        assert_eq!(value.to_string(), r#"{"a":[10,20],"b":"c"}"#);
+
+        assert!(value.iter_array_mut().is_none());
+        assert!(value.get_mut("a").unwrap().iter_object_mut().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_iterator_test() -> io::Result<()> {
+        let value: Value = (1..=3u64).map(|n| Value::Number(n.into())).collect();
+        assert_eq!(value.to_string(), "[1,2,3]");
+
+        let keys = [String::from("x"), String::from("y")];
+        let value: Value = keys.iter().map(|k| (k.as_str(), Value::Null)).collect();
+        assert_eq!(value.to_string(), r#"{"x":null,"y":null}"#);
+        assert!(value.is_borrowed());
+
+        let value: Value = vec![
+            (String::from("a"), Value::Bool(true)),
+            (String::from("a"), Value::Null),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(value.to_string(), r#"{"a":true,"a":null}"#);
+
+        let empty: Value = std::iter::empty::<Value>().collect();
+        assert_eq!(empty, Value::Array(Vec::new()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn constructors_test() -> io::Result<()> {
+        let value = Value::object([
+            ("s", Value::string("x")),
+            ("n", Value::number(u64::MAX)),
+            ("f", Value::number(-0.5)),
+            ("b", Value::bool(false)),
+            ("a", Value::array((1..=2u64).map(Value::number))),
+        ]);
+        let expected: Value = serde_json::from_str(
+            r#"{"s":"x","n":18446744073709551615,"f":-0.5,"b":false,"a":[1,2]}"#,
+        )?;
+        assert_eq!(value, expected);
+        assert!(value.is_borrowed());
+        assert_eq!(Value::string(String::from("x")), Value::Str("x".into()));
+        assert_eq!(Value::array([]), Value::Array(Vec::new()));
+        assert_eq!(
+            Value::object(std::iter::empty::<(String, Value)>()),
+            Value::Object(Vec::new())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn extend_test() -> io::Result<()> {
+        let mut value: Value = serde_json::from_str("[1]")?;
+        value.extend((2..=3u64).map(|n| Value::Number(n.into())));
+        assert_eq!(value.to_string(), "[1,2,3]");
+
+        let input = String::from(r#"{"a":1}"#);
+        let mut value: Value = serde_json::from_str(&input)?;
+        value.extend([("a", Value::Null), ("b", Value::Bool(true))]);
+        assert_eq!(value.to_string(), r#"{"a":1,"a":null,"b":true}"#);
+        assert!(value.is_borrowed());
+
+        let mut value = Value::Null;
+        value.extend(std::iter::empty::<Value>());
+        assert_eq!(value, Value::Array(Vec::new()));
+        let mut value = Value::Null;
+        value.extend(std::iter::empty::<(&str, Value)>());
+        assert_eq!(value, Value::Object(Vec::new()));
 
         Ok(())
     }
+
+    #[test]
+    #[should_panic(expected = "neither an array nor null")]
+    fn extend_array_panics_test() {
+        let mut value = Value::Object(Vec::new());
+        value.extend([Value::Null]);
+    }
+
+    #[test]
+    #[should_panic(expected = "neither an object nor null")]
+    fn extend_object_panics_test() {
+        let mut value = Value::Bool(true);
+        value.extend([("a", Value::Null)]);
+    }
 }
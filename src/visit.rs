@@ -0,0 +1,367 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::index::PathSegment;
+use crate::num::Number;
+use crate::Value;
+
+/// Callbacks for a depth-first traversal of a [`Value`], see [`Value::visit`].
+///
+/// Every method receives the path from the root to the current value and has an empty default
+/// implementation, so implementors only need to override the callbacks they are interested in.
+/// For arrays and objects, `enter_*` is called before their children are visited and `leave_*`
+/// afterwards.
+///
+/// # Examples
+///
+/// Counting the string leaves of a document:
+///
+/// ```
+/// use serde_json_borrow::{PathSegment, Value, Visitor};
+///
+/// struct StringCounter(usize);
+///
+/// impl Visitor for StringCounter {
+///     fn visit_str(&mut self, _path: &[PathSegment<'_>], _value: &str) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let data: Value = serde_json::from_str(r#"{"a": "x", "b": [1, "y", {"c": "z"}]}"#).unwrap();
+/// let mut counter = StringCounter(0);
+/// data.visit(&mut counter);
+/// assert_eq!(counter.0, 3);
+/// ```
+pub trait Visitor {
+    /// Called for a null value.
+    fn visit_null(&mut self, _path: &[PathSegment<'_>]) {}
+
+    /// Called for a boolean value.
+    fn visit_bool(&mut self, _path: &[PathSegment<'_>], _value: bool) {}
+
+    /// Called for a number value.
+    fn visit_number(&mut self, _path: &[PathSegment<'_>], _value: &Number) {}
+
+    /// Called for a string value.
+    fn visit_str(&mut self, _path: &[PathSegment<'_>], _value: &str) {}
+
+    /// Called for an unparsed value with its verbatim text. Requires the `raw_value` feature.
+    #[cfg(feature = "raw_value")]
+    fn visit_raw(&mut self, _path: &[PathSegment<'_>], _text: &str) {}
+
+    /// Called before the elements of an array are visited.
+    fn enter_array(&mut self, _path: &[PathSegment<'_>], _array: &[Value<'_>]) {}
+
+    /// Called after the elements of an array were visited.
+    fn leave_array(&mut self, _path: &[PathSegment<'_>], _array: &[Value<'_>]) {}
+
+    /// Called before the entries of an object are visited.
+    fn enter_object(&mut self, _path: &[PathSegment<'_>], _object: &[(Cow<'_, str>, Value<'_>)]) {}
+
+    /// Called after the entries of an object were visited.
+    fn leave_object(&mut self, _path: &[PathSegment<'_>], _object: &[(Cow<'_, str>, Value<'_>)]) {}
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Traverses the `Value` depth-first in document order, calling the matching [`Visitor`]
+    /// callback for every value, including `self`.
+    ///
+    /// The path passed to the callbacks is empty for `self`. Elements of arrays add a
+    /// [`PathSegment::Index`] and entries of objects a [`PathSegment::Key`].
+    pub fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        let mut path = Vec::new();
+        self.visit_at(&mut path, visitor);
+    }
+
+    fn visit_at<'a, V: Visitor + ?Sized>(
+        &'a self,
+        path: &mut Vec<PathSegment<'a>>,
+        visitor: &mut V,
+    ) {
+        match self {
+            Value::Null => visitor.visit_null(path),
+            Value::Bool(b) => visitor.visit_bool(path, *b),
+            Value::Number(n) => visitor.visit_number(path, n),
+            Value::Str(s) => visitor.visit_str(path, s),
+            #[cfg(feature = "raw_value")]
+            Value::Raw(text) => visitor.visit_raw(path, text),
+            Value::Array(arr) => {
+                visitor.enter_array(path, arr);
+                for (i, val) in arr.iter().enumerate() {
+                    path.push(PathSegment::Index(i));
+                    val.visit_at(path, visitor);
+                    path.pop();
+                }
+                visitor.leave_array(path, arr);
+            }
+            Value::Object(obj) => {
+                visitor.enter_object(path, obj);
+                for (key, val) in obj {
+                    path.push(PathSegment::Key(key));
+                    val.visit_at(path, visitor);
+                    path.pop();
+                }
+                visitor.leave_object(path, obj);
+            }
+        }
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Applies `f` to every string value in the tree, in place.
+    ///
+    /// The closure receives the `Cow` itself, so it can modify an owned string directly or replace
+    /// a borrowed string with an owned one, e.g. via `Cow::to_mut`. Strings which aren't modified
+    /// stay borrowed. Object keys are not visited, see [`map_keys_mut`](Value::map_keys_mut).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"user": " alice ", "tags": ["a", "b "]}"#).unwrap();
+    ///
+    /// data.map_strings_mut(|s| {
+    ///     if s.trim().len() != s.len() {
+    ///         *s = s.trim().to_string().into();
+    ///     }
+    /// });
+    /// assert_eq!(data.to_string(), r#"{"user":"alice","tags":["a","b"]}"#);
+    /// ```
+    pub fn map_strings_mut<F>(&mut self, mut f: F)
+    where F: FnMut(&mut Cow<'ctx, str>) {
+        self.map_strings_mut_with(&mut f);
+    }
+
+    fn map_strings_mut_with<F>(&mut self, f: &mut F)
+    where F: FnMut(&mut Cow<'ctx, str>) {
+        match self {
+            Value::Str(s) => f(s),
+            Value::Array(arr) => arr.iter_mut().for_each(|val| val.map_strings_mut_with(f)),
+            Value::Object(obj) => obj
+                .iter_mut()
+                .for_each(|(_, val)| val.map_strings_mut_with(f)),
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => {}
+        }
+    }
+
+    /// Builds a new owned tree with the same arrays, objects and keys, replacing every scalar leaf
+    /// with the result of `f`.
+    ///
+    /// Leaves are visited in document order. Null, booleans, numbers and strings are leaves, empty
+    /// arrays and objects are kept as they are. Keys are copied, since the result doesn't borrow
+    /// from the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let data: Value = serde_json::from_str(r#"{"id": 1, "tags": ["a", 2.5], "on": true}"#).unwrap();
+    ///
+    /// // Stringify all numbers.
+    /// let mapped = data.map_leaves(|leaf| match leaf {
+    ///     Value::Number(n) => Value::Str(n.to_string().into()),
+    ///     other => other.to_owned_value(),
+    /// });
+    /// assert_eq!(mapped.to_string(), r#"{"id":"1","tags":["a","2.5"],"on":true}"#);
+    /// ```
+    pub fn map_leaves<F>(&self, mut f: F) -> Value<'static>
+    where F: FnMut(&Value<'ctx>) -> Value<'static> {
+        self.map_leaves_with(&mut f)
+    }
+
+    fn map_leaves_with<F>(&self, f: &mut F) -> Value<'static>
+    where F: FnMut(&Value<'ctx>) -> Value<'static> {
+        match self {
+            Value::Array(arr) => {
+                Value::Array(arr.iter().map(|val| val.map_leaves_with(f)).collect())
+            }
+            Value::Object(obj) => Value::Object(
+                obj.iter()
+                    .map(|(key, val)| (Cow::Owned(key.to_string()), val.map_leaves_with(f)))
+                    .collect(),
+            ),
+            leaf => f(leaf),
+        }
+    }
+
+    /// Applies `f` to every object key in the tree, in place.
+    ///
+    /// Like [`map_strings_mut`](Value::map_strings_mut), but for keys instead of string values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_json_borrow::Value;
+    /// #
+    /// let mut data: Value = serde_json::from_str(r#"{"Name": "A", "Tags": [{"Key": "B"}]}"#).unwrap();
+    ///
+    /// data.map_keys_mut(|key| *key = key.to_lowercase().into());
+    /// assert_eq!(data.to_string(), r#"{"name":"A","tags":[{"key":"B"}]}"#);
+    /// ```
+    pub fn map_keys_mut<F>(&mut self, mut f: F)
+    where F: FnMut(&mut Cow<'ctx, str>) {
+        self.map_keys_mut_with(&mut f);
+    }
+
+    fn map_keys_mut_with<F>(&mut self, f: &mut F)
+    where F: FnMut(&mut Cow<'ctx, str>) {
+        match self {
+            Value::Array(arr) => arr.iter_mut().for_each(|val| val.map_keys_mut_with(f)),
+            Value::Object(obj) => obj.iter_mut().for_each(|(key, val)| {
+                f(key);
+                val.map_keys_mut_with(f);
+            }),
+            Value::Null | Value::Bool(_) | Value::Number(_) | Value::Str(_) => {}
+            #[cfg(feature = "raw_value")]
+            Value::Raw(_) => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    /// Records every callback as a line of text.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    fn fmt_path(path: &[PathSegment<'_>]) -> String {
+        path.iter()
+            .map(|segment| match segment {
+                PathSegment::Key(key) => format!(".{}", key),
+                PathSegment::Index(i) => format!("[{}]", i),
+            })
+            .collect()
+    }
+
+    impl Visitor for Recorder {
+        fn visit_null(&mut self, path: &[PathSegment<'_>]) {
+            self.0.push(format!("{} null", fmt_path(path)));
+        }
+
+        fn visit_bool(&mut self, path: &[PathSegment<'_>], value: bool) {
+            self.0.push(format!("{} {}", fmt_path(path), value));
+        }
+
+        fn visit_number(&mut self, path: &[PathSegment<'_>], value: &Number) {
+            self.0.push(format!("{} {:?}", fmt_path(path), value));
+        }
+
+        fn visit_str(&mut self, path: &[PathSegment<'_>], value: &str) {
+            self.0.push(format!("{} {:?}", fmt_path(path), value));
+        }
+
+        fn enter_array(&mut self, path: &[PathSegment<'_>], array: &[Value<'_>]) {
+            self.0
+                .push(format!("{} enter array {}", fmt_path(path), array.len()));
+        }
+
+        fn leave_array(&mut self, path: &[PathSegment<'_>], _array: &[Value<'_>]) {
+            self.0.push(format!("{} leave array", fmt_path(path)));
+        }
+
+        fn enter_object(&mut self, path: &[PathSegment<'_>], object: &[(Cow<'_, str>, Value<'_>)]) {
+            self.0
+                .push(format!("{} enter object {}", fmt_path(path), object.len()));
+        }
+
+        fn leave_object(
+            &mut self,
+            path: &[PathSegment<'_>],
+            _object: &[(Cow<'_, str>, Value<'_>)],
+        ) {
+            self.0.push(format!("{} leave object", fmt_path(path)));
+        }
+    }
+
+    #[test]
+    fn visit_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [null, true], "b": {"c": 1, "d": "e"}}"#)?;
+        let mut recorder = Recorder::default();
+        data.visit(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            [
+                " enter object 2",
+                ".a enter array 2",
+                ".a[0] null",
+                ".a[1] true",
+                ".a leave array",
+                ".b enter object 2",
+                ".b.c Number(1)",
+                ".b.d \"e\"",
+                ".b leave object",
+                " leave object",
+            ]
+        );
+
+        let mut recorder = Recorder::default();
+        Value::Str("root".into()).visit(&mut recorder);
+        assert_eq!(recorder.0, [" \"root\""]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_strings_mut_test() -> io::Result<()> {
+        let mut data: Value = serde_json::from_str(
+            r#"{"password": "secret", "list": ["a", {"token": "t"}], "n": 1}"#,
+        )?;
+
+        let mut count = 0;
+        data.map_strings_mut(|s| {
+            count += 1;
+            if s.starts_with('s') || *s == "t" {
+                s.to_mut().replace_range(.., "***");
+            }
+        });
+        assert_eq!(count, 3);
+        assert_eq!(
+            data.to_string(),
+            r#"{"password":"***","list":["a",{"token":"***"}],"n":1}"#
+        );
+        assert!(data.get("list").get(0).is_borrowed());
+        assert!(!data.get("password").is_borrowed());
+
+        let mut keys = Vec::new();
+        data.map_keys_mut(|key| {
+            keys.push(key.to_string());
+            key.to_mut().make_ascii_uppercase();
+        });
+        assert_eq!(keys, ["password", "list", "token", "n"]);
+        assert_eq!(
+            data.to_string(),
+            r#"{"PASSWORD":"***","LIST":["a",{"TOKEN":"***"}],"N":1}"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn map_leaves_test() -> io::Result<()> {
+        let data: Value = serde_json::from_str(r#"{"a": [1, null, [], {}], "b\n": {"c": "x"}}"#)?;
+        let mut visited = Vec::new();
+        let mapped = data.map_leaves(|leaf| {
+            visited.push(leaf.to_string());
+            Value::Bool(leaf.is_null())
+        });
+        assert_eq!(visited, ["1", "null", "\"x\""]);
+        assert_eq!(
+            mapped.to_string(),
+            r#"{"a":[false,true,[],{}],"b\n":{"c":false}}"#
+        );
+        assert!(!mapped.is_borrowed());
+
+        let scalar = Value::Str("s".into()).map_leaves(|_| Value::Null);
+        assert_eq!(scalar, Value::Null);
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+use crate::escape::write_escaped_str;
+use crate::num::{Number, N};
+use crate::Value;
+
+/// How floats are written by [`Value::write_to`].
+///
+/// Integers are always written exactly. Non-finite floats are written as `null`, like `serde_json`
+/// does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FloatFormat {
+    /// The shortest representation which round-trips, like `serde_json` writes floats, e.g. `1.0`,
+    /// `0.1` or `1e+100`.
+    #[default]
+    Shortest,
+    /// The shortest digits which round-trip, but never in scientific notation, e.g. `1.0`, `0.1`
+    /// or `0.0000001` instead of `1e-7`. A fraction `.0` is appended to integral values, so they
+    /// are read back as floats.
+    Decimal,
+    /// A fixed number of decimal places, without scientific notation, e.g. `1.50` for two places.
+    /// Values are rounded to the nearest representation.
+    Fixed(usize),
+}
+
+/// Options for writing a `Value` as JSON with [`Value::write_to`].
+///
+/// The default configuration writes the same compact JSON as `Display` does.
+///
+/// # Examples
+///
+/// ```
+/// use serde_json_borrow::{FloatFormat, Value, WriteConfig};
+///
+/// let config = WriteConfig {
+///     sort_keys: true,
+///     float_format: FloatFormat::Fixed(2),
+/// };
+/// assert_eq!(config, WriteConfig::default().sort_keys(true).float_format(FloatFormat::Fixed(2)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct WriteConfig {
+    /// Whether to sort the entries of objects by key. Entries with duplicate keys keep their
+    /// relative order.
+    pub sort_keys: bool,
+    /// How to write floats.
+    pub float_format: FloatFormat,
+}
+
+impl WriteConfig {
+    /// Sets whether to sort the entries of objects by key.
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Sets how to write floats.
+    pub fn float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+}
+
+impl<'ctx> Value<'ctx> {
+    /// Writes the `Value` as compact JSON into `w`, formatted according to `config`.
+    ///
+    /// With `sort_keys`, keys are compared by their bytes, i.e. by Unicode code points. With the
+    /// `arbitrary_precision` feature, numbers which are kept verbatim are written unchanged by
+    /// [`FloatFormat::Shortest`] and converted to `f64` by the other formats.
+    ///
+    /// A `Value::Raw` is parsed and written according to `config` as well. If its text is not
+    /// valid JSON, it is written as a string holding the text, so the output is always valid JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_json_borrow::{FloatFormat, Value, WriteConfig};
+    ///
+    /// let value: Value = serde_json::from_str(r#"{"b": 1e-7, "a": [1, 2.5]}"#).unwrap();
+    ///
+    /// let mut out = String::new();
+    /// value.write_to(&mut out, &WriteConfig::default()).unwrap();
+    /// assert_eq!(out, r#"{"b":1e-7,"a":[1,2.5]}"#);
+    ///
+    /// let config = WriteConfig::default().sort_keys(true).float_format(FloatFormat::Fixed(2));
+    /// let mut out = String::new();
+    /// value.write_to(&mut out, &config).unwrap();
+    /// assert_eq!(out, r#"{"a":[1,2.50],"b":0.00}"#);
+    ///
+    /// let config = WriteConfig::default().float_format(FloatFormat::Decimal);
+    /// let mut out = String::new();
+    /// value.write_to(&mut out, &config).unwrap();
+    /// assert_eq!(out, r#"{"b":0.0000001,"a":[1,2.5]}"#);
+    /// ```
+    pub fn write_to<W: Write + ?Sized>(&self, w: &mut W, config: &WriteConfig) -> fmt::Result {
+        write_value(w, self, config)
+    }
+}
+
+/// How [`write_value`] writes numbers and raw values and orders object entries.
+pub(crate) trait JsonFormat {
+    /// Whether to pretty-print the output, indented with two spaces like `serde_json` does.
+    fn pretty(&self) -> bool {
+        false
+    }
+
+    fn write_number<W: Write + ?Sized>(&self, w: &mut W, n: &Number) -> fmt::Result;
+
+    #[cfg(feature = "raw_value")]
+    fn write_raw<W: Write + ?Sized>(&self, w: &mut W, text: &str) -> fmt::Result;
+
+    /// Reorders the entries of an object before they are written.
+    fn sort_entries(&self, _entries: &mut [&(Cow<'_, str>, Value<'_>)]) {}
+}
+
+impl JsonFormat for WriteConfig {
+    fn write_number<W: Write + ?Sized>(&self, w: &mut W, n: &Number) -> fmt::Result {
+        write_number(w, n, self.float_format)
+    }
+
+    #[cfg(feature = "raw_value")]
+    fn write_raw<W: Write + ?Sized>(&self, w: &mut W, text: &str) -> fmt::Result {
+        write_value(w, &crate::value::parse_raw_lossy(text), self)
+    }
+
+    fn sort_entries(&self, entries: &mut [&(Cow<'_, str>, Value<'_>)]) {
+        if self.sort_keys {
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+    }
+}
+
+/// Writes `value` as JSON in the given format.
+///
+/// Nested arrays and objects are written iteratively, so deeply nested values don't overflow the
+/// stack.
+pub(crate) fn write_value<W: Write + ?Sized, F: JsonFormat>(
+    w: &mut W,
+    value: &Value<'_>,
+    format: &F,
+) -> fmt::Result {
+    /// Pieces of output, processed from a stack instead of recursing into nested values.
+    enum Task<'a, 'ctx> {
+        Value(&'a Value<'ctx>, usize),
+        Key(&'a str),
+        Text(&'static str),
+        /// A line break followed by the indentation of the given level.
+        Indent(usize),
+    }
+
+    let pretty = format.pretty();
+    let mut stack = vec![Task::Value(value, 0)];
+    let mut tasks = Vec::new();
+    while let Some(task) = stack.pop() {
+        match task {
+            Task::Text(text) => w.write_str(text)?,
+            Task::Key(key) => {
+                write_escaped_str(w, key)?;
+                w.write_str(if pretty { ": " } else { ":" })?;
+            }
+            Task::Indent(level) => {
+                w.write_char('\n')?;
+                for _ in 0..level {
+                    w.write_str("  ")?;
+                }
+            }
+            Task::Value(value, level) => match value {
+                Value::Null => w.write_str("null")?,
+                Value::Bool(true) => w.write_str("true")?,
+                Value::Bool(false) => w.write_str("false")?,
+                Value::Number(n) => format.write_number(w, n)?,
+                Value::Str(s) => write_escaped_str(w, s)?,
+                #[cfg(feature = "raw_value")]
+                Value::Raw(text) => format.write_raw(w, text)?,
+                Value::Array(arr) if arr.is_empty() => w.write_str("[]")?,
+                Value::Array(arr) => {
+                    w.write_char('[')?;
+                    for (i, val) in arr.iter().enumerate() {
+                        if i > 0 {
+                            tasks.push(Task::Text(","));
+                        }
+                        if pretty {
+                            tasks.push(Task::Indent(level + 1));
+                        }
+                        tasks.push(Task::Value(val, level + 1));
+                    }
+                    if pretty {
+                        tasks.push(Task::Indent(level));
+                    }
+                    tasks.push(Task::Text("]"));
+                    stack.extend(tasks.drain(..).rev());
+                }
+                Value::Object(obj) if obj.is_empty() => w.write_str("{}")?,
+                Value::Object(obj) => {
+                    let mut entries: Vec<_> = obj.iter().collect();
+                    format.sort_entries(&mut entries);
+                    w.write_char('{')?;
+                    for (i, (key, val)) in entries.into_iter().enumerate() {
+                        if i > 0 {
+                            tasks.push(Task::Text(","));
+                        }
+                        if pretty {
+                            tasks.push(Task::Indent(level + 1));
+                        }
+                        tasks.push(Task::Key(key));
+                        tasks.push(Task::Value(val, level + 1));
+                    }
+                    if pretty {
+                        tasks.push(Task::Indent(level));
+                    }
+                    tasks.push(Task::Text("}"));
+                    stack.extend(tasks.drain(..).rev());
+                }
+            },
+        }
+    }
+    Ok(())
+}
+
+fn write_number<W: Write + ?Sized>(w: &mut W, n: &Number, format: FloatFormat) -> fmt::Result {
+    let f = match n.n {
+        N::Float(f) => f,
+        #[cfg(feature = "arbitrary_precision")]
+        N::Raw(_) if format != FloatFormat::Shortest && !n.is_i128() && !n.is_u128() => {
+            n.as_f64().unwrap_or(f64::NAN)
+        }
+        _ => return write!(w, "{}", n),
+    };
+    if !f.is_finite() {
+        return w.write_str("null");
+    }
+    match format {
+        FloatFormat::Shortest => write!(w, "{}", n),
+        FloatFormat::Decimal if is_integral(f) => write!(w, "{}.0", f),
+        FloatFormat::Decimal => write!(w, "{}", f),
+        FloatFormat::Fixed(places) => write!(w, "{:.*}", places, f),
+    }
+}
+
+/// Returns whether the finite float has no fractional part, without `f64::fract` which requires
+/// `std`.
+fn is_integral(f: f64) -> bool {
+    // Floats of at least 2^52 have no fractional bits.
+    f.abs() >= 4_503_599_627_370_496.0 || f == f as i64 as f64
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    fn write(input: &str, config: WriteConfig) -> io::Result<String> {
+        let value: Value = serde_json::from_str(input)?;
+        let mut out = String::new();
+        value.write_to(&mut out, &config).unwrap();
+        Ok(out)
+    }
+
+    #[test]
+    fn write_to_default_matches_display_test() -> io::Result<()> {
+        let input = r#"{"s": "a\"b\\c\n\u0001é", "n": [0, -1, 1.0, 1e100, 1.5e-9, 18446744073709551615], "o": {"z": null, "a": false}}"#;
+        let value: Value = serde_json::from_str(input)?;
+        assert_eq!(write(input, WriteConfig::default())?, value.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn write_to_sort_keys_test() -> io::Result<()> {
+        let config = WriteConfig::default().sort_keys(true);
+        assert_eq!(
+            write(
+                r#"{"b": {"y": 1, "x": 2}, "a": [{"d": 1, "c": 2}], "b": 0}"#,
+                config
+            )?,
+            r#"{"a":[{"c":2,"d":1}],"b":{"x":2,"y":1},"b":0}"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_to_float_format_test() -> io::Result<()> {
+        let input = "[1, -2, 0.5, 1.0, -0.0, 1e-7, 1.5e20, 2.675]";
+        let decimal = WriteConfig::default().float_format(FloatFormat::Decimal);
+        assert_eq!(
+            write(input, decimal)?,
+            "[1,-2,0.5,1.0,-0.0,0.0000001,150000000000000000000.0,2.675]"
+        );
+        let fixed = WriteConfig::default().float_format(FloatFormat::Fixed(2));
+        assert_eq!(
+            write(input, fixed)?,
+            "[1,-2,0.50,1.00,-0.00,0.00,150000000000000000000.00,2.67]"
+        );
+        let fixed = WriteConfig::default().float_format(FloatFormat::Fixed(0));
+        assert_eq!(write("[0.5, 1.5, -7.25]", fixed)?, "[0,2,-7]");
+
+        let mut out = String::new();
+        Value::Number(f64::NAN.into())
+            .write_to(
+                &mut out,
+                &WriteConfig::default().float_format(FloatFormat::Decimal),
+            )
+            .unwrap();
+        assert_eq!(out, "null");
+        Ok(())
+    }
+}
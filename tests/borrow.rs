@@ -0,0 +1,60 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+use serde_json_borrow::Value;
+
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    id: u64,
+    #[serde(borrow)]
+    payload: Value<'a>,
+    #[serde(borrow)]
+    extra: Option<Value<'a>>,
+}
+
+#[derive(Deserialize)]
+struct Batch<'a> {
+    #[serde(borrow)]
+    items: Vec<Value<'a>>,
+}
+
+#[test]
+fn borrowed_value_field_test() {
+    let input = String::from(
+        r#"{"id": 7, "payload": {"name": "alice", "tags": ["a", "b"]}, "extra": null}"#,
+    );
+    let envelope: Envelope = serde_json::from_str(&input).unwrap();
+
+    assert_eq!(envelope.id, 7);
+    assert!(envelope.payload.is_borrowed());
+    assert!(matches!(
+        envelope.payload.get("name"),
+        Value::Str(Cow::Borrowed("alice"))
+    ));
+    let name = envelope.payload.get("name").as_str().unwrap();
+    assert!(input.as_bytes().as_ptr_range().contains(&name.as_ptr()));
+    assert_eq!(envelope.extra, None);
+}
+
+#[test]
+fn borrowed_value_field_with_escapes_test() {
+    let input = r#"{"id": 1, "payload": {"plain": "x", "escaped": "a\nb"}, "extra": [true]}"#;
+    let envelope: Envelope = serde_json::from_str(input).unwrap();
+
+    assert!(matches!(
+        envelope.payload.get("plain"),
+        Value::Str(Cow::Borrowed("x"))
+    ));
+    assert!(matches!(envelope.payload.get("escaped"), Value::Str(Cow::Owned(s)) if s == "a\nb"));
+    assert_eq!(envelope.extra.unwrap().get(0), true);
+}
+
+#[test]
+fn borrowed_value_in_collection_test() {
+    let input = r#"{"items": [{"k": "v"}, "s", 1]}"#;
+    let batch: Batch = serde_json::from_str(input).unwrap();
+
+    assert_eq!(batch.items.len(), 3);
+    assert!(batch.items.iter().all(Value::is_borrowed));
+    assert_eq!(batch.items[0].get("k"), "v");
+}